@@ -0,0 +1,11 @@
+#![no_main]
+
+// A análise sintática e a verificação de tipos retornam a árvore ou um erro para qualquer
+// sequência de tokens válida, sem entrar em pânico
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|source: &str| {
+    if let Ok(tokens) = compilador2::tokenize(source) {
+        let _ = compilador2::parse(&tokens);
+    }
+});
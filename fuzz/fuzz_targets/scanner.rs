@@ -0,0 +1,16 @@
+#![no_main]
+
+// A análise léxica retorna tokens ou um erro para qualquer texto, sem entrar em pânico, e cada
+// lexema é o trecho da entrada indicado pela sua posição
+use compilador2::Scanner;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|source: &str| {
+    for lexeme in Scanner::new(source) {
+        let Ok(lexeme) = lexeme else {
+            break;
+        };
+        let span = lexeme.get_span();
+        assert_eq!(&source[span.start..span.end], lexeme.get_lexeme());
+    }
+});
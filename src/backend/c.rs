@@ -0,0 +1,1011 @@
+use crate::common::types::Type;
+use crate::syntactic::ast::{
+    Declaration, Expression, ExpressionKind, Procedure, Program, Statement,
+};
+use crate::syntactic::constant::Value;
+use std::collections::HashMap;
+use std::mem;
+use std::rc::Rc;
+
+// Funções de execução incluídas no início de todo programa gerado
+const RUNTIME: &str = include_str!("runtime.c");
+
+// Nomes visíveis no programa, em uma unit ou em um procedimento, como os escopos do interpretador
+#[derive(Default)]
+struct Scope {
+    frame: Option<String>, // Registro de ativação dos procedimentos, os escopos globais não têm
+    prefix: String,        // Prefixo das variáveis globais em C
+    variables: HashMap<String, Type>,
+    constants: HashMap<String, Value>,
+    types: HashMap<String, Type>,
+    procedures: HashMap<String, (String, Rc<Procedure>)>, // Nome da função em C e a declaração
+}
+
+// Tradução da árvore sintática verificada para C99. Os ordinais são pas_integer, os arrays e
+// registros são estruturas copiadas por valor, e cada procedimento recebe o registro de ativação
+// de quem o declarou, por onde acessa as variáveis dos procedimentos que o envolvem
+#[derive(Default)]
+struct Generator {
+    scopes: Vec<Scope>,
+    structures: Vec<(Type, String)>, // Arrays e registros já declarados
+    cells: Vec<(Type, String)>,      // Variáveis dinâmicas, pelo tipo apontado
+    enumerations: Vec<(Type, String)>, // Descritores com os nomes das constantes
+    initialized: Vec<String>,        // Units já traduzidas
+    entries: Vec<String>,            // Corpos das units e do programa, chamados por main
+    names: usize,
+    // Trechos do arquivo, na ordem em que o C precisa das declarações
+    forward: String,
+    definitions: String,
+    cell_definitions: String,
+    frames: String,
+    globals: String,
+    prototypes: String,
+    functions: String,
+    // Função em geração
+    code: String,
+    indent: usize,
+    loops: Vec<(Option<String>, bool)>, // Rótulo do continue de cada repeat e se ele foi usado
+}
+
+// Programa em C com o mesmo comportamento do interpretador, inclusive as mensagens de erro
+pub fn generate(program: &Program) -> String {
+    let mut generator = Generator::default();
+    for unit in &program.units {
+        generator.unit(unit);
+    }
+    generator.global(program);
+    generator.finish(&program.name)
+}
+
+fn declaration(c_type: &str, name: &str) -> String {
+    if c_type.ends_with('*') {
+        format!("{c_type}{name}")
+    } else {
+        format!("{c_type} {name}")
+    }
+}
+
+fn integer(value: i64) -> String {
+    if value < 0 {
+        format!("({value}LL)")
+    } else {
+        format!("{value}LL")
+    }
+}
+
+fn real(value: f64) -> String {
+    match value {
+        value if value.is_infinite() && value > 0.0 => "HUGE_VAL".to_string(),
+        value if value.is_infinite() => "(-HUGE_VAL)".to_string(),
+        value if value.is_sign_negative() => format!("({value:?})"),
+        value => format!("{value:?}"),
+    }
+}
+
+// Literal de texto do C, os bytes fora do ASCII visível são escritos em octal
+fn quote(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for byte in text.bytes() {
+        match byte {
+            b'"' | b'\\' | b'?' => {
+                quoted.push('\\');
+                quoted.push(byte as char);
+            }
+            b' '..=b'~' => quoted.push(byte as char),
+            _ => quoted.push_str(&format!("\\{byte:03o}")),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn variables(declarations: &[Declaration]) -> Vec<(String, Type)> {
+    declarations
+        .iter()
+        .filter_map(|declaration| match declaration {
+            Declaration::Variable(name, variable_type) => {
+                Some((name.to_owned(), variable_type.to_owned()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+impl Generator {
+    fn unique(&mut self, prefix: &str) -> String {
+        self.names += 1;
+        format!("{prefix}{}", self.names)
+    }
+
+    fn line(&mut self, text: &str) {
+        for _ in 0..=self.indent {
+            self.code.push_str("    ");
+        }
+        self.code.push_str(text);
+        self.code.push('\n');
+    }
+
+    fn open(&mut self, text: &str) {
+        self.line(text);
+        self.indent += 1;
+    }
+
+    fn reopen(&mut self, text: &str) {
+        self.indent -= 1;
+        self.open(text);
+    }
+
+    fn close(&mut self) {
+        self.indent -= 1;
+        self.line("}");
+    }
+
+    fn function(&mut self, header: &str) {
+        let body = mem::take(&mut self.code);
+        self.functions
+            .push_str(&format!("{header} {{\n{body}}}\n\n"));
+    }
+
+    fn finish(self, name: &str) -> String {
+        let mut main = "int main(void) {\n".to_string();
+        for entry in &self.entries {
+            main.push_str(&format!("    {entry}();\n"));
+        }
+        main.push_str("    fflush(stdout);\n    return 0;\n}\n");
+        let sections = [
+            format!("// Programa {name}, gerado pelo compilador2\n{RUNTIME}"),
+            self.forward,
+            self.definitions,
+            self.cell_definitions,
+            self.frames,
+            self.globals,
+            self.prototypes,
+            self.functions,
+            main,
+        ];
+        sections
+            .iter()
+            .filter(|section| !section.is_empty())
+            .map(|section| section.trim_end().to_string() + "\n")
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    // Busca a partir do escopo atual, como a busca do interpretador pelos escopos de declaração
+    fn lookup<T>(&self, get: impl Fn(&Scope) -> Option<T>) -> Option<(usize, T)> {
+        self.scopes
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(index, scope)| get(scope).map(|found| (index, found)))
+    }
+
+    // Registro de ativação do escopo, seguindo os registros de quem declarou cada procedimento
+    fn frame(&self, index: usize) -> String {
+        format!("f{}", "->up".repeat(self.scopes.len() - 1 - index))
+    }
+
+    // Tipos nomeados apontados por ponteiros são resolvidos para a sua definição
+    fn resolve(&self, value_type: &Type) -> Type {
+        let Type::Named(name) = value_type else {
+            return value_type.to_owned();
+        };
+        match self.lookup(|scope| scope.types.get(name).cloned()) {
+            Some((_, definition)) => self.resolve(&definition),
+            None => Type::Integer,
+        }
+    }
+
+    fn range(&self, value_type: &Type) -> Option<(i64, i64)> {
+        self.resolve(value_type).range()
+    }
+
+    fn c_type(&mut self, value_type: &Type) -> String {
+        let resolved = self.resolve(value_type);
+        match resolved.host() {
+            Type::Real => "pas_real".to_string(),
+            Type::String => "pas_string".to_string(),
+            Type::Set(_) | Type::EmptySet => "pas_set".to_string(),
+            Type::Array(..) | Type::Record(..) => self.structure(&resolved),
+            Type::Pointer(target) => format!("{} *", self.cell(target)),
+            Type::Nil => "void *".to_string(),
+            _ => "pas_integer".to_string(),
+        }
+    }
+
+    // Arrays e registros são estruturas, os elementos do array ficam no campo e
+    fn structure(&mut self, value_type: &Type) -> String {
+        if let Some((_, name)) = self.structures.iter().find(|(t, _)| t == value_type) {
+            return name.to_owned();
+        }
+        let name = self.unique(match value_type {
+            Type::Array(..) => "a",
+            _ => "r",
+        });
+        self.structures
+            .push((value_type.to_owned(), name.to_owned()));
+        let mut fields = vec![];
+        match value_type {
+            Type::Array(index, element) => {
+                let (low, high) = self.range(index).unwrap_or((0, -1));
+                let element = self.c_type(element);
+                let length = (high - low + 1).max(1);
+                fields.push(declaration(&element, &format!("e[{length}]")));
+            }
+            Type::Record(record) => {
+                for (field, field_type) in record {
+                    let field_type = self.c_type(field_type);
+                    fields.push(declaration(&field_type, &format!("v_{field}")));
+                }
+            }
+            _ => {}
+        }
+        if fields.is_empty() {
+            fields.push("char empty".to_string());
+        }
+        let fields: String = fields
+            .iter()
+            .map(|field| format!("    {field};\n"))
+            .collect();
+        self.definitions
+            .push_str(&format!("typedef struct {{\n{fields}}} {name};\n\n"));
+        name
+    }
+
+    // Variável dinâmica do tipo apontado. A definição fica depois de todas as estruturas, o que
+    // permite registros com ponteiros para eles mesmos
+    fn cell(&mut self, target: &Type) -> String {
+        let target = self.resolve(target);
+        if let Some((_, name)) = self.cells.iter().find(|(t, _)| *t == target) {
+            return name.to_owned();
+        }
+        let name = self.unique("c");
+        self.cells.push((target.to_owned(), name.to_owned()));
+        self.forward
+            .push_str(&format!("typedef struct {name} {name};\n"));
+        let value = self.c_type(&target);
+        self.cell_definitions.push_str(&format!(
+            "struct {name} {{\n    pas_cell cell;\n    {};\n}};\n\n",
+            declaration(&value, "value")
+        ));
+        name
+    }
+
+    // Descritor usado para mostrar um ordinal do tipo
+    fn descriptor(&mut self, value_type: &Type) -> String {
+        let resolved = self.resolve(value_type);
+        let enumeration = match resolved.host() {
+            Type::Boolean => return "&pas_boolean_type".to_string(),
+            Type::Char => return "&pas_char_type".to_string(),
+            enumeration @ Type::Enumeration(..) => enumeration.to_owned(),
+            _ => return "&pas_integer_type".to_string(),
+        };
+        if let Some((_, name)) = self.enumerations.iter().find(|(t, _)| *t == enumeration) {
+            return format!("&{name}");
+        }
+        let name = self.unique("e");
+        let Type::Enumeration(_, constants) = &enumeration else {
+            unreachable!();
+        };
+        let names: Vec<String> = constants.iter().map(|constant| quote(constant)).collect();
+        self.definitions.push_str(&format!(
+            "static const char *const {name}_names[] = {{{}}};\n\
+             static const pas_type {name} = {{PAS_ENUMERATION, {}, {name}_names}};\n\n",
+            names.join(", "),
+            constants.len()
+        ));
+        self.enumerations.push((enumeration, name.to_owned()));
+        format!("&{name}")
+    }
+
+    fn declare(&mut self, declarations: &[Declaration]) {
+        for declaration in declarations {
+            let function = match declaration {
+                Declaration::Procedure(procedure) => {
+                    format!("{}_{}", self.unique("p"), procedure.name)
+                }
+                _ => String::new(),
+            };
+            let scope = self.scopes.last_mut().unwrap();
+            match declaration {
+                Declaration::Constant(name, value) => {
+                    scope.constants.insert(name.to_owned(), value.to_owned());
+                }
+                Declaration::Type(name, definition) => {
+                    scope.types.insert(name.to_owned(), definition.to_owned());
+                }
+                Declaration::Enumeration(name, constants) => {
+                    let definition = Type::Enumeration(name.to_owned(), constants.to_owned());
+                    for (position, constant) in constants.iter().enumerate() {
+                        let value = Value::Enumerated(
+                            constant.to_owned(),
+                            definition.to_owned(),
+                            position as i64,
+                        );
+                        scope.constants.insert(constant.to_owned(), value);
+                    }
+                    scope.types.insert(name.to_owned(), definition);
+                }
+                Declaration::Variable(name, variable_type) => {
+                    scope
+                        .variables
+                        .insert(name.to_owned(), variable_type.to_owned());
+                }
+                Declaration::Procedure(procedure) => {
+                    let procedure = Rc::clone(procedure);
+                    scope
+                        .procedures
+                        .insert(procedure.name.to_owned(), (function, procedure));
+                }
+            }
+        }
+    }
+
+    // Cada unit é traduzida uma única vez, depois das units que ela usa
+    fn unit(&mut self, unit: &Program) {
+        if self.initialized.contains(&unit.name) {
+            return;
+        }
+        self.initialized.push(unit.name.to_owned());
+        for used in &unit.units {
+            self.unit(used);
+        }
+        self.global(unit);
+    }
+
+    // Escopo de uma unit ou do programa, que continua visível para as units e o programa seguintes.
+    // As variáveis são globais do C e o corpo é uma função chamada por main
+    fn global(&mut self, program: &Program) {
+        let prefix = format!("g{}_", self.scopes.len());
+        self.scopes.push(Scope {
+            prefix: prefix.to_owned(),
+            ..Scope::default()
+        });
+        self.declare(&program.declarations);
+        let variables = variables(&program.declarations);
+        for (name, variable_type) in &variables {
+            let c_type = self.c_type(variable_type);
+            let global = declaration(&c_type, &format!("{prefix}{name}"));
+            self.globals.push_str(&format!("static {global};\n"));
+        }
+        self.procedures(&program.declarations);
+
+        self.line("pas_depth++;");
+        for (name, variable_type) in &variables {
+            self.initial(&format!("{prefix}{name}"), variable_type);
+        }
+        self.block(&program.body);
+        let function = format!("{}_{}", self.unique("s"), program.name);
+        self.function(&format!("static void {function}(void)"));
+        self.entries.push(function);
+    }
+
+    fn procedures(&mut self, declarations: &[Declaration]) {
+        for declaration in declarations {
+            if let Declaration::Procedure(procedure) = declaration {
+                self.procedure(procedure);
+            }
+        }
+    }
+
+    // Os parâmetros e as variáveis locais ficam no registro de ativação, que aponta para o de quem
+    // declarou o procedimento quando ele está dentro de outro
+    fn procedure(&mut self, procedure: &Procedure) {
+        let scope = self.scopes.last().unwrap();
+        let (function, _) = scope.procedures[&procedure.name].to_owned();
+        let parent = scope.frame.to_owned();
+        let frame = format!("f{}", &function[1..]);
+        let parameters: HashMap<String, Type> = procedure.parameters.iter().cloned().collect();
+        self.scopes.push(Scope {
+            frame: Some(frame.to_owned()),
+            variables: parameters,
+            ..Scope::default()
+        });
+        self.declare(&procedure.declarations);
+
+        let mut fields = vec![];
+        let mut parameters = vec![];
+        if let Some(parent) = &parent {
+            fields.push(format!("{parent} *up"));
+            parameters.push(format!("{parent} *up"));
+        }
+        for (index, (name, parameter_type)) in procedure.parameters.iter().enumerate() {
+            let c_type = self.c_type(parameter_type);
+            fields.push(declaration(&c_type, &format!("v_{name}")));
+            parameters.push(declaration(&c_type, &format!("a{index}")));
+        }
+        let locals = variables(&procedure.declarations);
+        for (name, variable_type) in &locals {
+            let c_type = self.c_type(variable_type);
+            fields.push(declaration(&c_type, &format!("v_{name}")));
+        }
+        let empty = fields.is_empty();
+        if empty {
+            fields.push("char empty".to_string());
+        }
+        let fields: String = fields
+            .iter()
+            .map(|field| format!("    {field};\n"))
+            .collect();
+        self.forward
+            .push_str(&format!("typedef struct {frame} {frame};\n"));
+        self.frames
+            .push_str(&format!("struct {frame} {{\n{fields}}};\n\n"));
+        self.procedures(&procedure.declarations);
+
+        self.line(&format!("{frame} activation, *f = &activation;"));
+        if empty {
+            self.line("(void) f;");
+        }
+        if parent.is_some() {
+            self.line("f->up = up;");
+        }
+        for (index, (name, _)) in procedure.parameters.iter().enumerate() {
+            self.line(&format!("f->v_{name} = a{index};"));
+        }
+        for (name, variable_type) in &locals {
+            self.initial(&format!("f->v_{name}"), variable_type);
+        }
+        self.block(&procedure.body);
+
+        let parameters = match parameters.is_empty() {
+            true => "void".to_string(),
+            false => parameters.join(", "),
+        };
+        let header = format!("static void {function}({parameters})");
+        self.prototypes.push_str(&format!("{header};\n"));
+        self.function(&header);
+        self.scopes.pop();
+    }
+
+    // Valor inicial de uma variável do tipo, o mesmo do interpretador
+    fn initial(&mut self, place: &str, value_type: &Type) {
+        let value = match self.resolve(value_type) {
+            Type::Real => "0.0".to_string(),
+            Type::String => "\"\"".to_string(),
+            Type::Set(_) => "pas_empty()".to_string(),
+            Type::Subrange(_, low, _) => integer(low),
+            Type::Pointer(_) => "NULL".to_string(),
+            Type::Array(index, element) => {
+                let (low, high) = self.range(&index).unwrap_or((0, -1));
+                let counter = self.unique("i");
+                self.open(&format!(
+                    "for (pas_integer {counter} = 0; {counter} < {}; {counter}++) {{",
+                    (high - low + 1).max(0)
+                ));
+                self.initial(&format!("{place}.e[{counter}]"), &element);
+                self.close();
+                return;
+            }
+            Type::Record(fields) => {
+                for (field, field_type) in &fields {
+                    self.initial(&format!("{place}.v_{field}"), field_type);
+                }
+                return;
+            }
+            _ => "0".to_string(),
+        };
+        self.line(&format!("{place} = {value};"));
+    }
+
+    fn temporary(&mut self, c_type: &str, value: &str) -> String {
+        let name = self.unique("t");
+        self.line(&format!("{} = {value};", declaration(c_type, &name)));
+        name
+    }
+
+    fn typed_temporary(&mut self, value_type: &Type, value: &str) -> String {
+        let c_type = self.c_type(value_type);
+        self.temporary(&c_type, value)
+    }
+
+    fn block(&mut self, statements: &[Statement]) {
+        for statement in statements {
+            self.statement(statement);
+        }
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Assignment(target, value) => {
+                let result = self.expression(value);
+                let place = self.place(target);
+                let result = self.coerce(
+                    result,
+                    &value.value_type,
+                    &target.value_type,
+                    target.line,
+                    target.column,
+                );
+                self.line(&format!("{place} = {result};"));
+            }
+            Statement::Call(name, arguments, line, column) => {
+                self.call(name, arguments, *line, *column);
+            }
+            Statement::If(condition, then, otherwise) => {
+                let condition = self.expression(condition);
+                self.open(&format!("if ({condition}) {{"));
+                self.statement(then);
+                if let Some(otherwise) = otherwise {
+                    self.reopen("} else {");
+                    self.statement(otherwise);
+                }
+                self.close();
+            }
+            Statement::While(condition, body) => {
+                self.open("for (;;) {");
+                let condition = self.expression(condition);
+                self.line(&format!("if (!{condition}) break;"));
+                self.loops.push((None, false));
+                self.statement(body);
+                self.loops.pop();
+                self.close();
+            }
+            Statement::Repeat(body, condition) => {
+                // O continue do C voltaria ao início do corpo, o do repeat vai para a condição
+                let label = self.unique("r");
+                self.open("for (;;) {");
+                self.loops.push((Some(label.to_owned()), false));
+                self.block(body);
+                if let Some((_, true)) = self.loops.pop() {
+                    self.line(&format!("{label}:;"));
+                }
+                let condition = self.expression(condition);
+                self.line(&format!("if ({condition}) break;"));
+                self.close();
+            }
+            Statement::For {
+                variable,
+                start,
+                end,
+                downto,
+                body,
+            } => {
+                let first = self.expression(start);
+                let last = self.expression(end);
+                let last = self.temporary("pas_integer", &last);
+                let place = self.place(variable);
+                let target = self.temporary("pas_integer *", &format!("&{place}"));
+                let ordinal = self.unique("o");
+                let (comparison, step) = if *downto { (">=", "--") } else { ("<=", "++") };
+                self.open(&format!(
+                    "for (pas_integer {ordinal} = {first}; {ordinal} {comparison} {last}; {ordinal}{step}) {{"
+                ));
+                let value = self.coerce(
+                    ordinal,
+                    &start.value_type,
+                    &variable.value_type,
+                    variable.line,
+                    variable.column,
+                );
+                self.line(&format!("*{target} = {value};"));
+                self.loops.push((None, false));
+                self.statement(body);
+                self.loops.pop();
+                self.close();
+            }
+            Statement::Case {
+                selector,
+                elements,
+                otherwise,
+            } => {
+                let selected = self.expression(selector);
+                let selected = self.temporary("pas_integer", &selected);
+                for (index, (labels, body)) in elements.iter().enumerate() {
+                    let condition: Vec<String> = labels
+                        .iter()
+                        .map(|(low, high)| match low == high {
+                            true => format!("{selected} == {}", integer(*low)),
+                            false => format!(
+                                "({selected} >= {} && {selected} <= {})",
+                                integer(*low),
+                                integer(*high)
+                            ),
+                        })
+                        .collect();
+                    let condition = condition.join(" || ");
+                    match index {
+                        0 => self.open(&format!("if ({condition}) {{")),
+                        _ => self.reopen(&format!("}} else if ({condition}) {{")),
+                    }
+                    self.statement(body);
+                }
+                if let Some(otherwise) = otherwise {
+                    match elements.is_empty() {
+                        true => self.open("{"),
+                        false => self.reopen("} else {"),
+                    }
+                    self.block(otherwise);
+                }
+                if !elements.is_empty() || otherwise.is_some() {
+                    self.close();
+                }
+            }
+            Statement::Block(statements) => self.block(statements),
+            Statement::Break => self.line("break;"),
+            Statement::Continue => match self.loops.last_mut() {
+                Some((Some(label), used)) => {
+                    *used = true;
+                    let label = label.to_owned();
+                    self.line(&format!("goto {label};"));
+                }
+                _ => self.line("continue;"),
+            },
+            Statement::Exit => self.line("return;"),
+        }
+    }
+
+    fn call(&mut self, name: &str, arguments: &[Expression], line: usize, column: usize) {
+        let found = self.lookup(|scope| scope.procedures.get(name).cloned());
+        let Some((declared, (function, procedure))) = found else {
+            return self.builtin(name, arguments, line, column);
+        };
+        // Os argumentos são avaliados depois da verificação da profundidade, como no interpretador
+        self.line(&format!(
+            "pas_check_depth({}, {line}, {column});",
+            quote(name)
+        ));
+        let mut values = vec![];
+        if self.scopes[declared].frame.is_some() {
+            values.push(self.frame(declared));
+        }
+        for ((_, parameter_type), argument) in procedure.parameters.iter().zip(arguments) {
+            let value = self.expression(argument);
+            let value = self.coerce(value, &argument.value_type, parameter_type, line, column);
+            values.push(value);
+        }
+        self.line("pas_depth++;");
+        self.line(&format!("{function}({});", values.join(", ")));
+        self.line("pas_depth--;");
+    }
+
+    // Procedimentos pré-definidos new, dispose, write e writeln
+    fn builtin(&mut self, name: &str, arguments: &[Expression], line: usize, column: usize) {
+        match name {
+            "write" | "writeln" => {
+                // Como no interpretador, nada é escrito se a avaliação de um argumento falhar
+                let values: Vec<String> = arguments
+                    .iter()
+                    .map(|argument| self.expression(argument))
+                    .collect();
+                for (value, argument) in values.iter().zip(arguments) {
+                    let write = match self.resolve(&argument.value_type).host() {
+                        Type::Char => format!("pas_write_char({value});"),
+                        Type::String => format!("pas_write_text({value});"),
+                        Type::Real => format!("pas_write_real({value});"),
+                        _ => format!(
+                            "pas_write_ordinal({value}, {});",
+                            self.descriptor(&argument.value_type)
+                        ),
+                    };
+                    self.line(&write);
+                }
+                if name == "writeln" {
+                    self.line("putchar('\\n');");
+                }
+            }
+            "new" => {
+                let place = self.place(&arguments[0]);
+                let target = match self.resolve(&arguments[0].value_type) {
+                    Type::Pointer(target) => *target,
+                    _ => Type::Integer,
+                };
+                let cell = self.cell(&target);
+                let pointer =
+                    self.temporary(&format!("{cell} *"), &format!("pas_new(sizeof({cell}))"));
+                self.initial(&format!("{pointer}->value"), &target);
+                self.line(&format!("{place} = {pointer};"));
+            }
+            _ => {
+                let pointer = self.place(&arguments[0]);
+                self.line(&format!("pas_dispose({pointer}, {line}, {column});"));
+            }
+        }
+    }
+
+    // Conversões implícitas da atribuição e a verificação dos limites de subintervalos e dos
+    // elementos de conjuntos
+    fn coerce(
+        &mut self,
+        value: String,
+        from: &Type,
+        to: &Type,
+        line: usize,
+        column: usize,
+    ) -> String {
+        let from = self.resolve(from);
+        match (self.resolve(to), from.host()) {
+            (Type::Real, Type::Real) => value,
+            (Type::Real, _) => format!("(pas_real) {value}"),
+            (Type::String, Type::Char) => {
+                self.temporary("pas_string", &format!("pas_char_text({value})"))
+            }
+            (to @ Type::Subrange(_, low, high), _) => {
+                let descriptor = self.descriptor(&from);
+                self.temporary(
+                    "pas_integer",
+                    &format!(
+                        "pas_range({value}, {}, {}, {descriptor}, {}, {line}, {column})",
+                        integer(low),
+                        integer(high),
+                        quote(&to.to_string())
+                    ),
+                )
+            }
+            (Type::Set(base), _) => match self.range(&base) {
+                Some((low, high)) if low > 0 || high < 255 => {
+                    let descriptor = self.descriptor(&base);
+                    self.temporary(
+                        "pas_set",
+                        &format!(
+                            "pas_check_set({value}, {}, {}, {descriptor}, {}, {line}, {column})",
+                            integer(low),
+                            integer(high),
+                            quote(&base.to_string())
+                        ),
+                    )
+                }
+                _ => value,
+            },
+            _ => value,
+        }
+    }
+
+    // Texto de um operando textual, os caracteres viram textos de um caractere
+    fn text(&mut self, value: String, value_type: &Type) -> String {
+        match self.resolve(value_type).host() {
+            Type::Char => self.temporary("pas_string", &format!("pas_char_text({value})")),
+            _ => value,
+        }
+    }
+
+    fn literal(&self, value: &Value) -> String {
+        match value {
+            Value::Integer(value) | Value::Enumerated(_, _, value) => integer(*value),
+            Value::Real(value) => real(*value),
+            Value::Boolean(value) => integer(*value as i64),
+            Value::Char(value) => integer(*value as i64),
+            Value::Text(value) => quote(value),
+        }
+    }
+
+    // Expressão em C sem efeitos colaterais. As verificações que podem falhar são emitidas antes
+    // como comandos, na ordem de avaliação do interpretador, e os resultados intermediários ficam
+    // em temporárias
+    fn expression(&mut self, expression: &Expression) -> String {
+        let (line, column) = (expression.line, expression.column);
+        match &expression.kind {
+            ExpressionKind::Literal(value) => self.literal(value),
+            ExpressionKind::Nil => "NULL".to_string(),
+            ExpressionKind::Variable(_)
+            | ExpressionKind::Index(..)
+            | ExpressionKind::Field(..)
+            | ExpressionKind::Dereference(..) => self.place(expression),
+            ExpressionKind::Call(name, arguments) => {
+                self.function_call(name, arguments, expression)
+            }
+            ExpressionKind::Unary(operator, operand) => {
+                let value = self.expression(operand);
+                let value = match (operator.as_str(), self.resolve(&operand.value_type).host()) {
+                    ("not", _) => format!("!{value}"),
+                    (_, Type::Real) => format!("-{value}"),
+                    _ => format!("pas_check_integer(-{value}, {line}, {column})"),
+                };
+                self.typed_temporary(&expression.value_type, &value)
+            }
+            ExpressionKind::Binary(operator, left, right) => {
+                self.binary(operator, left, right, expression)
+            }
+            ExpressionKind::Set(elements) => {
+                let set = self.temporary("pas_set", "pas_empty()");
+                for (low, high) in elements {
+                    let low = self.expression(low);
+                    let high = match high {
+                        Some(high) => self.expression(high),
+                        None => low.to_owned(),
+                    };
+                    self.line(&format!(
+                        "{set} = pas_include({set}, {low}, {high}, {line}, {column});"
+                    ));
+                }
+                set
+            }
+            ExpressionKind::Conversion(operand) => {
+                let value = self.expression(operand);
+                self.coerce(
+                    value,
+                    &operand.value_type,
+                    &expression.value_type,
+                    line,
+                    column,
+                )
+            }
+        }
+    }
+
+    // Variáveis, elementos, campos e variáveis dinâmicas, que também podem receber atribuições
+    fn place(&mut self, expression: &Expression) -> String {
+        let (line, column) = (expression.line, expression.column);
+        match &expression.kind {
+            ExpressionKind::Variable(name) => {
+                let found = self.lookup(|scope| match scope.variables.contains_key(name) {
+                    true => Some(None),
+                    false => scope.constants.get(name).cloned().map(Some),
+                });
+                match found {
+                    Some((index, None)) => match self.scopes[index].frame {
+                        Some(_) => format!("{}->v_{name}", self.frame(index)),
+                        None => format!("{}{name}", self.scopes[index].prefix),
+                    },
+                    Some((_, Some(value))) => self.literal(&value),
+                    None => unreachable!("Unknown variable in a checked program."),
+                }
+            }
+            ExpressionKind::Index(array, index) => {
+                let array_place = self.place(array);
+                let position = self.expression(index);
+                let index_type = match self.resolve(&array.value_type) {
+                    Type::Array(index_type, _) => *index_type,
+                    _ => Type::Unknown,
+                };
+                let (low, high) = self.range(&index_type).unwrap_or((0, -1));
+                let descriptor = self.descriptor(&index.value_type);
+                let offset = self.temporary(
+                    "pas_integer",
+                    &format!(
+                        "pas_index({position}, {}, {}, {descriptor}, {}, {line}, {column})",
+                        integer(low),
+                        (high - low + 1).max(0),
+                        quote(&index_type.to_string())
+                    ),
+                );
+                format!("{array_place}.e[{offset}]")
+            }
+            ExpressionKind::Field(record, name) => format!("{}.v_{name}", self.place(record)),
+            ExpressionKind::Dereference(pointer) => {
+                let pointer = self.expression(pointer);
+                self.line(&format!("pas_check_pointer({pointer}, {line}, {column});"));
+                format!("{pointer}->value")
+            }
+            _ => self.expression(expression),
+        }
+    }
+
+    fn binary(
+        &mut self,
+        operator: &str,
+        left: &Expression,
+        right: &Expression,
+        expression: &Expression,
+    ) -> String {
+        let (line, column) = (expression.line, expression.column);
+        // Os operadores lógicos avaliam o segundo operando apenas quando necessário
+        if operator == "and" || operator == "or" {
+            let value = self.expression(left);
+            let result = self.temporary("pas_integer", &value);
+            match operator {
+                "and" => self.open(&format!("if ({result}) {{")),
+                _ => self.open(&format!("if (!{result}) {{")),
+            }
+            let value = self.expression(right);
+            self.line(&format!("{result} = {value};"));
+            self.close();
+            return result;
+        }
+
+        let left_type = self.resolve(&left.value_type).host().to_owned();
+        let right_type = self.resolve(&right.value_type).host().to_owned();
+        let (a, b) = (self.expression(left), self.expression(right));
+        let value = match operator {
+            "+" | "-" | "*" | "/" => match self.resolve(&expression.value_type).host() {
+                Type::Set(_) | Type::EmptySet => {
+                    let function = match operator {
+                        "+" => "pas_union",
+                        "-" => "pas_difference",
+                        _ => "pas_intersection",
+                    };
+                    format!("{function}({a}, {b})")
+                }
+                Type::String => {
+                    let a = self.text(a, &left_type);
+                    let b = self.text(b, &right_type);
+                    format!("pas_concat({a}, {b})")
+                }
+                Type::Real if operator == "/" => {
+                    format!("(pas_real) {a} / pas_real_divisor({b}, {line}, {column})")
+                }
+                Type::Real => format!("(pas_real) {a} {operator} {b}"),
+                // A verificação de tipos considera integer o resultado da divisão entre inteiros
+                _ if operator == "/" => format!(
+                    "pas_check_integer({a} / pas_divisor({b}, {line}, {column}), {line}, {column})"
+                ),
+                _ => format!("pas_check_integer({a} {operator} {b}, {line}, {column})"),
+            },
+            "in" => format!("pas_in({a}, {b})"),
+            _ => self.comparison(operator, (a, &left_type), (b, &right_type)),
+        };
+        self.typed_temporary(&expression.value_type, &value)
+    }
+
+    fn comparison(
+        &mut self,
+        operator: &str,
+        left: (String, &Type),
+        right: (String, &Type),
+    ) -> String {
+        let ((a, left_type), (b, right_type)) = (left, right);
+        let either = |matches: fn(&Type) -> bool| matches(left_type) || matches(right_type);
+        let c_operator = match operator {
+            "=" => "==",
+            "<>" => "!=",
+            operator => operator,
+        };
+        if either(|t| matches!(t, Type::Set(_) | Type::EmptySet)) {
+            // Igualdade, continência (<=) e contenção (>=)
+            return match operator {
+                "=" => format!("pas_set_equal({a}, {b})"),
+                "<>" => format!("!pas_set_equal({a}, {b})"),
+                "<=" => format!("pas_subset({a}, {b})"),
+                _ => format!("pas_subset({b}, {a})"),
+            };
+        }
+        if either(|t| matches!(t, Type::Pointer(_) | Type::Nil)) {
+            return format!("(void *) {a} {c_operator} (void *) {b}");
+        }
+        if either(|t| *t == Type::String) {
+            let a = self.text(a, left_type);
+            let b = self.text(b, right_type);
+            return format!("pas_compare({a}, {b}) {c_operator} 0");
+        }
+        format!("{a} {c_operator} {b}")
+    }
+
+    // Funções pré-definidas, a sobrecarga é escolhida pelo tipo do argumento
+    fn function_call(
+        &mut self,
+        name: &str,
+        arguments: &[Expression],
+        expression: &Expression,
+    ) -> String {
+        let (line, column) = (expression.line, expression.column);
+        let mut values = vec![];
+        for argument in arguments {
+            let value = self.expression(argument);
+            // Os parâmetros de length, copy e pos são textos, que também aceitam caracteres
+            match name {
+                "length" | "copy" | "pos" => values.push(self.text(value, &argument.value_type)),
+                _ => values.push(value),
+            }
+        }
+        let argument_type = arguments
+            .first()
+            .map(|argument| self.resolve(&argument.value_type))
+            .unwrap_or_default();
+        let real = *argument_type.host() == Type::Real;
+        let x = values.first().cloned().unwrap_or_default();
+        let position = format!("{line}, {column}");
+        let value = match name {
+            "abs" if real => format!("fabs({x})"),
+            "abs" => format!("pas_check_integer(llabs({x}), {position})"),
+            "sqr" if real => format!("{x} * {x}"),
+            "sqr" => format!("pas_check_integer({x} * {x}, {position})"),
+            "sqrt" => format!("sqrt(pas_check_argument({x}, !({x} < 0.0), \"sqrt\", {position}))"),
+            "ln" => format!("log(pas_check_argument({x}, !({x} <= 0.0), \"ln\", {position}))"),
+            "sin" | "cos" | "exp" => format!("{name}({x})"),
+            "trunc" | "round" => format!("pas_truncate({name}({x}), {x}, \"{name}\", {position})"),
+            "odd" => format!("{x} % 2 != 0"),
+            "chr" => format!("pas_chr({x}, {position})"),
+            "succ" | "pred" => format!(
+                "pas_successor({x}, {}, {}, \"{name}\", {position})",
+                if name == "succ" { 1 } else { -1 },
+                self.descriptor(&argument_type)
+            ),
+            "length" => format!("pas_length({x})"),
+            "copy" => format!("pas_copy({x}, {}, {})", values[1], values[2]),
+            "pos" => format!("pas_pos({x}, {})", values[1]),
+            // ord
+            _ => x,
+        };
+        self.typed_temporary(&expression.value_type, &value)
+    }
+}
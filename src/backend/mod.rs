@@ -0,0 +1,58 @@
+// Geração de código a partir da árvore sintática verificada. Apenas o alvo c tem um backend, os
+// outros alvos de build são aceitos e falham com a mensagem de unavailable
+mod c;
+mod test;
+
+use crate::syntactic::ast::Program;
+use std::fmt;
+
+// Alvos aceitos por build
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Target {
+    C,
+    Llvm,
+    Wat,
+    Asm,
+    Bytecode,
+}
+
+impl Target {
+    pub fn from_name(name: &str) -> Option<Self> {
+        let target = match name {
+            "c" => Target::C,
+            "llvm" => Target::Llvm,
+            "wat" => Target::Wat,
+            "asm" => Target::Asm,
+            "bytecode" => Target::Bytecode,
+            _ => return None,
+        };
+        Some(target)
+    }
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Target::C => "c",
+            Target::Llvm => "llvm",
+            Target::Wat => "wat",
+            Target::Asm => "asm",
+            Target::Bytecode => "bytecode",
+        };
+        write!(f, "{name}")
+    }
+}
+
+// Código do programa no alvo, None quando o alvo ainda não tem backend
+pub fn generate(program: &Program, target: Target) -> Option<String> {
+    match target {
+        Target::C => Some(c::generate(program)),
+        _ => None,
+    }
+}
+
+pub fn unavailable(target: Target) -> String {
+    format!(
+        "The '{target}' backend is not available yet, the program was checked but nothing was generated."
+    )
+}
@@ -0,0 +1,468 @@
+// Biblioteca de execução dos programas traduzidos para C. As verificações e as mensagens são as
+// do interpretador, um programa falha da mesma forma nos dois
+#include <math.h>
+#include <stdarg.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+// Cada programa usa apenas parte da biblioteca
+#ifdef __GNUC__
+#pragma GCC diagnostic ignored "-Wunused-function"
+#pragma GCC diagnostic ignored "-Wunused-const-variable"
+#endif
+
+#define PAS_MAX_INTEGER 2147483647LL
+#define PAS_MAX_DEPTH 200
+#define PAS_SET_SIZE 256
+
+// Os ordinais, inclusive boolean, char e as enumerações, são guardados como pas_integer
+typedef long long pas_integer;
+typedef double pas_real;
+// Os textos são imutáveis e codificados em UTF-8, cada operação cria um texto novo
+typedef const char *pas_string;
+
+// Mapa de bits de um conjunto, um bit para cada ordinal entre 0 e PAS_SET_SIZE - 1
+typedef struct {
+    unsigned long long words[PAS_SET_SIZE / 64];
+} pas_set;
+
+// Início de cada variável dinâmica, as liberadas por dispose continuam marcadas como mortas
+typedef struct {
+    int alive;
+} pas_cell;
+
+// Tipo de um ordinal, usado para mostrá-lo nas mensagens e na saída
+typedef struct {
+    enum { PAS_INTEGER, PAS_BOOLEAN, PAS_CHAR, PAS_ENUMERATION } kind;
+    pas_integer count;
+    const char *const *names; // Constantes das enumerações, na ordem
+} pas_type;
+
+static const pas_type pas_integer_type = {PAS_INTEGER, 0, NULL};
+static const pas_type pas_boolean_type = {PAS_BOOLEAN, 0, NULL};
+static const pas_type pas_char_type = {PAS_CHAR, 0, NULL};
+
+// Escopos ativos, contados como no interpretador: o pré-definido, as units, o programa e as chamadas
+static int pas_depth = 1;
+
+static void pas_fail(const char *format, ...) {
+    va_list arguments;
+    fflush(stdout);
+    fputs("An error occurred while running the program.\n", stderr);
+    va_start(arguments, format);
+    vfprintf(stderr, format, arguments);
+    va_end(arguments);
+    fputc('\n', stderr);
+    exit(6);
+}
+
+static void *pas_allocate(size_t size) {
+    void *memory = calloc(1, size > 0 ? size : 1);
+    if (memory == NULL) {
+        pas_fail("Out of memory.");
+    }
+    return memory;
+}
+
+// Bytes do caractere em UTF-8, retorna a quantidade
+static size_t pas_encode(pas_integer code, char *bytes) {
+    if (code < 0x80) {
+        bytes[0] = (char) code;
+        return 1;
+    }
+    if (code < 0x800) {
+        bytes[0] = (char) (0xC0 | code >> 6);
+        bytes[1] = (char) (0x80 | (code & 0x3F));
+        return 2;
+    }
+    if (code < 0x10000) {
+        bytes[0] = (char) (0xE0 | code >> 12);
+        bytes[1] = (char) (0x80 | (code >> 6 & 0x3F));
+        bytes[2] = (char) (0x80 | (code & 0x3F));
+        return 3;
+    }
+    bytes[0] = (char) (0xF0 | code >> 18);
+    bytes[1] = (char) (0x80 | (code >> 12 & 0x3F));
+    bytes[2] = (char) (0x80 | (code >> 6 & 0x3F));
+    bytes[3] = (char) (0x80 | (code & 0x3F));
+    return 4;
+}
+
+static pas_string pas_char_text(pas_integer code) {
+    char *text = pas_allocate(5);
+    text[pas_encode(code, text)] = '\0';
+    return text;
+}
+
+static pas_string pas_concat(pas_string left, pas_string right) {
+    size_t length = strlen(left);
+    char *text = pas_allocate(length + strlen(right) + 1);
+    memcpy(text, left, length);
+    strcpy(text + length, right);
+    return text;
+}
+
+static int pas_continuation(const char *byte) {
+    return ((unsigned char) *byte & 0xC0) == 0x80;
+}
+
+// Os tamanhos e as posições dos textos são contados em caracteres, não em bytes
+static pas_integer pas_length(pas_string text) {
+    pas_integer length = 0;
+    for (; *text != '\0'; text++) {
+        length += !pas_continuation(text);
+    }
+    return length;
+}
+
+static const char *pas_skip(const char *text, pas_integer count) {
+    for (; *text != '\0' && count > 0; count--) {
+        do {
+            text++;
+        } while (pas_continuation(text));
+    }
+    return text;
+}
+
+static pas_string pas_copy(pas_string text, pas_integer index, pas_integer count) {
+    const char *start = pas_skip(text, index > 1 ? index - 1 : 0);
+    const char *end = pas_skip(start, count > 0 ? count : 0);
+    char *copy = pas_allocate((size_t) (end - start) + 1);
+    memcpy(copy, start, (size_t) (end - start));
+    return copy;
+}
+
+static pas_integer pas_pos(pas_string pattern, pas_string text) {
+    const char *found = *pattern == '\0' ? NULL : strstr(text, pattern);
+    pas_integer position = 1;
+    if (found == NULL) {
+        return 0;
+    }
+    for (; text < found; text++) {
+        position += !pas_continuation(text);
+    }
+    return position;
+}
+
+static int pas_compare(pas_string left, pas_string right) {
+    return strcmp(left, right);
+}
+
+// Menor quantidade de dígitos que representa o real, com a notação do interpretador: decimal com
+// pelo menos uma casa, ou científica abaixo de 1e-4 e a partir de 1e16
+static void pas_format_real(char *text, pas_real value) {
+    char digits[32], *exponent;
+    int precision, power, count, i;
+    if (isnan(value)) {
+        strcpy(text, "NaN");
+        return;
+    }
+    if (isinf(value)) {
+        strcpy(text, value < 0 ? "-inf" : "inf");
+        return;
+    }
+    if (value == 0) {
+        strcpy(text, signbit(value) ? "-0.0" : "0.0");
+        return;
+    }
+    if (value < 0) {
+        *text++ = '-';
+        value = -value;
+    }
+    for (precision = 0; precision < 17; precision++) {
+        sprintf(digits, "%.*e", precision, value);
+        if (strtod(digits, NULL) == value) {
+            break;
+        }
+    }
+    exponent = strchr(digits, 'e');
+    power = atoi(exponent + 1);
+    *exponent = '\0';
+    if (digits[1] == '.') {
+        memmove(digits + 1, digits + 2, strlen(digits + 2) + 1);
+    }
+    count = (int) strlen(digits);
+    while (count > 1 && digits[count - 1] == '0') {
+        digits[--count] = '\0';
+    }
+    if (power < -4 || power >= 16) {
+        if (count == 1) {
+            sprintf(text, "%ce%d", digits[0], power);
+        } else {
+            sprintf(text, "%c.%se%d", digits[0], digits + 1, power);
+        }
+    } else if (power < 0) {
+        text += sprintf(text, "0.");
+        for (i = 0; i < -power - 1; i++) {
+            *text++ = '0';
+        }
+        strcpy(text, digits);
+    } else {
+        for (i = 0; i <= power; i++) {
+            *text++ = i < count ? digits[i] : '0';
+        }
+        *text++ = '.';
+        strcpy(text, count > power + 1 ? digits + power + 1 : "0");
+    }
+}
+
+static int pas_is_control(pas_integer code) {
+    return code < 0x20 || (code >= 0x7F && code <= 0x9F);
+}
+
+// Texto de um ordinal nas mensagens: os caracteres entre aspas e as constantes pelo nome
+static pas_string pas_show(pas_integer value, const pas_type *type) {
+    char bytes[4], *text, *target;
+    size_t length, i;
+    if (type->kind == PAS_BOOLEAN) {
+        return value != 0 ? "true" : "false";
+    }
+    if (type->kind == PAS_ENUMERATION && value >= 0 && value < type->count) {
+        return type->names[value];
+    }
+    text = pas_allocate(32);
+    if (type->kind != PAS_CHAR) {
+        sprintf(text, "%lld", value);
+    } else if (pas_is_control(value)) {
+        sprintf(text, "#%lld", value);
+    } else {
+        length = pas_encode(value, bytes);
+        target = text;
+        *target++ = '\'';
+        for (i = 0; i < length; i++) {
+            if (bytes[i] == '\'') {
+                *target++ = '\'';
+            }
+            *target++ = bytes[i];
+        }
+        *target++ = '\'';
+    }
+    return text;
+}
+
+static pas_string pas_show_real(pas_real value) {
+    char *text = pas_allocate(64);
+    pas_format_real(text, value);
+    return text;
+}
+
+static pas_integer pas_check_integer(pas_integer value, int line, int column) {
+    if (value < -PAS_MAX_INTEGER - 1 || value > PAS_MAX_INTEGER) {
+        pas_fail("Integer overflow at line %d column %d.", line, column);
+    }
+    return value;
+}
+
+static pas_integer pas_divisor(pas_integer value, int line, int column) {
+    if (value == 0) {
+        pas_fail("Division by zero at line %d column %d.", line, column);
+    }
+    return value;
+}
+
+static pas_real pas_real_divisor(pas_real value, int line, int column) {
+    if (value == 0.0) {
+        pas_fail("Division by zero at line %d column %d.", line, column);
+    }
+    return value;
+}
+
+// Valor atribuído a um subintervalo
+static pas_integer pas_range(pas_integer value, pas_integer low, pas_integer high,
+                             const pas_type *type, const char *name, int line, int column) {
+    if (value < low || value > high) {
+        pas_fail("Value %s is out of the range '%s' at line %d column %d.", pas_show(value, type),
+                 name, line, column);
+    }
+    return value;
+}
+
+// Posição do elemento no array, a partir do primeiro índice
+static pas_integer pas_index(pas_integer position, pas_integer low, pas_integer length,
+                             const pas_type *type, const char *name, int line, int column) {
+    if (position - low < 0 || position - low >= length) {
+        pas_fail("Index %s is out of the range '%s' at line %d column %d.",
+                 pas_show(position, type), name, line, column);
+    }
+    return position - low;
+}
+
+static void pas_check_depth(const char *name, int line, int column) {
+    if (pas_depth > PAS_MAX_DEPTH) {
+        pas_fail("Stack overflow in the call to '%s' at line %d column %d.", name, line, column);
+    }
+}
+
+static void *pas_new(size_t size) {
+    pas_cell *cell = pas_allocate(size);
+    cell->alive = 1;
+    return cell;
+}
+
+// A memória das variáveis liberadas não é reaproveitada, assim o acesso a elas é detectado
+static void pas_dispose(void *pointer, int line, int column) {
+    pas_cell *cell = pointer;
+    if (cell == NULL || !cell->alive) {
+        pas_fail("Dispose of an invalid pointer at line %d column %d.", line, column);
+    }
+    cell->alive = 0;
+}
+
+static void pas_check_pointer(void *pointer, int line, int column) {
+    pas_cell *cell = pointer;
+    if (cell == NULL) {
+        pas_fail("Dereference of a nil pointer at line %d column %d.", line, column);
+    }
+    if (!cell->alive) {
+        pas_fail("Dereference of a disposed pointer at line %d column %d.", line, column);
+    }
+}
+
+static pas_set pas_empty(void) {
+    pas_set set = {{0}};
+    return set;
+}
+
+static int pas_in(pas_integer value, pas_set set) {
+    return value >= 0 && value < PAS_SET_SIZE && (set.words[value / 64] >> (value % 64) & 1);
+}
+
+// Adiciona os elementos de low a high, que precisam estar entre 0 e PAS_SET_SIZE - 1
+static pas_set pas_include(pas_set set, pas_integer low, pas_integer high, int line, int column) {
+    pas_integer value;
+    for (value = low; value <= high; value++) {
+        if (value < 0 || value >= PAS_SET_SIZE) {
+            pas_fail("Set element out of the range 0..%d at line %d column %d.", PAS_SET_SIZE - 1,
+                     line, column);
+        }
+        set.words[value / 64] |= 1ULL << (value % 64);
+    }
+    return set;
+}
+
+static pas_set pas_union(pas_set left, pas_set right) {
+    int i;
+    for (i = 0; i < PAS_SET_SIZE / 64; i++) {
+        left.words[i] |= right.words[i];
+    }
+    return left;
+}
+
+static pas_set pas_difference(pas_set left, pas_set right) {
+    int i;
+    for (i = 0; i < PAS_SET_SIZE / 64; i++) {
+        left.words[i] &= ~right.words[i];
+    }
+    return left;
+}
+
+static pas_set pas_intersection(pas_set left, pas_set right) {
+    int i;
+    for (i = 0; i < PAS_SET_SIZE / 64; i++) {
+        left.words[i] &= right.words[i];
+    }
+    return left;
+}
+
+static int pas_subset(pas_set left, pas_set right) {
+    int i;
+    for (i = 0; i < PAS_SET_SIZE / 64; i++) {
+        if (left.words[i] & ~right.words[i]) {
+            return 0;
+        }
+    }
+    return 1;
+}
+
+static int pas_set_equal(pas_set left, pas_set right) {
+    return pas_subset(left, right) && pas_subset(right, left);
+}
+
+// Conjunto atribuído a um conjunto de um tipo base menor
+static pas_set pas_check_set(pas_set set, pas_integer low, pas_integer high,
+                             const pas_type *base, const char *name, int line, int column) {
+    pas_integer value;
+    for (value = 0; value < PAS_SET_SIZE; value++) {
+        if (pas_in(value, set) && (value < low || value > high)) {
+            pas_fail("Set element %s is out of the range '%s' at line %d column %d.",
+                     pas_show(value, base), name, line, column);
+        }
+    }
+    return set;
+}
+
+static pas_integer pas_chr(pas_integer value, int line, int column) {
+    if (value < 0 || value > 255) {
+        pas_fail("Invalid argument %lld for 'chr' at line %d column %d.", value, line, column);
+    }
+    return value;
+}
+
+// succ e pred, os caracteres vão até o último ponto de código do Unicode
+static pas_integer pas_successor(pas_integer value, pas_integer step, const pas_type *type,
+                                 const char *name, int line, int column) {
+    pas_integer result = value + step;
+    int inside;
+    switch (type->kind) {
+    case PAS_BOOLEAN:
+        inside = result >= 0 && result <= 1;
+        break;
+    case PAS_ENUMERATION:
+        inside = result >= 0 && result < type->count;
+        break;
+    case PAS_CHAR:
+        inside = result >= 0 && result <= 0x10FFFF && (result < 0xD800 || result > 0xDFFF);
+        break;
+    default:
+        inside = result >= -PAS_MAX_INTEGER - 1 && result <= PAS_MAX_INTEGER;
+        break;
+    }
+    if (!inside) {
+        pas_fail("'%s' of %s at line %d column %d is out of the range of its type.", name,
+                 pas_show(value, type), line, column);
+    }
+    return result;
+}
+
+static pas_real pas_check_argument(pas_real value, int valid, const char *name, int line,
+                                   int column) {
+    if (!valid) {
+        pas_fail("Invalid argument %s for '%s' at line %d column %d.", pas_show_real(value), name,
+                 line, column);
+    }
+    return value;
+}
+
+// trunc e round, os reais fora do intervalo do integer não podem ser convertidos
+static pas_integer pas_truncate(pas_real value, pas_real argument, const char *name, int line,
+                                int column) {
+    pas_check_argument(argument, isfinite(value), name, line, column);
+    if (value < -PAS_MAX_INTEGER - 1 || value > PAS_MAX_INTEGER) {
+        pas_fail("Integer overflow at line %d column %d.", line, column);
+    }
+    return (pas_integer) value;
+}
+
+static void pas_write_text(pas_string text) {
+    fputs(text, stdout);
+}
+
+static void pas_write_char(pas_integer code) {
+    char bytes[4];
+    fwrite(bytes, 1, pas_encode(code, bytes), stdout);
+}
+
+static void pas_write_real(pas_real value) {
+    char text[64];
+    pas_format_real(text, value);
+    fputs(text, stdout);
+}
+
+static void pas_write_ordinal(pas_integer value, const pas_type *type) {
+    if (type->kind == PAS_INTEGER) {
+        printf("%lld", value);
+    } else {
+        fputs(pas_show(value, type), stdout);
+    }
+}
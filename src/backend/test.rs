@@ -0,0 +1,230 @@
+#[cfg(test)]
+mod backend_tests {
+    use crate::backend::c::generate;
+    use crate::generator::program;
+    use crate::interpreter::Interpreter;
+    use crate::lexical::Scanner;
+    use crate::syntactic::Parser;
+    use std::fs::{self, File};
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+
+    // Os programas em C são compilados pelo cc do sistema, sem ele os testes não comparam nada
+    fn compiler_available() -> bool {
+        Command::new("cc").arg("--version").output().is_ok()
+    }
+
+    fn directory(test: &str) -> PathBuf {
+        let directory =
+            std::env::temp_dir().join(format!("compilador2-backend-{test}-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        directory
+    }
+
+    // Saída padrão, saída de erros e código de saída do programa no interpretador e compilado
+    // para C, que devem ser iguais
+    fn outputs(source: &str, directory: &Path) -> ((String, String, i32), (String, String, i32)) {
+        let units = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/units");
+        let tokens = Scanner::new(source).init().unwrap();
+        let mut parser = Parser::new(&tokens);
+        parser.set_source_path(&units.join("main.pas"));
+        parser.set_unit_paths(vec![units.join("lib"), units]);
+        parser.check().unwrap_or_else(|e| panic!("{e}\n{source}"));
+        let program = parser.get_program().unwrap();
+
+        let output = directory.join("saida.txt");
+        let mut interpreter = Interpreter::default();
+        interpreter.set_output(Box::new(File::create(&output).unwrap()));
+        let (errors, code) = match interpreter.run(program) {
+            Ok(()) => (String::new(), 0),
+            Err(message) => (
+                format!("An error occurred while running the program.\n{message}\n"),
+                6,
+            ),
+        };
+        drop(interpreter);
+        let interpreted = (fs::read_to_string(&output).unwrap(), errors, code);
+
+        let code = directory.join("programa.c");
+        let executable = directory.join("programa");
+        fs::write(&code, generate(program)).unwrap();
+        let compilation = Command::new("cc")
+            .args(["-std=c99", "-o"])
+            .arg(&executable)
+            .arg(&code)
+            .arg("-lm")
+            .output()
+            .unwrap();
+        let errors = String::from_utf8_lossy(&compilation.stderr);
+        assert!(compilation.status.success(), "{errors}\n{source}");
+        let run = Command::new(&executable).output().unwrap();
+        let compiled = (
+            String::from_utf8_lossy(&run.stdout).to_string(),
+            String::from_utf8_lossy(&run.stderr).to_string(),
+            run.status.code().unwrap_or_default(),
+        );
+        (interpreted, compiled)
+    }
+
+    fn compare(sources: &[String], test: &str) {
+        if !compiler_available() {
+            return;
+        }
+        let directory = directory(test);
+        for source in sources {
+            let (interpreted, compiled) = outputs(source, &directory);
+            assert_eq!(interpreted, compiled, "{source}");
+        }
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_fixtures() {
+        let sources: Vec<String> = [
+            "ola_mundo.pas",
+            "tests/Test2.pas",
+            "tests/Test5.pas",
+            "tests/fatorial.pas",
+        ]
+        .iter()
+        .map(|path| fs::read_to_string(path).unwrap())
+        .collect();
+        compare(&sources, "fixtures");
+    }
+
+    #[test]
+    fn test_generated_programs() {
+        let sources: Vec<String> = (0..20).map(program).collect();
+        compare(&sources, "generated");
+    }
+
+    #[test]
+    fn test_language() {
+        let source = "program mistura;
+            uses geometria, formas;
+            const limite = 5; saudacao = 'olá';
+            type
+                cor = (vermelho, verde, azul);
+                faixa = 1..10;
+                letras = set of 'a'..'z';
+                vetor = array[1..limite] of integer;
+                matriz = array[cor] of vetor;
+                no = ^celula;
+                celula = record valor: integer; proximo: no end;
+            var
+                i, j: integer; f: faixa; c: cor; r: real; s, t: string; ch: char; b: boolean;
+                v: vetor; m: matriz; conj: letras; numeros: set of 0..100; lista, atual: no;
+                p: Ponto;
+
+            procedure externo(n: integer);
+            var total: integer;
+                procedure interno(k: integer);
+                begin
+                    total := total + k;
+                    if k > 0 then interno(k - 1)
+                end;
+            begin
+                total := 0;
+                interno(n);
+                writeln('total de ', n, ' = ', total)
+            end;
+
+            procedure saida(x: integer);
+            begin
+                if x > 2 then exit;
+                writeln('saida ', x)
+            end;
+
+            begin
+                writeln(saudacao, length(saudacao), pos('á', saudacao), copy(saudacao, 2, 5));
+                s := 'abc'; ch := 'z'; t := s + ch + 'é';
+                writeln(t, ' ', length(t), ' ', t < s, ' ', s = 'abc', ' ', ch > 'a');
+                for i := 1 to limite do v[i] := i * i;
+                for c := vermelho to azul do m[c] := v;
+                m[verde][2] := -7;
+                writeln(m[vermelho][2], ' ', m[verde][2], ' ', m[azul][limite]);
+                for c := azul downto vermelho do write(c, ' ', ord(c), ' ');
+                writeln(succ(vermelho), ' ', pred(azul), ' ', succ('a'), ' ', chr(65));
+                conj := ['a', 'c'..'f']; numeros := [1, 3, 5] + [2] - [3];
+                writeln('b' in conj, ' ', 'd' in conj, ' ', 3 in numeros, ' ', 2 in numeros);
+                writeln(conj <= ['a'..'z'], ' ', numeros = [1, 2, 5], ' ', [] = numeros * [7]);
+                i := 0;
+                repeat
+                    i := i + 1;
+                    if i = 2 then continue;
+                    if i = 5 then break;
+                    write(i, ' ')
+                until i >= 10;
+                j := 0;
+                while j < 10 do
+                begin
+                    j := j + 3;
+                    if odd(j) then continue;
+                    write(j, ' ')
+                end;
+                for i := 1 to 12 do
+                    case i of
+                        1, 2: write('a');
+                        3..5: write('b');
+                        10: write('c')
+                    else
+                        write('.')
+                    end;
+                lista := nil;
+                for i := 1 to 4 do
+                begin
+                    new(atual); atual^.valor := i * 10; atual^.proximo := lista; lista := atual
+                end;
+                atual := lista;
+                while atual <> nil do
+                begin
+                    write(atual^.valor, ' '); atual := atual^.proximo
+                end;
+                writeln(lista = atual, ' ', lista <> nil);
+                dispose(lista);
+                externo(4);
+                for i := 1 to 4 do saida(i);
+                r := 1.0 / 3;
+                writeln(r, ' ', 1e16, ' ', 1.5e16, ' ', 0.00001, ' ', -0.0, ' ', 123456789.125);
+                writeln(sqrt(2), ' ', sqr(1.5), ' ', abs(-3), ' ', trunc(2.7), ' ', round(-2.5));
+                writeln(exp(1), ' ', 7 / 2, ' ', 7.0 / 2, ' ', 2 * 3 + 4 - 1);
+                b := (i > 3) and not (j < 0) or false;
+                f := 3; f := f + 4;
+                writeln(b, ' ', f, ' ', chr(9), chr(200));
+                desenhar(3);
+                p.x := 1.5; p.y := 2;
+                writeln(origem.x, ' ', p.x + p.y, ' ', horizontal);
+                f := f + 4
+            end.";
+        compare(&[source.to_string()], "language");
+    }
+
+    // As falhas durante a execução têm a mesma mensagem e o mesmo código de saída
+    #[test]
+    fn test_runtime_errors() {
+        let sources: Vec<String> = [
+            "var a: array[1..3] of integer; i: integer; begin i := 4; a[i] := 1 end.",
+            "type c = (r, g, b); var a: array[r..g] of integer; x: c; begin x := b; a[x] := 1 end.",
+            "var p: ^integer; begin p^ := 1 end.",
+            "var p: ^integer; begin new(p); dispose(p); writeln(p^) end.",
+            "var p: ^integer; begin new(p); dispose(p); dispose(p) end.",
+            "var i: integer; begin i := 2147483647; writeln('antes'); i := i + 1 end.",
+            "var i: integer; begin i := 0; writeln('antes', 10 / i) end.",
+            "var s: set of 'a'..'c'; t: set of char; begin t := ['a'..'e']; s := t end.",
+            "var i: integer; begin i := 300; writeln(3 in [1, i]) end.",
+            "var r: real; begin r := -2; writeln(sqrt(r)) end.",
+            "var i: integer; begin i := 256; writeln(chr(i)) end.",
+            "type c = (r, g); var x: c; begin x := g; writeln(succ(x)) end.",
+            "var x: char; begin x := chr(0); writeln(pred(x)) end.",
+            "var r: real; begin r := 1e12; writeln(trunc(r)) end.",
+            "procedure p(n: integer); begin p(n + 1) end; begin p(0) end.",
+            "var f: 'a'..'f'; c: char; begin c := 'z'; f := c end.",
+            "var f: 1..5; begin for f := 3 to 7 do write(f) end.",
+            "procedure q(x: 1..3); begin writeln(x) end; begin q(2); q(4) end.",
+        ]
+        .iter()
+        .map(|program| format!("program erro; {program}"))
+        .collect();
+        compare(&sources, "errors");
+    }
+}
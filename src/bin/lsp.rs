@@ -0,0 +1,7 @@
+// Servidor de linguagem como um binário próprio, o mesmo que 'compilador2 lsp'
+use compilador2::__private as cli;
+use std::process::exit;
+
+fn main() {
+    exit(cli::main(&["lsp".to_string()]));
+}
@@ -0,0 +1,430 @@
+mod test;
+
+use crate::backend;
+use crate::backend::Target;
+use crate::common::token::Token;
+use crate::dump;
+use crate::dump::Format;
+use crate::formatter;
+use crate::interpreter::Interpreter;
+use crate::lexical::Scanner;
+use crate::syntactic::Parser;
+use crate::{lsp, repl};
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+const USAGE: &str = "Usage: compilador2 <command> [options] <file>
+
+Commands:
+  check     check the program and show its warnings
+  run       check and run the program
+  build     compile the program, --target c, llvm, wat, asm or bytecode
+  tokens    show the tokens of the file, --format table or json
+  ast       show the syntax tree of the program, --format tree, sexp or json, or --emit dot
+  parse-tree
+            show the derivation of the program, one node per grammar production
+  cfg       show the control-flow graph of each procedure, in DOT
+  fmt       show the program formatted
+  repl      start an interactive session
+  lsp       start a language server on the standard input and output
+
+Options:
+  -o <file>            write the output to the file instead of the standard output
+  --target <target>    backend used by build
+  --format <format>    output format of tokens, ast and parse-tree
+  --emit dot           output ast, parse-tree or cfg as a Graphviz graph
+  --quiet              do not show warnings
+  --timings            show how long each phase took
+  --strict-case        warn about keywords and identifiers written in a different case
+  --unit-path=<dir>    also look for units in the directory, may be repeated
+  -h, --help           show this message
+
+The file '-' is read from the standard input.";
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Command {
+    Check,
+    Run,
+    Build,
+    Tokens,
+    Ast,
+    ParseTree,
+    Cfg,
+    Fmt,
+    Repl,
+    Lsp,
+    Help,
+}
+
+impl Command {
+    fn from_name(name: &str) -> Option<Self> {
+        let command = match name {
+            "check" => Command::Check,
+            "run" => Command::Run,
+            "build" => Command::Build,
+            "tokens" => Command::Tokens,
+            "ast" => Command::Ast,
+            "parse-tree" => Command::ParseTree,
+            "cfg" => Command::Cfg,
+            "fmt" => Command::Fmt,
+            "repl" => Command::Repl,
+            "lsp" => Command::Lsp,
+            _ => return None,
+        };
+        Some(command)
+    }
+
+    fn needs_input(self) -> bool {
+        !matches!(self, Command::Repl | Command::Lsp | Command::Help)
+    }
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Command::Check => "check",
+            Command::Run => "run",
+            Command::Build => "build",
+            Command::Tokens => "tokens",
+            Command::Ast => "ast",
+            Command::ParseTree => "parse-tree",
+            Command::Cfg => "cfg",
+            Command::Fmt => "fmt",
+            Command::Repl => "repl",
+            Command::Lsp => "lsp",
+            Command::Help => "help",
+        };
+        write!(f, "{name}")
+    }
+}
+
+type Scanned = (Vec<Token>, Vec<(usize, String)>);
+
+#[derive(PartialEq, Debug)]
+pub struct Options {
+    pub command: Command,
+    pub input: Option<String>,  // '-' é a entrada padrão
+    pub output: Option<String>, // None é a saída padrão
+    pub target: Option<Target>,
+    pub format: Option<Format>,
+    pub quiet: bool,
+    pub timings: bool,
+    pub strict_case: bool,
+    pub unit_paths: Vec<PathBuf>,
+}
+
+// Classes de falha, cada uma com o seu código de saída
+#[derive(PartialEq, Debug)]
+pub enum Failure {
+    Usage(String),
+    Io(String),
+    Lexical(String),
+    Syntactic(String),
+    Runtime(String),
+    Unsupported(String),
+}
+
+impl Failure {
+    pub fn code(&self) -> i32 {
+        match self {
+            Failure::Usage(_) => 2,
+            Failure::Io(_) => 3,
+            Failure::Lexical(_) => 4,
+            Failure::Syntactic(_) => 5,
+            Failure::Runtime(_) => 6,
+            Failure::Unsupported(_) => 7,
+        }
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Usage(message) => {
+                write!(f, "{message}\nType 'compilador2 --help' to see the usage.")
+            }
+            Failure::Io(message) | Failure::Unsupported(message) => write!(f, "{message}"),
+            Failure::Lexical(message) => {
+                write!(f, "An error occurred in the lexical parsing.\n{message}")
+            }
+            Failure::Syntactic(message) => {
+                write!(f, "An error occurred in the syntactic parsing.\n{message}")
+            }
+            Failure::Runtime(message) => {
+                write!(f, "An error occurred while running the program.\n{message}")
+            }
+        }
+    }
+}
+
+// Interpreta os argumentos, sem o nome do executável
+// Sem um comando conhecido, o primeiro argumento é o arquivo e o comando é check
+pub fn parse(args: &[String]) -> Result<Options, Failure> {
+    let mut options = Options {
+        command: Command::Check,
+        input: None,
+        output: None,
+        target: None,
+        format: None,
+        quiet: false,
+        timings: false,
+        strict_case: false,
+        unit_paths: vec![],
+    };
+    let mut args = args.iter();
+    let mut command = None;
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| match args.next() {
+            Some(value) => Ok(value.to_owned()),
+            None => Err(Failure::Usage(format!(
+                "The option '{flag}' needs a value."
+            ))),
+        };
+        match arg.as_str() {
+            "-h" | "--help" => command = Some(Command::Help),
+            "-o" => options.output = Some(value(arg)?),
+            "--target" => options.target = Some(target(&value(arg)?)?),
+            "--format" => options.format = Some(format(&value(arg)?)?),
+            "--emit" => options.format = Some(emit_format(&value(arg)?)?),
+            "--quiet" => options.quiet = true,
+            "--timings" => options.timings = true,
+            "--strict-case" => options.strict_case = true,
+            "--unit-path" => options.unit_paths.push(PathBuf::from(value(arg)?)),
+            _ => {
+                if let Some(name) = arg.strip_prefix("--target=") {
+                    options.target = Some(target(name)?);
+                } else if let Some(name) = arg.strip_prefix("--format=") {
+                    options.format = Some(format(name)?);
+                } else if let Some(name) = arg.strip_prefix("--emit=") {
+                    options.format = Some(emit_format(name)?);
+                } else if let Some(path) = arg.strip_prefix("--unit-path=") {
+                    options.unit_paths.push(PathBuf::from(path));
+                } else if arg.starts_with('-') && arg != "-" {
+                    return Err(Failure::Usage(format!("Unknown option '{arg}'.")));
+                } else if command.is_none() && options.input.is_none() {
+                    match Command::from_name(arg) {
+                        Some(name) => command = Some(name),
+                        None => options.input = Some(arg.to_owned()),
+                    }
+                } else if options.input.is_none() {
+                    options.input = Some(arg.to_owned());
+                } else {
+                    return Err(Failure::Usage(format!("Unexpected argument '{arg}'.")));
+                }
+            }
+        }
+    }
+    options.command = command.unwrap_or(Command::Check);
+
+    if options.command.needs_input() && options.input.is_none() {
+        return Err(Failure::Usage("No file path was given.".to_string()));
+    }
+    if options.command == Command::Build && options.target.is_none() {
+        return Err(Failure::Usage(
+            "The build command needs a --target: c, llvm, wat, asm or bytecode.".to_string(),
+        ));
+    }
+    if options.target.is_some() && options.command != Command::Build {
+        return Err(Failure::Usage(
+            "The option '--target' is only accepted by build.".to_string(),
+        ));
+    }
+    let accepted = match options.command {
+        Command::Tokens => [Format::Table, Format::Json].as_slice(),
+        Command::Ast => [Format::Tree, Format::Sexp, Format::Json, Format::Dot].as_slice(),
+        Command::ParseTree => [Format::Tree, Format::Dot].as_slice(),
+        Command::Cfg => [Format::Dot].as_slice(),
+        _ => [].as_slice(),
+    };
+    match options.format {
+        Some(_) if accepted.is_empty() => {
+            return Err(Failure::Usage(
+                "The options '--format' and '--emit' are only accepted by tokens, ast, parse-tree \
+                 and cfg."
+                    .to_string(),
+            ))
+        }
+        Some(format) if !accepted.contains(&format) => {
+            return Err(Failure::Usage(format!(
+                "The format '{}' is not accepted by {}.",
+                format, options.command
+            )))
+        }
+        None => options.format = accepted.first().copied(),
+        Some(_) => {}
+    }
+    Ok(options)
+}
+
+fn format(name: &str) -> Result<Format, Failure> {
+    Format::from_name(name).ok_or_else(|| {
+        Failure::Usage(format!(
+            "Unknown format '{name}', the formats are table, tree, sexp and json."
+        ))
+    })
+}
+
+// DOT é o único formato de --emit
+fn emit_format(name: &str) -> Result<Format, Failure> {
+    match name {
+        "dot" => Ok(Format::Dot),
+        _ => Err(Failure::Usage(format!(
+            "Unknown value '{name}' for '--emit', the only one is dot."
+        ))),
+    }
+}
+
+fn target(name: &str) -> Result<Target, Failure> {
+    Target::from_name(name).ok_or_else(|| {
+        Failure::Usage(format!(
+            "Unknown target '{name}', the targets are c, llvm, wat, asm and bytecode."
+        ))
+    })
+}
+
+// Executa a linha de comando e retorna o código de saída
+pub fn main(args: &[String]) -> i32 {
+    let result = match parse(args) {
+        Ok(options) if options.command == Command::Repl => return repl::run(),
+        Ok(options) if options.command == Command::Lsp => return lsp::serve(),
+        Ok(options) => execute(&options),
+        Err(failure) => Err(failure),
+    };
+    match result {
+        Ok(()) => 0,
+        Err(failure) => {
+            eprintln!("{failure}");
+            failure.code()
+        }
+    }
+}
+
+pub fn execute(options: &Options) -> Result<(), Failure> {
+    match options.command {
+        Command::Help => {
+            println!("{USAGE}");
+            return Ok(());
+        }
+        Command::Repl | Command::Lsp => unreachable!(),
+        _ => {}
+    }
+
+    let input = read_input(options)?;
+    let (tokens, directives) = scan(options, &input, options.command == Command::Fmt)?;
+    match options.command {
+        Command::Tokens => return emit(options, &dump::tokens(&tokens, options.format.unwrap())),
+        Command::Fmt => return emit(options, &formatter::format(&tokens)),
+        _ => {}
+    }
+
+    let parser = check(options, tokens, &directives)?;
+    let program = parser
+        .get_program()
+        .expect("A checked program has a syntax tree.");
+    match options.command {
+        Command::Ast => emit(options, &dump::program(program, options.format.unwrap())),
+        Command::ParseTree => {
+            let derivation = parser
+                .get_derivation()
+                .expect("A checked program has a derivation.");
+            emit(
+                options,
+                &dump::derivation(&derivation, options.format.unwrap()),
+            )
+        }
+        Command::Cfg => emit(options, &dump::control_flow(program)),
+        Command::Run => {
+            let mut interpreter = Interpreter::default();
+            if let Some(path) = &options.output {
+                interpreter.set_output(Box::new(create(path)?));
+            }
+            let now = Instant::now();
+            let result = interpreter.run(program).map_err(Failure::Runtime);
+            timing(options, "Execution", now);
+            result
+        }
+        Command::Build => {
+            let target = options.target.unwrap();
+            let now = Instant::now();
+            let code = backend::generate(program, target);
+            timing(options, "Code generation", now);
+            match code {
+                Some(code) => emit(options, &code),
+                None => Err(Failure::Unsupported(backend::unavailable(target))),
+            }
+        }
+        _ => Ok(()),
+    }
+}
+
+fn read_input(options: &Options) -> Result<String, Failure> {
+    let path = options.input.as_deref().unwrap_or("-");
+    let mut content = String::new();
+    let result = if path == "-" {
+        io::stdin().read_to_string(&mut content)
+    } else {
+        File::open(path).and_then(|mut file| file.read_to_string(&mut content))
+    };
+    result.map_err(|e| Failure::Io(format!("Unable to read '{path}': {e}")))?;
+    Ok(content)
+}
+
+fn create(path: &str) -> Result<File, Failure> {
+    File::create(path).map_err(|e| Failure::Io(format!("Unable to write '{path}': {e}")))
+}
+
+// Escreve o resultado do comando no arquivo de -o ou na saída padrão
+fn emit(options: &Options, text: &str) -> Result<(), Failure> {
+    let result = match &options.output {
+        Some(path) => create(path)?.write_all(text.as_bytes()),
+        None => io::stdout().write_all(text.as_bytes()),
+    };
+    result.map_err(|e| Failure::Io(format!("Unable to write the output: {e}")))
+}
+
+fn timing(options: &Options, phase: &str, start: Instant) {
+    if options.timings {
+        eprintln!("{phase} took {}μs.", start.elapsed().as_micros());
+    }
+}
+
+// Tokens e diretivas do arquivo, com os comentários apenas quando trivia é verdadeiro
+fn scan(options: &Options, input: &str, trivia: bool) -> Result<Scanned, Failure> {
+    let mut scanner = Scanner::new(input);
+    scanner.set_trivia(trivia);
+    let now = Instant::now();
+    let tokens = scanner
+        .init()
+        .map_err(|e| Failure::Lexical(e.to_string()))?;
+    timing(options, "Lexical scanner", now);
+    Ok((tokens, scanner.get_directives().to_vec()))
+}
+
+fn check(
+    options: &Options,
+    tokens: Vec<Token>,
+    directives: &[(usize, String)],
+) -> Result<Parser, Failure> {
+    let mut parser = Parser::new(&tokens);
+    parser.set_strict_case(options.strict_case);
+    parser.set_directives(directives);
+    let path = options.input.as_deref().unwrap_or("-");
+    parser.set_source_path(Path::new(path));
+    parser.set_unit_paths(options.unit_paths.to_owned());
+    parser.set_derivation(options.command == Command::ParseTree);
+
+    let now = Instant::now();
+    let result = parser.check();
+    timing(options, "Syntactic parser", now);
+    if !options.quiet {
+        for warning in parser.get_warnings() {
+            eprintln!("Warning: {warning}");
+        }
+    }
+    result.map_err(|e| Failure::Syntactic(e.to_string()))?;
+    Ok(parser)
+}
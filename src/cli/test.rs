@@ -0,0 +1,162 @@
+#[cfg(test)]
+mod cli_tests {
+    use crate::backend::Target;
+    use crate::cli::{execute, parse, Command, Failure, Options};
+    use crate::dump::Format;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    fn options(args: &str) -> Result<Options, Failure> {
+        let args: Vec<String> = args.split_whitespace().map(str::to_string).collect();
+        parse(&args)
+    }
+
+    fn fixture(name: &str) -> String {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join(name)
+            .display()
+            .to_string()
+    }
+
+    #[test]
+    fn test_parse_arguments() {
+        let parsed = options("run --quiet -o saida.txt programa.pas --timings").unwrap();
+        assert_eq!(parsed.command, Command::Run);
+        assert_eq!(parsed.input.as_deref(), Some("programa.pas"));
+        assert_eq!(parsed.output.as_deref(), Some("saida.txt"));
+        assert!(parsed.quiet && parsed.timings);
+
+        let parsed = options("build --target=llvm - --unit-path lib --unit-path=vendor").unwrap();
+        assert_eq!(parsed.target, Some(Target::Llvm));
+        assert_eq!(parsed.input.as_deref(), Some("-"));
+        assert_eq!(
+            parsed.unit_paths,
+            vec![PathBuf::from("lib"), PathBuf::from("vendor")]
+        );
+
+        // Sem comando, o arquivo é apenas verificado
+        let parsed = options("programa.pas --strict-case").unwrap();
+        assert_eq!(parsed.command, Command::Check);
+        assert!(parsed.strict_case);
+        assert_eq!(options("repl").unwrap().command, Command::Repl);
+        assert_eq!(options("--help").unwrap().command, Command::Help);
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        let usage = |args: &str| matches!(options(args), Err(Failure::Usage(_)));
+        assert!(usage(""));
+        assert!(usage("run"));
+        assert!(usage("check a.pas b.pas"));
+        assert!(usage("check --verbose a.pas"));
+        assert!(usage("run a.pas -o"));
+        assert!(usage("build a.pas"));
+        assert!(usage("build --target java a.pas"));
+        assert!(usage("check --target c a.pas"));
+    }
+
+    #[test]
+    fn test_output_formats() {
+        assert_eq!(options("tokens a.pas").unwrap().format, Some(Format::Table));
+        assert_eq!(options("ast a.pas").unwrap().format, Some(Format::Tree));
+        let parsed = options("ast --format sexp a.pas").unwrap();
+        assert_eq!(parsed.format, Some(Format::Sexp));
+        let parsed = options("tokens --format=json a.pas").unwrap();
+        assert_eq!(parsed.format, Some(Format::Json));
+        assert_eq!(options("check a.pas").unwrap().format, None);
+        let parsed = options("ast --emit dot a.pas").unwrap();
+        assert_eq!(parsed.format, Some(Format::Dot));
+        let parsed = options("parse-tree a.pas").unwrap();
+        assert_eq!(parsed.format, Some(Format::Tree));
+        assert_eq!(options("cfg a.pas").unwrap().format, Some(Format::Dot));
+
+        let usage = |args: &str| matches!(options(args), Err(Failure::Usage(_)));
+        assert!(usage("tokens --format tree a.pas"));
+        assert!(usage("ast --format table a.pas"));
+        assert!(usage("ast --format xml a.pas"));
+        assert!(usage("run --format json a.pas"));
+        assert!(usage("tokens --emit dot a.pas"));
+        assert!(usage("ast --emit=svg a.pas"));
+        assert!(usage("cfg --format tree a.pas"));
+    }
+
+    #[test]
+    fn test_failure_classes() {
+        let failure = |args: &str| execute(&options(args).unwrap()).unwrap_err();
+        let missing = failure("check nao_existe.pas");
+        assert!(matches!(missing, Failure::Io(_)));
+
+        let lexical = failure(&format!("check {}", fixture("tests/Test1.pas")));
+        assert!(matches!(lexical, Failure::Lexical(_)));
+
+        let syntactic = failure(&format!("check {}", fixture("tests/Test3.pas")));
+        assert!(matches!(syntactic, Failure::Syntactic(_)));
+
+        let unsupported = failure(&format!("build --target llvm {}", fixture("ola_mundo.pas")));
+        assert_eq!(
+            unsupported,
+            Failure::Unsupported(
+                "The 'llvm' backend is not available yet, the program was checked but nothing \
+                 was generated."
+                    .to_string()
+            )
+        );
+
+        let codes: Vec<i32> = [missing, lexical, syntactic, unsupported]
+            .iter()
+            .map(Failure::code)
+            .collect();
+        assert_eq!(codes, vec![3, 4, 5, 7]);
+    }
+
+    #[test]
+    fn test_run_writes_output() {
+        let directory =
+            std::env::temp_dir().join(format!("compilador2-cli-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let program = directory.join("contar.pas");
+        let output = directory.join("saida.txt");
+        fs::write(
+            &program,
+            "program contar; var i: integer;
+            begin for i := 1 to 3 do write(i, ' '); writeln; writeln(i * 1.5, true, 'x') end.",
+        )
+        .unwrap();
+
+        let args = format!("run {} -o {}", program.display(), output.display());
+        execute(&options(&args).unwrap()).unwrap();
+        assert_eq!(fs::read_to_string(&output).unwrap(), "1 2 3 \n4.5truex\n");
+
+        fs::write(
+            &program,
+            "program falhar; var a: array[1..2] of integer; i: integer;
+            begin i := 3; writeln('antes'); a[i] := 0 end.",
+        )
+        .unwrap();
+        let failure = execute(&options(&args).unwrap()).unwrap_err();
+        assert!(matches!(failure, Failure::Runtime(_)));
+        assert_eq!(fs::read_to_string(&output).unwrap(), "antes\n");
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_build_writes_c() {
+        let directory =
+            std::env::temp_dir().join(format!("compilador2-build-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let output = directory.join("ola_mundo.c");
+        let args = format!(
+            "build --target c {} -o {}",
+            fixture("ola_mundo.pas"),
+            output.display()
+        );
+        execute(&options(&args).unwrap()).unwrap();
+        let code = fs::read_to_string(&output).unwrap();
+        assert!(code.contains("int main(void) {"));
+        assert!(
+            code.contains("static void p2_somatorio(pas_integer a0);"),
+            "{code}"
+        );
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,132 @@
+use crate::common::symbol::{Symbol, SymbolKind};
+use crate::common::token::{Token, TokenType};
+use crate::common::types::Type;
+use std::fmt;
+use std::fmt::Formatter;
+use Declared::{Any, Of};
+
+// Famílias de tipos aceitas pelos parâmetros das rotinas pré-definidas, que não são tipos da
+// linguagem: 'ordinal' aceita qualquer ordinal, 'pointer' qualquer ponteiro e 'printable'
+// ordinais, reais e textos. Como retorno, 'ordinal' indica o mesmo tipo do argumento
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Generic {
+    Ordinal,
+    Pointer,
+    Printable,
+}
+
+impl fmt::Display for Generic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Generic::Ordinal => write!(f, "ordinal"),
+            Generic::Pointer => write!(f, "pointer"),
+            Generic::Printable => write!(f, "printable"),
+        }
+    }
+}
+
+// Tipo de um parâmetro ou do retorno de uma rotina pré-definida
+enum Declared {
+    Of(Type),
+    Any(Generic),
+}
+
+type Parameters = &'static [(&'static str, Declared)];
+
+// Funções pré-definidas: (nome, tipo de retorno, parâmetros)
+// Um mesmo nome pode aparecer mais de uma vez, cada entrada é uma sobrecarga
+const FUNCTIONS: [(&str, Declared, Parameters); 19] = [
+    ("abs", Of(Type::Integer), &[("x", Of(Type::Integer))]),
+    ("abs", Of(Type::Real), &[("x", Of(Type::Real))]),
+    ("sqr", Of(Type::Integer), &[("x", Of(Type::Integer))]),
+    ("sqr", Of(Type::Real), &[("x", Of(Type::Real))]),
+    ("sqrt", Of(Type::Real), &[("x", Of(Type::Real))]),
+    ("sin", Of(Type::Real), &[("x", Of(Type::Real))]),
+    ("cos", Of(Type::Real), &[("x", Of(Type::Real))]),
+    ("exp", Of(Type::Real), &[("x", Of(Type::Real))]),
+    ("ln", Of(Type::Real), &[("x", Of(Type::Real))]),
+    ("trunc", Of(Type::Integer), &[("x", Of(Type::Real))]),
+    ("round", Of(Type::Integer), &[("x", Of(Type::Real))]),
+    ("odd", Of(Type::Boolean), &[("x", Of(Type::Integer))]),
+    ("ord", Of(Type::Integer), &[("x", Any(Generic::Ordinal))]),
+    ("chr", Of(Type::Char), &[("i", Of(Type::Integer))]),
+    (
+        "succ",
+        Any(Generic::Ordinal),
+        &[("x", Any(Generic::Ordinal))],
+    ),
+    (
+        "pred",
+        Any(Generic::Ordinal),
+        &[("x", Any(Generic::Ordinal))],
+    ),
+    ("length", Of(Type::Integer), &[("s", Of(Type::String))]),
+    (
+        "copy",
+        Of(Type::String),
+        &[
+            ("s", Of(Type::String)),
+            ("index", Of(Type::Integer)),
+            ("count", Of(Type::Integer)),
+        ],
+    ),
+    (
+        "pos",
+        Of(Type::Integer),
+        &[("substr", Of(Type::String)), ("s", Of(Type::String))],
+    ),
+];
+
+const PROCEDURES: [(&str, Parameters); 2] = [
+    ("new", &[("p", Any(Generic::Pointer))]),
+    ("dispose", &[("p", Any(Generic::Pointer))]),
+];
+
+// Procedimentos de saída, variádicos: o último parâmetro se repete para cada argumento a mais
+const OUTPUT: [(&str, Parameters); 3] = [
+    ("write", &[("value", Any(Generic::Printable))]),
+    ("writeln", &[]),
+    ("writeln", &[("value", Any(Generic::Printable))]),
+];
+
+// Símbolos do escopo pré-definido, que envolve o escopo global do programa
+pub fn prelude() -> Vec<Symbol> {
+    let functions = FUNCTIONS.iter().map(|(name, return_type, parameters)| {
+        builtin(name, SymbolKind::Function, return_type, parameters)
+    });
+    let procedure = Of(Type::Procedure);
+    let procedures = PROCEDURES
+        .iter()
+        .map(|(name, parameters)| builtin(name, SymbolKind::Procedure, &procedure, parameters));
+    let output = OUTPUT.iter().map(|(name, parameters)| {
+        let mut symbol = builtin(name, SymbolKind::Procedure, &procedure, parameters);
+        symbol.set_variadic(!parameters.is_empty());
+        symbol
+    });
+    functions.chain(procedures).chain(output).collect()
+}
+
+fn builtin(
+    name: &str,
+    kind: SymbolKind,
+    symbol_type: &Declared,
+    parameters: &[(&str, Declared)],
+) -> Symbol {
+    let declare = |name: &str, declared: &Declared| {
+        let mut symbol = Symbol::new(Token::new(name, TokenType::Identifier, 0, 0));
+        match declared {
+            Of(declared) => symbol.change_type(declared),
+            Any(generic) => symbol.set_generic(*generic),
+        }
+        symbol
+    };
+    let mut symbol = declare(name, symbol_type);
+    symbol.set_kind(kind);
+    symbol.set_parameters(
+        parameters
+            .iter()
+            .map(|(name, parameter_type)| declare(name, parameter_type))
+            .collect(),
+    );
+    symbol
+}
@@ -0,0 +1,61 @@
+use crate::common::token::{Span, Token};
+use std::error::Error;
+use std::fmt;
+use std::path::PathBuf;
+
+// Erro ou aviso da análise com a posição a que ele se refere, para que o servidor de linguagem
+// destaque o trecho sem depender do texto da mensagem
+#[derive(PartialEq, Debug, Clone)]
+pub struct Diagnostic {
+    pub message: String,
+    pub position: Option<(usize, usize)>, // Linha e coluna, ausentes no fim inesperado do arquivo
+    pub span: Span, // Trecho do código fonte, vazio quando só a posição é conhecida
+    pub file: Option<PathBuf>, // Unit de onde o aviso veio, ausente no próprio arquivo analisado
+}
+
+impl Diagnostic {
+    pub fn new(message: String, line: usize, column: usize) -> Self {
+        Self {
+            message,
+            position: Some((line, column)),
+            span: Span::default(),
+            file: None,
+        }
+    }
+
+    // Diagnóstico sobre o trecho do token
+    pub fn at(token: &Token, message: String) -> Self {
+        Self {
+            message,
+            position: Some((token.get_line(), token.get_column())),
+            span: token.get_span(),
+            file: None,
+        }
+    }
+}
+
+// Mensagens sem posição conhecida
+impl From<String> for Diagnostic {
+    fn from(message: String) -> Self {
+        Self {
+            message,
+            position: None,
+            span: Span::default(),
+            file: None,
+        }
+    }
+}
+
+impl From<&str> for Diagnostic {
+    fn from(message: &str) -> Self {
+        Self::from(message.to_string())
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for Diagnostic {}
@@ -0,0 +1,304 @@
+use std::fmt;
+use std::fmt::Formatter;
+use std::iter::Peekable;
+use std::str::Chars;
+
+// Valor JSON usado nas mensagens do servidor de linguagem e nas saídas em JSON da linha de comando
+#[derive(PartialEq, Debug, Clone)]
+pub enum Json {
+    Null,
+    Boolean(bool),
+    Number(f64),
+    Text(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+const NULL: Json = Json::Null;
+
+impl Json {
+    pub fn object<const N: usize>(fields: [(&str, Json); N]) -> Self {
+        Json::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        )
+    }
+
+    // Texto indentado com dois espaços por nível, para ser lido por pessoas
+    pub fn pretty(&self) -> String {
+        let mut text = String::new();
+        self.write_pretty(&mut text, 0);
+        text
+    }
+
+    fn write_pretty(&self, text: &mut String, depth: usize) {
+        let indent = "  ".repeat(depth + 1);
+        let (open, close, items): (char, char, Vec<(Option<&str>, &Json)>) = match self {
+            Json::Array(values) if !values.is_empty() => {
+                ('[', ']', values.iter().map(|value| (None, value)).collect())
+            }
+            Json::Object(fields) if !fields.is_empty() => (
+                '{',
+                '}',
+                fields
+                    .iter()
+                    .map(|(key, value)| (Some(key.as_str()), value))
+                    .collect(),
+            ),
+            _ => {
+                text.push_str(&self.to_string());
+                return;
+            }
+        };
+        text.push(open);
+        for (index, (key, value)) in items.into_iter().enumerate() {
+            text.push_str(if index > 0 { ",\n" } else { "\n" });
+            text.push_str(&indent);
+            if let Some(key) = key {
+                text.push_str(&format!("{}: ", Json::Text(key.to_string())));
+            }
+            value.write_pretty(text, depth + 1);
+        }
+        text.push('\n');
+        text.push_str(&"  ".repeat(depth));
+        text.push(close);
+    }
+
+    // Campo de um objeto, ou Null quando ele não existe
+    pub fn get(&self, key: &str) -> &Json {
+        match self {
+            Json::Object(fields) => fields
+                .iter()
+                .find(|(name, _)| name == key)
+                .map_or(&NULL, |(_, value)| value),
+            _ => &NULL,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::Text(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_usize(&self) -> Option<usize> {
+        match self {
+            Json::Number(value) if *value >= 0.0 && value.fract() == 0.0 => Some(*value as usize),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Json::Boolean(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn parse(input: &str) -> Result<Json, String> {
+        let mut chars = input.chars().peekable();
+        let value = Self::value(&mut chars)?;
+        Self::skip_whitespace(&mut chars);
+        match chars.next() {
+            None => Ok(value),
+            Some(c) => Err(format!("Unexpected character '{c}' after the JSON value.")),
+        }
+    }
+
+    fn skip_whitespace(chars: &mut Peekable<Chars>) {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn expect(chars: &mut Peekable<Chars>, expected: &str) -> Result<(), String> {
+        for c in expected.chars() {
+            if chars.next() != Some(c) {
+                return Err(format!("Expected '{expected}' in the JSON input."));
+            }
+        }
+        Ok(())
+    }
+
+    fn value(chars: &mut Peekable<Chars>) -> Result<Json, String> {
+        Self::skip_whitespace(chars);
+        match chars.peek() {
+            None => Err("Unexpected end of the JSON input.".to_string()),
+            Some('n') => Self::expect(chars, "null").map(|_| Json::Null),
+            Some('t') => Self::expect(chars, "true").map(|_| Json::Boolean(true)),
+            Some('f') => Self::expect(chars, "false").map(|_| Json::Boolean(false)),
+            Some('"') => Self::text(chars).map(Json::Text),
+            Some('[') => {
+                chars.next();
+                let mut values = vec![];
+                Self::skip_whitespace(chars);
+                if chars.next_if_eq(&']').is_some() {
+                    return Ok(Json::Array(values));
+                }
+                loop {
+                    values.push(Self::value(chars)?);
+                    Self::skip_whitespace(chars);
+                    match chars.next() {
+                        Some(',') => continue,
+                        Some(']') => return Ok(Json::Array(values)),
+                        _ => return Err("Expected ',' or ']' in a JSON array.".to_string()),
+                    }
+                }
+            }
+            Some('{') => {
+                chars.next();
+                let mut fields = vec![];
+                Self::skip_whitespace(chars);
+                if chars.next_if_eq(&'}').is_some() {
+                    return Ok(Json::Object(fields));
+                }
+                loop {
+                    Self::skip_whitespace(chars);
+                    let key = Self::text(chars)?;
+                    Self::skip_whitespace(chars);
+                    Self::expect(chars, ":")?;
+                    fields.push((key, Self::value(chars)?));
+                    Self::skip_whitespace(chars);
+                    match chars.next() {
+                        Some(',') => continue,
+                        Some('}') => return Ok(Json::Object(fields)),
+                        _ => return Err("Expected ',' or '}' in a JSON object.".to_string()),
+                    }
+                }
+            }
+            Some(_) => {
+                let mut number = String::new();
+                while let Some(c) = chars
+                    .next_if(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+                {
+                    number.push(c);
+                }
+                number
+                    .parse()
+                    .map(Json::Number)
+                    .map_err(|_| format!("Invalid JSON number '{number}'."))
+            }
+        }
+    }
+
+    fn text(chars: &mut Peekable<Chars>) -> Result<String, String> {
+        Self::expect(chars, "\"")?;
+        let mut text = String::new();
+        loop {
+            match chars.next() {
+                None => return Err("Unterminated JSON string.".to_string()),
+                Some('"') => return Ok(text),
+                Some('\\') => match chars.next() {
+                    Some('n') => text.push('\n'),
+                    Some('t') => text.push('\t'),
+                    Some('r') => text.push('\r'),
+                    Some('b') => text.push('\u{8}'),
+                    Some('f') => text.push('\u{c}'),
+                    Some('u') => {
+                        let high = Self::code_unit(chars)?;
+                        // Caracteres fora do plano básico chegam como um par de substitutos UTF-16
+                        let code = if (0xD800..0xDC00).contains(&high) {
+                            Self::expect(chars, "\\u")?;
+                            let low = Self::code_unit(chars)?;
+                            0x10000 + ((high - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF)
+                        } else {
+                            high
+                        };
+                        text.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+                    }
+                    Some(c) => text.push(c),
+                    None => return Err("Unterminated JSON string.".to_string()),
+                },
+                Some(c) => text.push(c),
+            }
+        }
+    }
+
+    fn code_unit(chars: &mut Peekable<Chars>) -> Result<u32, String> {
+        let digits: String = chars.take(4).collect();
+        u32::from_str_radix(&digits, 16).map_err(|_| format!("Invalid JSON escape '\\u{digits}'."))
+    }
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Boolean(value) => write!(f, "{value}"),
+            Json::Number(value) => write!(f, "{value}"),
+            Json::Text(value) => {
+                write!(f, "\"")?;
+                for c in value.chars() {
+                    match c {
+                        '"' => write!(f, "\\\"")?,
+                        '\\' => write!(f, "\\\\")?,
+                        '\n' => write!(f, "\\n")?,
+                        '\r' => write!(f, "\\r")?,
+                        '\t' => write!(f, "\\t")?,
+                        c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+                        c => write!(f, "{c}")?,
+                    }
+                }
+                write!(f, "\"")
+            }
+            Json::Array(values) => {
+                write!(f, "[")?;
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(fields) => {
+                write!(f, "{{")?;
+                for (index, (key, value)) in fields.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}:{value}", Json::Text(key.to_owned()))?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Self {
+        Json::Text(value.to_string())
+    }
+}
+
+impl From<String> for Json {
+    fn from(value: String) -> Self {
+        Json::Text(value)
+    }
+}
+
+impl From<usize> for Json {
+    fn from(value: usize) -> Self {
+        Json::Number(value as f64)
+    }
+}
+
+impl From<bool> for Json {
+    fn from(value: bool) -> Self {
+        Json::Boolean(value)
+    }
+}
+
+impl From<Vec<Json>> for Json {
+    fn from(values: Vec<Json>) -> Self {
+        Json::Array(values)
+    }
+}
@@ -1,3 +1,7 @@
+pub mod builtins;
+pub mod diagnostic;
+pub mod json;
 pub mod symbol;
 pub mod token;
+pub mod types;
 pub mod utils;
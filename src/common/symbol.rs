@@ -1,18 +1,41 @@
+use crate::common::builtins::Generic;
 use crate::common::token::Token;
 use crate::common::token::TokenType::Identifier;
+use crate::common::types::Type;
 use std::fmt;
 use std::fmt::Formatter;
 
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Clone)]
 pub enum Symbol {
     Identifier(SymbolIdentifier),
     EOS, // End of Scope
 }
 
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum SymbolKind {
+    Program,
+    Variable,
+    Procedure,
+    Function,
+    Type,
+    Constant,
+}
+
 #[derive(PartialEq, Debug, Clone)]
 pub struct SymbolIdentifier {
     pub token: Token,
-    pub identifier_type: String,
+    pub kind: SymbolKind,
+    pub identifier_type: Type,    // Em funções é o tipo de retorno
+    pub generic: Option<Generic>, // Família de tipos dos parâmetros e retornos pré-definidos
+    pub parameters: Vec<Symbol>,  // Parâmetros formais, usado apenas por procedimentos e funções
+    pub forward: bool,            // Procedimento declarado com 'forward' que ainda não tem corpo
+    pub variadic: bool,           // O último parâmetro se repete, como em write e writeln
+    pub value: String,            // Valor de constantes, a posição no caso de constantes enumeradas
+    pub read: bool,               // Variável lida em alguma expressão
+    pub written: bool,            // Variável que recebe um valor em algum comando
+    pub assigned: bool, // Variável que com certeza já recebeu um valor no ponto atual da análise
+    pub unit: Option<String>, // Unit que exporta o símbolo, vazio nos declarados no próprio arquivo
 }
 
 impl fmt::Display for Symbol {
@@ -33,7 +56,10 @@ impl PartialEq for Symbol {
         if self.is_eos() && other.is_eos() {
             true
         } else if self.is_identifier() && other.is_identifier() {
-            self.as_token().unwrap().get_lexeme() == other.as_token().unwrap().get_lexeme()
+            other
+                .as_token()
+                .unwrap()
+                .is_lexeme(self.as_token().unwrap().get_lexeme())
         } else {
             false
         }
@@ -44,7 +70,17 @@ impl Symbol {
     pub fn new(token: Token) -> Self {
         Symbol::Identifier(SymbolIdentifier {
             token,
-            identifier_type: "".to_string(),
+            kind: SymbolKind::Variable,
+            identifier_type: Type::Unknown,
+            generic: None,
+            parameters: vec![],
+            forward: false,
+            variadic: false,
+            value: "".to_string(),
+            read: false,
+            written: false,
+            assigned: false,
+            unit: None,
         })
     }
 
@@ -55,15 +91,153 @@ impl Symbol {
         }
     }
 
-    pub fn change_type(&mut self, new_type: &str) {
+    pub fn change_type(&mut self, new_type: &Type) {
         match self {
             Symbol::Identifier(t) => {
-                t.identifier_type = new_type.to_string();
+                t.identifier_type = new_type.to_owned();
             }
             Symbol::EOS => {}
         }
     }
 
+    pub fn get_kind(&self) -> Option<SymbolKind> {
+        match self {
+            Symbol::Identifier(t) => Some(t.kind),
+            Symbol::EOS => None,
+        }
+    }
+
+    pub fn set_kind(&mut self, kind: SymbolKind) {
+        if let Symbol::Identifier(t) = self {
+            t.kind = kind;
+        }
+    }
+
+    pub fn is_callable(&self) -> bool {
+        matches!(
+            self.get_kind(),
+            Some(SymbolKind::Procedure) | Some(SymbolKind::Function)
+        )
+    }
+
+    pub fn get_parameters(&self) -> Vec<Symbol> {
+        match self {
+            Symbol::Identifier(t) => t.parameters.to_owned(),
+            Symbol::EOS => vec![],
+        }
+    }
+
+    pub fn set_parameters(&mut self, parameters: Vec<Symbol>) {
+        if let Symbol::Identifier(t) = self {
+            t.parameters = parameters;
+        }
+    }
+
+    pub fn is_variadic(&self) -> bool {
+        match self {
+            Symbol::Identifier(t) => t.variadic,
+            Symbol::EOS => false,
+        }
+    }
+
+    pub fn set_variadic(&mut self, value: bool) {
+        if let Symbol::Identifier(t) = self {
+            t.variadic = value;
+        }
+    }
+
+    pub fn accepts_arguments(&self, amount: usize) -> bool {
+        let parameters = self.get_parameters().len();
+        amount == parameters || self.is_variadic() && amount > parameters
+    }
+
+    // Parâmetro que recebe cada um dos amount argumentos, o último se repete nos variádicos
+    pub fn parameters_for(&self, amount: usize) -> Vec<Symbol> {
+        let mut parameters = self.get_parameters();
+        if let Some(last) = parameters.last().filter(|_| self.is_variadic()).cloned() {
+            parameters.resize(amount.max(parameters.len()), last);
+        }
+        parameters
+    }
+
+    pub fn get_unit(&self) -> Option<&str> {
+        match self {
+            Symbol::Identifier(t) => t.unit.as_deref(),
+            Symbol::EOS => None,
+        }
+    }
+
+    pub fn set_unit(&mut self, unit: &str) {
+        if let Symbol::Identifier(t) = self {
+            t.unit = Some(unit.to_string());
+        }
+    }
+
+    pub fn is_forward(&self) -> bool {
+        match self {
+            Symbol::Identifier(t) => t.forward,
+            Symbol::EOS => false,
+        }
+    }
+
+    pub fn set_forward(&mut self, value: bool) {
+        if let Symbol::Identifier(t) = self {
+            t.forward = value;
+        }
+    }
+
+    pub fn get_value(&self) -> Option<String> {
+        match self {
+            Symbol::Identifier(t) => Some(t.value.to_owned()),
+            Symbol::EOS => None,
+        }
+    }
+
+    pub fn set_value(&mut self, value: &str) {
+        if let Symbol::Identifier(t) = self {
+            t.value = value.to_string();
+        }
+    }
+
+    pub fn is_read(&self) -> bool {
+        match self {
+            Symbol::Identifier(t) => t.read,
+            Symbol::EOS => false,
+        }
+    }
+
+    pub fn set_read(&mut self, value: bool) {
+        if let Symbol::Identifier(t) = self {
+            t.read = value;
+        }
+    }
+
+    pub fn is_written(&self) -> bool {
+        match self {
+            Symbol::Identifier(t) => t.written,
+            Symbol::EOS => false,
+        }
+    }
+
+    pub fn set_written(&mut self, value: bool) {
+        if let Symbol::Identifier(t) = self {
+            t.written = value;
+        }
+    }
+
+    pub fn is_assigned(&self) -> bool {
+        match self {
+            Symbol::Identifier(t) => t.assigned,
+            Symbol::EOS => false,
+        }
+    }
+
+    pub fn set_assigned(&mut self, value: bool) {
+        if let Symbol::Identifier(t) = self {
+            t.assigned = value;
+        }
+    }
+
     pub fn is_eos(&self) -> bool {
         matches!(self, Symbol::EOS)
     }
@@ -75,13 +249,34 @@ impl Symbol {
         }
     }
 
-    pub fn get_type(&self) -> Option<String> {
+    pub fn get_type(&self) -> Option<Type> {
         match self {
             Symbol::Identifier(t) => Some(t.identifier_type.to_owned()),
             Symbol::EOS => None,
         }
     }
 
+    pub fn get_generic(&self) -> Option<Generic> {
+        match self {
+            Symbol::Identifier(t) => t.generic,
+            Symbol::EOS => None,
+        }
+    }
+
+    pub fn set_generic(&mut self, generic: Generic) {
+        if let Symbol::Identifier(t) = self {
+            t.generic = Some(generic);
+        }
+    }
+
+    // Texto do tipo, ou da família de tipos nas rotinas pré-definidas
+    pub fn type_name(&self) -> String {
+        match self.get_generic() {
+            Some(generic) => generic.to_string(),
+            None => self.get_type().unwrap_or_default().to_string(),
+        }
+    }
+
     /*
     pub fn is_integer(&self) -> bool {
         matches!(self, Symbol::Integer(_))
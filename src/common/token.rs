@@ -1,26 +1,24 @@
 use std::fmt;
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
 pub enum TokenType {
-    Keyword, // program, var, integer, real, boolean, procedure, begin, end, if, then, else, while, do, not
+    Keyword, // program, unit, interface, implementation, uses, const, type, var, integer, real, boolean, char, string, array, set, of, record, nil, procedure, forward, begin, end, if, then, else, while, do, for, to, downto, repeat, until, case, break, continue, exit, not
     Identifier, // [a-z|A-Z]+[0-9]*[_]*
-    Integer, // [0-9]+
-    Real,    // [0-9]+.[0-9]*
-    Delimiter, // ; . : ( ) ,
-    RelationalOperators, // = < > <= >= <>
+    Integer, // [0-9]+ | $[0-9a-fA-F]+
+    Real,    // [0-9]+.[0-9]+([eE][+-]?[0-9]+)? | [0-9]+[eE][+-]?[0-9]+
+    Delimiter, // ; . .. : ( ) [ ] ^ ,
+    RelationalOperators, // = < > <= >= <> in
     Assignment, // :=
     AdditiveOperators, // + - or
     MultiplicativeOperators, // * / and
     Boolean,
+    Char,    // '[^']' | ''''
+    Text,    // '([^']|'')*'
+    Comment, // {[^}]*}, apenas quando o analisador léxico mantém os comentários
+    #[default]
     Invalid,
 }
 
-impl Default for TokenType {
-    fn default() -> Self {
-        TokenType::Invalid
-    }
-}
-
 impl fmt::Display for TokenType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -37,18 +35,77 @@ impl fmt::Display for TokenType {
                 TokenType::AdditiveOperators => "Additive Operators",
                 TokenType::MultiplicativeOperators => "Multiplicative Operators",
                 TokenType::Boolean => "Boolean",
+                TokenType::Char => "Char",
+                TokenType::Text => "String",
+                TokenType::Comment => "Comment",
                 TokenType::Invalid => "Invalid",
             }
         )
     }
 }
 
+// Posição do lexema no código fonte, em bytes, do início até logo depois do fim
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+// Token produzido pelo analisador léxico, com o lexema emprestado do código fonte
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Lexeme<'s> {
+    lexeme: &'s str,
+    category: TokenType,
+    line: usize,
+    column: usize,
+    span: Span,
+}
+
+impl<'s> Lexeme<'s> {
+    pub fn new(
+        source: &'s str,
+        category: TokenType,
+        line: usize,
+        column: usize,
+        span: Span,
+    ) -> Self {
+        Self {
+            lexeme: &source[span.start..span.end],
+            category,
+            line,
+            column,
+            span,
+        }
+    }
+
+    pub fn get_type(&self) -> TokenType {
+        self.category
+    }
+
+    pub fn get_lexeme(&self) -> &'s str {
+        self.lexeme
+    }
+
+    pub fn get_line(&self) -> usize {
+        self.line
+    }
+
+    pub fn get_column(&self) -> usize {
+        self.column
+    }
+
+    pub fn get_span(&self) -> Span {
+        self.span
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct Token {
     lexeme: String,
     category: TokenType,
     line: usize,
     column: usize,
+    span: Span, // Vazio nos tokens criados fora do analisador léxico
 }
 
 impl Token {
@@ -58,6 +115,7 @@ impl Token {
             category,
             line,
             column,
+            span: Span::default(),
         }
     }
 
@@ -73,6 +131,11 @@ impl Token {
         &self.lexeme
     }
 
+    // Pascal não diferencia maiúsculas de minúsculas, o lexema original é mantido para os diagnósticos
+    pub fn is_lexeme(&self, lexeme: &str) -> bool {
+        self.lexeme.eq_ignore_ascii_case(lexeme)
+    }
+
     pub fn get_line(&self) -> usize {
         self.line
     }
@@ -80,6 +143,22 @@ impl Token {
     pub fn get_column(&self) -> usize {
         self.column
     }
+
+    pub fn get_span(&self) -> Span {
+        self.span
+    }
+}
+
+impl From<Lexeme<'_>> for Token {
+    fn from(lexeme: Lexeme<'_>) -> Self {
+        Token {
+            lexeme: lexeme.lexeme.to_string(),
+            category: lexeme.category,
+            line: lexeme.line,
+            column: lexeme.column,
+            span: lexeme.span,
+        }
+    }
 }
 
 impl PartialEq for Token {
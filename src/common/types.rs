@@ -0,0 +1,111 @@
+use std::fmt;
+use std::fmt::Formatter;
+
+// Tipo de um símbolo ou de uma expressão, como calculado pela verificação de tipos. O texto de
+// cada tipo é o da sua definição no programa: 'integer', '1..10', 'array[color] of real'
+#[derive(PartialEq, Debug, Clone, Default)]
+#[non_exhaustive]
+pub enum Type {
+    Integer,
+    Real,
+    Boolean,
+    Char,
+    String,
+    Enumeration(String, Vec<String>), // Nome do tipo e as suas constantes, na ordem
+    Subrange(Box<Type>, i64, i64),    // Tipo dos limites e os ordinais do menor e do maior
+    Array(Box<Type>, Box<Type>),      // Tipo do índice e dos elementos
+    Set(Box<Type>),
+    Record(Vec<(String, Type)>), // Campos, com os nomes em minúsculo
+    Pointer(Box<Type>),
+    Named(String), // Tipo apontado pelo nome, resolvido no acesso, o que permite tipos recursivos
+    Nil,
+    EmptySet,  // O conjunto vazio '[]', compatível com todos os conjuntos
+    Procedure, // Procedimentos, que não têm valor
+    #[default]
+    Unknown, // Símbolos sem tipo, como o nome do programa
+}
+
+impl Type {
+    // Subintervalos são tratados como o tipo dos seus limites
+    pub fn host(&self) -> &Type {
+        match self {
+            Type::Subrange(host, _, _) => host,
+            _ => self,
+        }
+    }
+
+    pub fn is_ordinal(&self) -> bool {
+        matches!(
+            self.host(),
+            Type::Integer | Type::Char | Type::Boolean | Type::Enumeration(..)
+        )
+    }
+
+    pub fn is_textual(&self) -> bool {
+        matches!(self, Type::Char | Type::String)
+    }
+
+    // Menor e maior ordinal dos tipos que podem ser índices de arrays, o integer não tem limites
+    pub fn range(&self) -> Option<(i64, i64)> {
+        match self {
+            Type::Char => Some((0, 255)),
+            Type::Boolean => Some((0, 1)),
+            Type::Enumeration(_, constants) => Some((0, constants.len() as i64 - 1)),
+            Type::Subrange(_, low, high) => Some((*low, *high)),
+            _ => None,
+        }
+    }
+
+    // Texto de um ordinal do tipo, como escrito no programa
+    pub fn ordinal_name(&self, ordinal: i64) -> String {
+        let quote = |text: String| format!("'{}'", text.replace('\'', "''"));
+        match self.host() {
+            Type::Boolean => (ordinal != 0).to_string(),
+            Type::Char => match u32::try_from(ordinal).ok().and_then(char::from_u32) {
+                Some(value) => quote(value.to_string()),
+                None => ordinal.to_string(),
+            },
+            Type::Enumeration(_, constants) => usize::try_from(ordinal)
+                .ok()
+                .and_then(|index| constants.get(index))
+                .cloned()
+                .unwrap_or_else(|| ordinal.to_string()),
+            _ => ordinal.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Integer => write!(f, "integer"),
+            Type::Real => write!(f, "real"),
+            Type::Boolean => write!(f, "boolean"),
+            Type::Char => write!(f, "char"),
+            Type::String => write!(f, "string"),
+            Type::Enumeration(name, _) | Type::Named(name) => write!(f, "{name}"),
+            Type::Subrange(host, low, high) => {
+                write!(
+                    f,
+                    "{}..{}",
+                    host.ordinal_name(*low),
+                    host.ordinal_name(*high)
+                )
+            }
+            Type::Array(index, element) => write!(f, "array[{index}] of {element}"),
+            Type::Set(base) => write!(f, "set of {base}"),
+            Type::Record(fields) => {
+                let fields: Vec<String> = fields
+                    .iter()
+                    .map(|(name, field_type)| format!("{name}: {field_type}"))
+                    .collect();
+                write!(f, "record {} end", fields.join("; "))
+            }
+            Type::Pointer(target) => write!(f, "^{target}"),
+            Type::Nil => write!(f, "nil"),
+            Type::EmptySet => write!(f, "[]"),
+            Type::Procedure => write!(f, "procedure"),
+            Type::Unknown => Ok(()),
+        }
+    }
+}
@@ -1,10 +1,24 @@
-const KEYWORDS: [&str; 14] = [
+const KEYWORDS: [&str; 37] = [
     "program",
+    "unit",
+    "interface",
+    "implementation",
+    "uses",
+    "const",
+    "type",
     "var",
     "integer",
     "real",
     "boolean",
+    "char",
+    "string",
+    "array",
+    "set",
+    "of",
+    "record",
+    "nil",
     "procedure",
+    "forward",
     "begin",
     "end",
     "if",
@@ -12,9 +26,48 @@ const KEYWORDS: [&str; 14] = [
     "else",
     "while",
     "do",
+    "for",
+    "to",
+    "downto",
+    "repeat",
+    "until",
+    "case",
+    "break",
+    "continue",
+    "exit",
     "not",
 ];
-const CHECK_TYPES: [&str; 3] = ["integer", "real", "boolean"];
+const CHECK_TYPES: [&str; 5] = ["integer", "real", "boolean", "char", "string"];
 pub fn is_keyword(input: &str) -> bool {
-    KEYWORDS.contains(&input)
+    KEYWORDS.contains(&input.to_ascii_lowercase().as_str())
 }
+
+pub fn is_type(input: &str) -> bool {
+    CHECK_TYPES.contains(&input.to_ascii_lowercase().as_str())
+}
+
+pub const MAX_INTEGER: i64 = i32::MAX as i64;
+
+// Converte um literal inteiro, decimal ou hexadecimal ('$FF'), respeitando o tamanho do integer
+pub fn parse_integer(lexeme: &str) -> Option<i64> {
+    let value = match lexeme.strip_prefix('$') {
+        Some(hex) => i64::from_str_radix(hex, 16).ok()?,
+        None => lexeme.parse::<i64>().ok()?,
+    };
+    if value > MAX_INTEGER {
+        return None;
+    }
+    Some(value)
+}
+
+// Remove as aspas de um literal de texto e desfaz o escape de aspas duplicadas
+pub fn unquote(lexeme: &str) -> String {
+    let inner = lexeme
+        .strip_prefix('\'')
+        .and_then(|value| value.strip_suffix('\''))
+        .unwrap_or(lexeme);
+    inner.replace("''", "'")
+}
+
+// Conjuntos são representados como mapas de bits, com elementos de ordinal entre 0 e 255
+pub const MAX_SET_SIZE: i64 = 256;
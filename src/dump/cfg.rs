@@ -0,0 +1,311 @@
+use super::dot::Dot;
+use crate::syntactic::ast::{Declaration, Expression, ExpressionKind, Program, Statement};
+
+// Aresta ainda sem destino: o nó de onde ela sai e o seu rótulo
+type Exit = (usize, Option<String>);
+
+#[derive(Clone, Copy)]
+enum Shape {
+    Terminal, // Entrada e saída do procedimento
+    Block,    // Bloco básico, com comandos executados em sequência
+    Decision, // Condição de um if, laço ou case
+}
+
+// Breaks e continues de um laço, ligados ao fim e ao teste do laço quando ele termina
+#[derive(Default)]
+struct Loop {
+    breaks: Vec<Exit>,
+    continues: Vec<Exit>,
+}
+
+// Grafo de fluxo de controle do corpo de um procedimento ou do programa
+struct Graph {
+    nodes: Vec<(String, Shape)>,
+    edges: Vec<(usize, usize, Option<String>)>,
+    open: Option<usize>, // Bloco básico que ainda recebe os comandos simples seguintes
+    loops: Vec<Loop>,
+    exit: usize,
+}
+
+// Um subgrafo por procedimento, incluindo os aninhados, e um para o corpo do programa
+pub(super) fn graphs(program: &Program) -> String {
+    let mut dot = Dot::new(&program.name);
+    procedures(&mut dot, &program.declarations);
+    draw(
+        &mut dot,
+        &format!("program {}", program.name),
+        &program.body,
+    );
+    dot.finish()
+}
+
+fn procedures(dot: &mut Dot, declarations: &[Declaration]) {
+    for declaration in declarations {
+        if let Declaration::Procedure(procedure) = declaration {
+            procedures(dot, &procedure.declarations);
+            let label = format!("procedure {}", procedure.name);
+            draw(dot, &label, &procedure.body);
+        }
+    }
+}
+
+fn draw(dot: &mut Dot, label: &str, body: &[Statement]) {
+    let mut graph = Graph {
+        nodes: vec![],
+        edges: vec![],
+        open: None,
+        loops: vec![],
+        exit: 0,
+    };
+    let entry = graph.add("entry".to_string(), Shape::Terminal);
+    graph.exit = graph.add("exit".to_string(), Shape::Terminal);
+    let exits = graph.sequence(body, vec![(entry, None)]);
+    graph.connect(exits, graph.exit);
+
+    dot.open_cluster(label);
+    let ids: Vec<String> = graph
+        .nodes
+        .iter()
+        .map(|(label, shape)| {
+            let shape = match shape {
+                Shape::Terminal => "oval",
+                Shape::Block => "box",
+                Shape::Decision => "diamond",
+            };
+            dot.node(label, shape)
+        })
+        .collect();
+    for (from, to, label) in &graph.edges {
+        dot.edge(&ids[*from], &ids[*to], label.as_deref());
+    }
+    dot.close_cluster();
+}
+
+impl Graph {
+    fn add(&mut self, label: String, shape: Shape) -> usize {
+        self.nodes.push((label, shape));
+        self.open = None;
+        self.nodes.len() - 1
+    }
+
+    fn connect(&mut self, from: Vec<Exit>, to: usize) {
+        for (node, label) in from {
+            self.edges.push((node, to, label));
+        }
+    }
+
+    fn after(&mut self, from: Vec<Exit>, label: String, shape: Shape) -> usize {
+        let node = self.add(label, shape);
+        self.connect(from, node);
+        node
+    }
+
+    fn sequence(&mut self, statements: &[Statement], mut from: Vec<Exit>) -> Vec<Exit> {
+        for statement in statements {
+            from = self.statement(statement, from);
+        }
+        from
+    }
+
+    // Comandos simples seguidos entram no mesmo bloco básico enquanto o fluxo não se divide
+    fn simple(&mut self, text: String, from: Vec<Exit>) -> Vec<Exit> {
+        if let (Some(open), [(node, None)]) = (self.open, from.as_slice()) {
+            if open == *node {
+                self.nodes[open].0.push('\n');
+                self.nodes[open].0.push_str(&text);
+                return from;
+            }
+        }
+        let block = self.after(from, text, Shape::Block);
+        self.open = Some(block);
+        vec![(block, None)]
+    }
+
+    // Corpo de um laço: o fim do corpo e os continues voltam para o teste
+    fn body(&mut self, statement: &Statement, test: usize, label: &str) -> Vec<Exit> {
+        self.loops.push(Loop::default());
+        let exits = self.statement(statement, vec![(test, Some(label.to_string()))]);
+        let current = self.loops.pop().unwrap_or_default();
+        self.connect(exits, test);
+        self.connect(current.continues, test);
+        current.breaks
+    }
+
+    fn statement(&mut self, statement: &Statement, from: Vec<Exit>) -> Vec<Exit> {
+        let branch = |node: usize, label: &str| (node, Some(label.to_string()));
+        match statement {
+            Statement::Assignment(target, value) => {
+                self.simple(format!("{} := {}", source(target), source(value)), from)
+            }
+            Statement::Call(name, arguments, _, _) => self.simple(call(name, arguments), from),
+            Statement::Block(statements) => self.sequence(statements, from),
+            Statement::If(condition, then, otherwise) => {
+                let test = self.after(from, format!("if {}", source(condition)), Shape::Decision);
+                let mut exits = self.statement(then, vec![branch(test, "true")]);
+                match otherwise {
+                    Some(otherwise) => {
+                        exits.extend(self.statement(otherwise, vec![branch(test, "false")]))
+                    }
+                    None => exits.push(branch(test, "false")),
+                }
+                self.open = None;
+                exits
+            }
+            Statement::While(condition, body) => {
+                let label = format!("while {}", source(condition));
+                let test = self.after(from, label, Shape::Decision);
+                let mut exits = self.body(body, test, "true");
+                exits.push(branch(test, "false"));
+                self.open = None;
+                exits
+            }
+            Statement::For {
+                variable,
+                start,
+                end,
+                downto,
+                body,
+            } => {
+                let direction = if *downto { "downto" } else { "to" };
+                let label = format!(
+                    "for {} := {} {direction} {}",
+                    source(variable),
+                    source(start),
+                    source(end)
+                );
+                let test = self.after(from, label, Shape::Decision);
+                let mut exits = self.body(body, test, "next");
+                exits.push(branch(test, "done"));
+                self.open = None;
+                exits
+            }
+            Statement::Repeat(body, condition) => {
+                // O corpo começa em um bloco novo, destino da volta do laço
+                self.open = None;
+                let head = self.nodes.len();
+                self.loops.push(Loop::default());
+                let exits = self.sequence(body, from);
+                let current = self.loops.pop().unwrap_or_default();
+                let label = format!("until {}", source(condition));
+                let test = self.after(exits, label, Shape::Decision);
+                self.connect(current.continues, test);
+                self.edges.push((test, head, Some("false".to_string())));
+                let mut exits = current.breaks;
+                exits.push(branch(test, "true"));
+                exits
+            }
+            Statement::Case {
+                selector,
+                elements,
+                otherwise,
+            } => {
+                let label = format!("case {} of", source(selector));
+                let test = self.after(from, label, Shape::Decision);
+                let mut exits = vec![];
+                for (labels, body) in elements {
+                    let labels: Vec<String> = labels
+                        .iter()
+                        .map(|(low, high)| {
+                            let first = selector.value_type.ordinal_name(*low);
+                            if low == high {
+                                first
+                            } else {
+                                format!("{first}..{}", selector.value_type.ordinal_name(*high))
+                            }
+                        })
+                        .collect();
+                    exits.extend(self.statement(body, vec![branch(test, &labels.join(", "))]));
+                }
+                match otherwise {
+                    Some(body) => exits.extend(self.sequence(body, vec![branch(test, "else")])),
+                    None => exits.push(branch(test, "else")),
+                }
+                self.open = None;
+                exits
+            }
+            Statement::Break => {
+                if let Some(current) = self.loops.last_mut() {
+                    current.breaks.extend(from);
+                }
+                vec![]
+            }
+            Statement::Continue => {
+                if let Some(current) = self.loops.last_mut() {
+                    current.continues.extend(from);
+                }
+                vec![]
+            }
+            Statement::Exit => {
+                self.connect(from, self.exit);
+                vec![]
+            }
+        }
+    }
+}
+
+fn call(name: &str, arguments: &[Expression]) -> String {
+    if arguments.is_empty() {
+        return name.to_string();
+    }
+    let arguments: Vec<String> = arguments.iter().map(source).collect();
+    format!("{name}({})", arguments.join(", "))
+}
+
+// Precedência dos operadores, para escrever os parênteses apenas onde são necessários
+fn precedence(expression: &Expression) -> u8 {
+    match &expression.kind {
+        ExpressionKind::Binary(operator, _, _) => match operator.as_str() {
+            "*" | "/" | "and" => 3,
+            "+" | "-" | "or" => 2,
+            _ => 1,
+        },
+        ExpressionKind::Conversion(operand) => precedence(operand),
+        _ => 4,
+    }
+}
+
+// Texto da expressão como seria escrito no programa, sem as conversões implícitas
+fn source(expression: &Expression) -> String {
+    let operand = |operand: &Expression, minimum: u8| {
+        if precedence(operand) < minimum {
+            format!("({})", source(operand))
+        } else {
+            source(operand)
+        }
+    };
+    match &expression.kind {
+        ExpressionKind::Literal(value) => value.as_token(0, 0).get_lexeme().to_string(),
+        ExpressionKind::Nil => "nil".to_string(),
+        ExpressionKind::Variable(name) => name.to_owned(),
+        ExpressionKind::Index(array, index) => format!("{}[{}]", source(array), source(index)),
+        ExpressionKind::Field(record, name) => format!("{}.{name}", source(record)),
+        ExpressionKind::Dereference(pointer) => format!("{}^", source(pointer)),
+        ExpressionKind::Call(name, arguments) => call(name, arguments),
+        ExpressionKind::Unary(operator, value) if operator == "not" => {
+            format!("not {}", operand(value, 4))
+        }
+        ExpressionKind::Unary(operator, value) => format!("{operator}{}", operand(value, 4)),
+        ExpressionKind::Binary(operator, left, right) => {
+            let level = precedence(expression);
+            // Os operadores associam à esquerda, o operando direito de mesmo nível leva parênteses,
+            // e comparações nunca se encadeiam sem eles
+            let left_level = if level == 1 { 2 } else { level };
+            format!(
+                "{} {operator} {}",
+                operand(left, left_level),
+                operand(right, level + 1)
+            )
+        }
+        ExpressionKind::Conversion(operand) => source(operand),
+        ExpressionKind::Set(elements) => {
+            let elements: Vec<String> = elements
+                .iter()
+                .map(|(low, high)| match high {
+                    None => source(low),
+                    Some(high) => format!("{}..{}", source(low), source(high)),
+                })
+                .collect();
+            format!("[{}]", elements.join(", "))
+        }
+    }
+}
@@ -0,0 +1,76 @@
+// Montagem de grafos no formato DOT do Graphviz
+pub(super) struct Dot {
+    lines: Vec<String>,
+    nodes: usize,
+    indent: usize,
+}
+
+impl Dot {
+    pub(super) fn new(name: &str) -> Self {
+        Self {
+            lines: vec![
+                format!("digraph {} {{", quote(name)),
+                "  node [fontname=\"monospace\"];".to_string(),
+            ],
+            nodes: 0,
+            indent: 1,
+        }
+    }
+
+    // Nós de cada subgrafo são agrupados em uma caixa com o nome dele
+    pub(super) fn open_cluster(&mut self, label: &str) {
+        let id = self.nodes;
+        self.nodes += 1;
+        self.push(format!("subgraph cluster_{id} {{"));
+        self.indent += 1;
+        self.push(format!("label={};", quote(label)));
+    }
+
+    pub(super) fn close_cluster(&mut self) {
+        self.indent -= 1;
+        self.push("}".to_string());
+    }
+
+    // Adiciona um nó e retorna o seu identificador
+    pub(super) fn node(&mut self, label: &str, shape: &str) -> String {
+        let id = format!("n{}", self.nodes);
+        self.nodes += 1;
+        self.push(format!("{id} [label={}, shape={shape}];", quote(label)));
+        id
+    }
+
+    pub(super) fn edge(&mut self, from: &str, to: &str, label: Option<&str>) {
+        match label {
+            Some(label) => self.push(format!("{from} -> {to} [label={}];", quote(label))),
+            None => self.push(format!("{from} -> {to};")),
+        }
+    }
+
+    fn push(&mut self, line: String) {
+        self.lines
+            .push(format!("{}{line}", "  ".repeat(self.indent)));
+    }
+
+    pub(super) fn finish(mut self) -> String {
+        self.lines.push("}".to_string());
+        format!("{}\n", self.lines.join("\n"))
+    }
+}
+
+// As quebras de linha alinham o texto à esquerda, como nos blocos do grafo de fluxo
+fn quote(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\l"),
+            c => quoted.push(c),
+        }
+    }
+    if text.contains('\n') {
+        quoted.push_str("\\l");
+    }
+    quoted.push('"');
+    quoted
+}
@@ -0,0 +1,473 @@
+mod cfg;
+mod dot;
+mod test;
+
+use crate::common::json::Json;
+use crate::common::token::Token;
+use crate::syntactic::ast::{Declaration, Expression, ExpressionKind, Program, Statement};
+use crate::syntactic::derivation::Derivation;
+use dot::Dot;
+use std::fmt;
+
+// Formatos de saída dos comandos tokens, ast, parse-tree e cfg
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Format {
+    Table,
+    Tree,
+    Sexp,
+    Json,
+    Dot,
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Self> {
+        let format = match name {
+            "table" => Format::Table,
+            "tree" => Format::Tree,
+            "sexp" => Format::Sexp,
+            "json" => Format::Json,
+            "dot" => Format::Dot,
+            _ => return None,
+        };
+        Some(format)
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Format::Table => "table",
+            Format::Tree => "tree",
+            Format::Sexp => "sexp",
+            Format::Json => "json",
+            Format::Dot => "dot",
+        };
+        write!(f, "{name}")
+    }
+}
+
+// Tokens em uma tabela alinhada ou em uma lista JSON
+pub fn tokens(tokens: &[Token], format: Format) -> String {
+    if format == Format::Json {
+        let tokens: Vec<Json> = tokens
+            .iter()
+            .map(|token| {
+                Json::object([
+                    ("lexeme", token.get_lexeme().into()),
+                    ("type", token.get_type().to_string().into()),
+                    ("line", token.get_line().into()),
+                    ("column", token.get_column().into()),
+                    ("start", token.get_span().start.into()),
+                    ("end", token.get_span().end.into()),
+                ])
+            })
+            .collect();
+        return format!("{}\n", Json::Array(tokens).pretty());
+    }
+
+    let rows: Vec<[String; 4]> = tokens
+        .iter()
+        .map(|token| {
+            [
+                token.get_line().to_string(),
+                token.get_column().to_string(),
+                token.get_type().to_string(),
+                token.get_lexeme().to_string(),
+            ]
+        })
+        .collect();
+    let header = ["Line", "Column", "Type", "Lexeme"].map(str::to_string);
+    let width = |index: usize| {
+        rows.iter()
+            .chain([&header])
+            .map(|row| row[index].chars().count())
+            .max()
+            .unwrap_or_default()
+    };
+    let (line, column, category) = (width(0), width(1), width(2));
+    let mut text = String::new();
+    for row in [&header].into_iter().chain(&rows) {
+        let [l, c, t, lexeme] = row;
+        text.push_str(&format!(
+            "{l:>line$}  {c:>column$}  {t:<category$}  {lexeme}\n"
+        ));
+    }
+    text
+}
+
+// Árvore sintática como árvore indentada, expressão S, JSON ou grafo DOT
+pub fn program(program: &Program, format: Format) -> String {
+    let node = Node::program(program, "program");
+    match format {
+        Format::Json => format!("{}\n", node.json().pretty()),
+        Format::Sexp => format!("{}\n", node.sexp(0)),
+        Format::Dot => {
+            let mut dot = Dot::new(&program.name);
+            node.dot(&mut dot);
+            dot.finish()
+        }
+        Format::Tree | Format::Table => node.tree(0),
+    }
+}
+
+// Árvore de derivação como árvore indentada ou grafo DOT, com os tokens em caixas
+pub fn derivation(derivation: &Derivation, format: Format) -> String {
+    fn tree(derivation: &Derivation, depth: usize, text: &mut String) {
+        text.push_str(&format!("{}{}\n", "  ".repeat(depth), derivation.label));
+        for child in &derivation.children {
+            tree(child, depth + 1, text);
+        }
+    }
+    fn dot(derivation: &Derivation, graph: &mut Dot) -> String {
+        let shape = if derivation.terminal {
+            "box"
+        } else {
+            "ellipse"
+        };
+        let id = graph.node(&derivation.label, shape);
+        for child in &derivation.children {
+            let child = dot(child, graph);
+            graph.edge(&id, &child, None);
+        }
+        id
+    }
+
+    if format == Format::Dot {
+        let mut graph = Dot::new(&derivation.label);
+        dot(derivation, &mut graph);
+        return graph.finish();
+    }
+    let mut text = String::new();
+    tree(derivation, 0, &mut text);
+    text
+}
+
+// Grafos de fluxo de controle de cada procedimento e do programa, em DOT
+pub fn control_flow(program: &Program) -> String {
+    cfg::graphs(program)
+}
+
+// Nó da árvore mostrada: o tipo do nó, os seus atributos e os filhos. Todos os formatos partem
+// dessa mesma representação
+struct Node {
+    kind: &'static str,
+    attributes: Vec<(&'static str, String)>,
+    children: Vec<Node>,
+    position: Option<(usize, usize)>, // Linha e coluna das expressões, apenas no JSON
+}
+
+impl Node {
+    fn new(
+        kind: &'static str,
+        attributes: Vec<(&'static str, String)>,
+        children: Vec<Node>,
+    ) -> Self {
+        Self {
+            kind,
+            attributes,
+            children,
+            position: None,
+        }
+    }
+
+    fn program(program: &Program, kind: &'static str) -> Self {
+        let units = program.units.iter().map(|unit| Node::program(unit, "unit"));
+        let declarations = program.declarations.iter().map(Node::declaration);
+        let body = Node::new("body", vec![], Node::statements(&program.body));
+        let children = units.chain(declarations).chain([body]).collect();
+        Node::new(kind, vec![("name", program.name.to_owned())], children)
+    }
+
+    fn declaration(declaration: &Declaration) -> Self {
+        match declaration {
+            Declaration::Constant(name, value) => Node::new(
+                "const",
+                vec![
+                    ("name", name.to_owned()),
+                    ("value", value.as_token(0, 0).get_lexeme().to_string()),
+                    ("type", value.get_type().to_string()),
+                ],
+                vec![],
+            ),
+            Declaration::Type(name, definition) => Node::new(
+                "type",
+                vec![("name", name.to_owned()), ("type", definition.to_string())],
+                vec![],
+            ),
+            Declaration::Enumeration(name, constants) => Node::new(
+                "enumeration",
+                vec![
+                    ("name", name.to_owned()),
+                    ("constants", format!("({})", constants.join(", "))),
+                ],
+                vec![],
+            ),
+            Declaration::Variable(name, variable_type) => Node::new(
+                "var",
+                vec![
+                    ("name", name.to_owned()),
+                    ("type", variable_type.to_string()),
+                ],
+                vec![],
+            ),
+            Declaration::Procedure(procedure) => {
+                let parameters = procedure.parameters.iter().map(|(name, parameter_type)| {
+                    Node::new(
+                        "parameter",
+                        vec![
+                            ("name", name.to_owned()),
+                            ("type", parameter_type.to_string()),
+                        ],
+                        vec![],
+                    )
+                });
+                let declarations = procedure.declarations.iter().map(Node::declaration);
+                let body = Node::new("body", vec![], Node::statements(&procedure.body));
+                let children = parameters.chain(declarations).chain([body]).collect();
+                Node::new(
+                    "procedure",
+                    vec![("name", procedure.name.to_owned())],
+                    children,
+                )
+            }
+        }
+    }
+
+    fn statements(statements: &[Statement]) -> Vec<Node> {
+        statements.iter().map(Node::statement).collect()
+    }
+
+    fn statement(statement: &Statement) -> Self {
+        match statement {
+            Statement::Assignment(target, value) => Node::new(
+                "assign",
+                vec![],
+                vec![Node::expression(target), Node::expression(value)],
+            ),
+            Statement::Call(name, arguments, _, _) => Node::new(
+                "call",
+                vec![("name", name.to_owned())],
+                arguments.iter().map(Node::expression).collect(),
+            ),
+            Statement::If(condition, then, otherwise) => {
+                let mut children = vec![Node::expression(condition), Node::statement(then)];
+                children.extend(otherwise.iter().map(|otherwise| Node::statement(otherwise)));
+                Node::new("if", vec![], children)
+            }
+            Statement::While(condition, body) => Node::new(
+                "while",
+                vec![],
+                vec![Node::expression(condition), Node::statement(body)],
+            ),
+            Statement::Repeat(body, condition) => Node::new(
+                "repeat",
+                vec![],
+                vec![
+                    Node::new("body", vec![], Node::statements(body)),
+                    Node::expression(condition),
+                ],
+            ),
+            Statement::For {
+                variable,
+                start,
+                end,
+                downto,
+                body,
+            } => Node::new(
+                "for",
+                vec![(
+                    "direction",
+                    if *downto { "downto" } else { "to" }.to_string(),
+                )],
+                vec![
+                    Node::expression(variable),
+                    Node::expression(start),
+                    Node::expression(end),
+                    Node::statement(body),
+                ],
+            ),
+            Statement::Case {
+                selector,
+                elements,
+                otherwise,
+            } => {
+                let elements = elements.iter().map(|(labels, body)| {
+                    let labels: Vec<String> = labels
+                        .iter()
+                        .map(|(low, high)| {
+                            if low == high {
+                                low.to_string()
+                            } else {
+                                format!("{low}..{high}")
+                            }
+                        })
+                        .collect();
+                    Node::new(
+                        "element",
+                        vec![("labels", labels.join(", "))],
+                        vec![Node::statement(body)],
+                    )
+                });
+                let otherwise = otherwise
+                    .iter()
+                    .map(|body| Node::new("otherwise", vec![], Node::statements(body)));
+                let children = [Node::expression(selector)]
+                    .into_iter()
+                    .chain(elements)
+                    .chain(otherwise)
+                    .collect();
+                Node::new("case", vec![], children)
+            }
+            Statement::Block(statements) => {
+                Node::new("block", vec![], Node::statements(statements))
+            }
+            Statement::Break => Node::new("break", vec![], vec![]),
+            Statement::Continue => Node::new("continue", vec![], vec![]),
+            Statement::Exit => Node::new("exit", vec![], vec![]),
+        }
+    }
+
+    fn expression(expression: &Expression) -> Self {
+        let node = |kind, attribute: Option<(&'static str, String)>, children| {
+            let mut attributes: Vec<_> = attribute.into_iter().collect();
+            attributes.push(("type", expression.value_type.to_string()));
+            Node {
+                position: Some((expression.line, expression.column)),
+                ..Node::new(kind, attributes, children)
+            }
+        };
+        match &expression.kind {
+            ExpressionKind::Literal(value) => node(
+                "literal",
+                Some(("value", value.as_token(0, 0).get_lexeme().to_string())),
+                vec![],
+            ),
+            ExpressionKind::Nil => node("nil", None, vec![]),
+            ExpressionKind::Variable(name) => {
+                node("variable", Some(("name", name.to_owned())), vec![])
+            }
+            ExpressionKind::Index(array, index) => node(
+                "index",
+                None,
+                vec![Node::expression(array), Node::expression(index)],
+            ),
+            ExpressionKind::Field(record, name) => node(
+                "field",
+                Some(("name", name.to_owned())),
+                vec![Node::expression(record)],
+            ),
+            ExpressionKind::Dereference(pointer) => {
+                node("dereference", None, vec![Node::expression(pointer)])
+            }
+            ExpressionKind::Call(name, arguments) => node(
+                "call",
+                Some(("name", name.to_owned())),
+                arguments.iter().map(Node::expression).collect(),
+            ),
+            ExpressionKind::Unary(operator, operand) => node(
+                "unary",
+                Some(("operator", operator.to_owned())),
+                vec![Node::expression(operand)],
+            ),
+            ExpressionKind::Binary(operator, left, right) => node(
+                "binary",
+                Some(("operator", operator.to_owned())),
+                vec![Node::expression(left), Node::expression(right)],
+            ),
+            ExpressionKind::Conversion(operand) => {
+                node("conversion", None, vec![Node::expression(operand)])
+            }
+            ExpressionKind::Set(elements) => node(
+                "set",
+                None,
+                elements
+                    .iter()
+                    .map(|(low, high)| match high {
+                        None => Node::expression(low),
+                        Some(high) => Node::new(
+                            "range",
+                            vec![],
+                            vec![Node::expression(low), Node::expression(high)],
+                        ),
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    // O tipo do nó, os atributos e, depois de ':', o tipo da expressão
+    fn label(&self) -> String {
+        let mut label = self.kind.to_string();
+        for (name, value) in &self.attributes {
+            match *name {
+                "type" => label.push_str(&format!(" : {value}")),
+                _ => label.push_str(&format!(" {value}")),
+            }
+        }
+        label
+    }
+
+    // Uma linha por nó, com os filhos indentados
+    fn tree(&self, depth: usize) -> String {
+        let mut line = format!("{}{}\n", "  ".repeat(depth), self.label());
+        for child in &self.children {
+            line.push_str(&child.tree(depth + 1));
+        }
+        line
+    }
+
+    // Os atributos vêm na ordem, com o tipo marcado por ':type', e os filhos em linhas próprias
+    fn sexp(&self, depth: usize) -> String {
+        let atom = |value: &str| {
+            let plain = !value.is_empty()
+                && !value
+                    .chars()
+                    .any(|c| c.is_whitespace() || "()\";".contains(c));
+            if plain {
+                value.to_string()
+            } else {
+                Json::Text(value.to_string()).to_string()
+            }
+        };
+        let mut text = format!("({}", self.kind);
+        for (name, value) in &self.attributes {
+            if *name == "type" {
+                text.push_str(" :type");
+            }
+            text.push(' ');
+            text.push_str(&atom(value));
+        }
+        for child in &self.children {
+            text.push('\n');
+            text.push_str(&"  ".repeat(depth + 1));
+            text.push_str(&child.sexp(depth + 1));
+        }
+        text.push(')');
+        text
+    }
+
+    fn dot(&self, graph: &mut Dot) -> String {
+        let id = graph.node(&self.label(), "box");
+        for child in &self.children {
+            let child = child.dot(graph);
+            graph.edge(&id, &child, None);
+        }
+        id
+    }
+
+    fn json(&self) -> Json {
+        let mut fields = vec![("kind".to_string(), Json::from(self.kind))];
+        for (name, value) in &self.attributes {
+            fields.push((name.to_string(), value.as_str().into()));
+        }
+        if let Some((line, column)) = self.position {
+            fields.push(("line".to_string(), line.into()));
+            fields.push(("column".to_string(), column.into()));
+        }
+        if !self.children.is_empty() {
+            let children = self.children.iter().map(Node::json).collect::<Vec<_>>();
+            fields.push(("children".to_string(), children.into()));
+        }
+        Json::Object(fields)
+    }
+}
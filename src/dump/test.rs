@@ -0,0 +1,184 @@
+#[cfg(test)]
+mod dump_tests {
+    use crate::common::json::Json;
+    use crate::dump::{control_flow, derivation, program, tokens, Format};
+    use crate::lexical::Scanner;
+    use crate::syntactic::Parser;
+
+    const PROGRAM: &str = "program soma;
+        var x: integer; r: real;
+        begin
+            x := 1 + 2 * x;
+            if x > 0 then r := x else writeln('x = ', x)
+        end.";
+
+    fn dump(format: Format) -> String {
+        let tokens = Scanner::new(PROGRAM).init().unwrap();
+        let mut parser = Parser::new(&tokens);
+        parser.check().unwrap();
+        program(parser.get_program().unwrap(), format)
+    }
+
+    #[test]
+    fn test_token_table() {
+        let scanned = Scanner::new("x := 'a b'").init().unwrap();
+        let expected = "Line  Column  Type        Lexeme
+   1       1  Identifier  x
+   1       3  Assignment  :=
+   1       6  String      'a b'
+";
+        assert_eq!(tokens(&scanned, Format::Table), expected);
+    }
+
+    #[test]
+    fn test_token_json() {
+        let scanned = Scanner::new("begin\n  x\nend").init().unwrap();
+        let json = Json::parse(&tokens(&scanned, Format::Json)).unwrap();
+        let tokens = json.as_array().unwrap();
+        assert_eq!(tokens.len(), 3);
+        assert_eq!(tokens[1].get("lexeme").as_str(), Some("x"));
+        assert_eq!(tokens[1].get("type").as_str(), Some("Identifier"));
+        assert_eq!(tokens[1].get("line").as_usize(), Some(2));
+        assert_eq!(tokens[1].get("column").as_usize(), Some(3));
+        assert_eq!(tokens[1].get("start").as_usize(), Some(8));
+        assert_eq!(tokens[1].get("end").as_usize(), Some(9));
+    }
+
+    #[test]
+    fn test_ast_tree() {
+        let expected = "program soma
+  var x : integer
+  var r : real
+  body
+    assign
+      variable x : integer
+      binary + : integer
+        literal 1 : integer
+        binary * : integer
+          literal 2 : integer
+          variable x : integer
+    if
+      binary > : boolean
+        variable x : integer
+        literal 0 : integer
+      assign
+        variable r : real
+        conversion : real
+          variable x : integer
+      call writeln
+        literal 'x = ' : string
+        variable x : integer
+";
+        assert_eq!(dump(Format::Tree), expected);
+    }
+
+    #[test]
+    fn test_ast_sexp() {
+        let sexp = dump(Format::Sexp);
+        assert!(sexp.starts_with("(program soma\n  (var x :type integer)\n"));
+        assert!(sexp.contains("(literal \"'x = '\" :type string)"));
+        assert!(sexp.ends_with("(variable x :type integer)))))\n"));
+        let open = sexp.matches('(').count();
+        assert_eq!(open, sexp.matches(')').count());
+    }
+
+    #[test]
+    fn test_ast_json() {
+        let json = Json::parse(&dump(Format::Json)).unwrap();
+        assert_eq!(json.get("kind").as_str(), Some("program"));
+        let body = &json.get("children").as_array().unwrap()[2];
+        let assignment = &body.get("children").as_array().unwrap()[0];
+        let sum = &assignment.get("children").as_array().unwrap()[1];
+        assert_eq!(sum.get("operator").as_str(), Some("+"));
+        assert_eq!(sum.get("type").as_str(), Some("integer"));
+        assert_eq!(sum.get("line").as_usize(), Some(4));
+    }
+
+    #[test]
+    fn test_derivation() {
+        let tokens = Scanner::new("program p; begin x := 1 end.").init().unwrap();
+        let mut parser = Parser::new(&tokens);
+        parser.set_derivation(true);
+        parser.check().unwrap_err();
+        assert_eq!(parser.get_derivation(), None);
+
+        let tokens = Scanner::new(PROGRAM).init().unwrap();
+        let mut parser = Parser::new(&tokens);
+        parser.set_derivation(true);
+        parser.check().unwrap();
+        let tree = derivation(&parser.get_derivation().unwrap(), Format::Tree);
+        assert!(tree.starts_with("programa\n  program\n  soma\n  ;\n"));
+        assert!(tree.contains("\n      list_of_commands\n"));
+        assert!(tree.contains("list_of_commands'\n"));
+
+        let dot = derivation(&parser.get_derivation().unwrap(), Format::Dot);
+        assert!(dot.starts_with("digraph \"programa\" {\n"));
+        assert!(dot.contains("[label=\"list_of_commands'\", shape=ellipse];"));
+        assert!(dot.contains("[label=\":=\", shape=box];"));
+    }
+
+    #[test]
+    fn test_ast_dot() {
+        let dot = dump(Format::Dot);
+        assert!(dot.starts_with("digraph \"soma\" {\n"));
+        assert!(dot.contains("n0 [label=\"program soma\", shape=box];"));
+        assert!(dot.contains("[label=\"literal 'x = ' : string\", shape=box];"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_control_flow() {
+        let source = "program laco;
+            var i: integer;
+            procedure mostra(n: integer);
+            begin
+                if n > 1 then exit;
+                writeln(n)
+            end;
+            begin
+                i := 0;
+                repeat
+                    i := i + 1;
+                    if i = 3 then continue;
+                    mostra(i)
+                until i >= 5
+            end.";
+        let tokens = Scanner::new(source).init().unwrap();
+        let mut parser = Parser::new(&tokens);
+        parser.check().unwrap();
+        let expected = "digraph \"laco\" {
+  node [fontname=\"monospace\"];
+  subgraph cluster_0 {
+    label=\"procedure mostra\";
+    n1 [label=\"entry\", shape=oval];
+    n2 [label=\"exit\", shape=oval];
+    n3 [label=\"if n > 1\", shape=diamond];
+    n4 [label=\"writeln(n)\", shape=box];
+    n1 -> n3;
+    n3 -> n2 [label=\"true\"];
+    n3 -> n4 [label=\"false\"];
+    n4 -> n2;
+  }
+  subgraph cluster_5 {
+    label=\"program laco\";
+    n6 [label=\"entry\", shape=oval];
+    n7 [label=\"exit\", shape=oval];
+    n8 [label=\"i := 0\", shape=box];
+    n9 [label=\"i := i + 1\", shape=box];
+    n10 [label=\"if i = 3\", shape=diamond];
+    n11 [label=\"mostra(i)\", shape=box];
+    n12 [label=\"until i >= 5\", shape=diamond];
+    n6 -> n8;
+    n8 -> n9;
+    n9 -> n10;
+    n10 -> n11 [label=\"false\"];
+    n11 -> n12;
+    n10 -> n12 [label=\"true\"];
+    n12 -> n9 [label=\"false\"];
+    n12 -> n7 [label=\"true\"];
+  }
+}
+";
+        assert_eq!(control_flow(parser.get_program().unwrap()), expected);
+    }
+}
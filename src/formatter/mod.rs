@@ -0,0 +1,474 @@
+mod test;
+
+use crate::common::token::Token;
+use crate::common::token::TokenType::*;
+
+const INDENT: &str = "  ";
+
+// Construções abertas durante a formatação, cada uma com o nível de indentação da linha onde começou
+#[derive(PartialEq, Debug, Copy, Clone)]
+enum Frame {
+    Routine(usize), // Procedimento com corpo, suas seções e seu begin ficam no mesmo nível
+    Section(usize), // Seção const, type ou var
+    Record(usize),  // record ... end
+    Block(usize),   // begin ... end
+    Repeat(usize),  // repeat ... until
+    Case(usize),    // case ... of
+    CaseElse(usize), // Comandos do else de um case
+    If(usize),      // if ... then, aguardando um possível else
+    Body(usize),    // Comando único depois de then, else, do ou de um rótulo do case
+}
+
+// Reimprime um programa a partir dos seus tokens, que devem incluir os comentários.
+// Palavras reservadas ficam em minúsculo, blocos begin/end são indentados, cada declaração e cada
+// comando ocupam uma linha e os operadores são separados por espaços
+pub fn format(tokens: &[Token]) -> String {
+    let mut formatter = Formatter {
+        tokens,
+        lines: vec![],
+        line: String::new(),
+        frames: vec![],
+        pending: false,
+        depth: 0,
+        header: None,
+        interface: false,
+        case_pending: false,
+        previous: None,
+        comments: vec![],
+        unary: false,
+        prefix_caret: false,
+    };
+    for position in 0..tokens.len() {
+        formatter.token(position);
+    }
+    formatter.finish()
+}
+
+struct Formatter<'t> {
+    tokens: &'t [Token],
+    lines: Vec<String>,
+    line: String,
+    frames: Vec<Frame>,
+    pending: bool,               // Quebra de linha antes do próximo token
+    depth: usize,                // Parênteses e colchetes abertos
+    header: Option<usize>,       // Nível do cabeçalho de procedimento sendo escrito
+    interface: bool,             // Na seção interface de uma unit os procedimentos não têm corpo
+    case_pending: bool,          // O próximo 'of' pertence a um case
+    previous: Option<&'t Token>, // Último token escrito
+    comments: Vec<&'t Token>,    // Comentários em linha própria aguardando o próximo token
+    unary: bool,                 // O último token foi um sinal unário
+    prefix_caret: bool,          // O último token foi o '^' de um tipo ponteiro
+}
+
+impl<'t> Formatter<'t> {
+    fn token(&mut self, position: usize) {
+        let token = &self.tokens[position];
+        let next = self
+            .tokens
+            .get(position + 1..)
+            .and_then(|rest| rest.iter().find(|token| !token.is_type_of(Comment)));
+        let next_is = |lexeme: &str| next.is_some_and(|next| next.is_lexeme(lexeme));
+
+        if token.is_type_of(Comment) {
+            return self.comment(token);
+        }
+
+        let lexeme = token.get_lexeme();
+        let keyword = token.is_type_of(Keyword) || token.is_type_of(Boolean) || is_word(token);
+        let text = if keyword {
+            lexeme.to_ascii_lowercase()
+        } else {
+            lexeme.to_string()
+        };
+
+        if token.is_type_of(Keyword) && self.depth == 0 {
+            match text.as_str() {
+                "interface" | "implementation" => {
+                    self.interface = text == "interface";
+                    self.pop_sections();
+                    self.write_line(token, &text);
+                    return;
+                }
+                "const" | "type" | "var" if !self.in_record() => {
+                    self.pop_sections();
+                    let indent = self.indentation();
+                    self.write_line(token, &text);
+                    self.frames.push(Frame::Section(indent));
+                    return;
+                }
+                "procedure" => {
+                    self.pop_sections();
+                    let level = match self.frames.last() {
+                        Some(Frame::Routine(level)) => level + 1,
+                        _ => 0,
+                    };
+                    self.pending = true;
+                    self.write_at(token, &text, level);
+                    self.header = Some(level);
+                    return;
+                }
+                "begin" => {
+                    self.pop_sections();
+                    let indent = match self.frames.last() {
+                        Some(Frame::Body(indent)) if self.pending => *indent,
+                        _ if self.pending || self.in_declarations() => self.indentation(),
+                        _ => self.line_indent(),
+                    };
+                    if self.in_declarations() {
+                        self.pending = true;
+                    }
+                    self.write_at(token, &text, indent);
+                    self.frames.push(Frame::Block(indent));
+                    self.pending = true;
+                    return;
+                }
+                "end" => {
+                    self.pop_statements();
+                    self.pop_sections();
+                    let indent = match self.frames.last().copied() {
+                        Some(
+                            Frame::Block(indent)
+                            | Frame::Case(indent)
+                            | Frame::CaseElse(indent)
+                            | Frame::Record(indent),
+                        ) => {
+                            self.frames.pop();
+                            indent
+                        }
+                        _ => self.indentation(),
+                    };
+                    self.pending = true;
+                    self.write_at(token, &text, indent);
+                    if let Some(Frame::Routine(_)) = self.frames.last() {
+                        self.frames.pop();
+                    }
+                    return;
+                }
+                "record" => {
+                    let indent = self.line_indent();
+                    self.write(token, &text);
+                    self.frames.push(Frame::Record(indent));
+                    self.pending = true;
+                    return;
+                }
+                "if" => {
+                    self.write(token, &text);
+                    let indent = self.line_indent();
+                    self.frames.push(Frame::If(indent));
+                    return;
+                }
+                "then" => {
+                    self.write(token, &text);
+                    let owner = match self.frames.last() {
+                        Some(Frame::If(indent)) => *indent,
+                        _ => self.line_indent(),
+                    };
+                    self.open_body(owner, next_is("begin"));
+                    return;
+                }
+                "do" => {
+                    self.write(token, &text);
+                    let owner = self.line_indent();
+                    self.open_body(owner, next_is("begin"));
+                    return;
+                }
+                "else" => {
+                    self.pop_bodies();
+                    match self.frames.last().copied() {
+                        Some(Frame::If(indent)) => {
+                            self.frames.pop();
+                            self.pending = true;
+                            self.write_at(token, &text, indent);
+                            if next_is("if") {
+                                self.frames.push(Frame::Body(indent));
+                            } else {
+                                self.open_body(indent, next_is("begin"));
+                            }
+                        }
+                        Some(Frame::Case(indent)) => {
+                            self.frames.pop();
+                            self.pending = true;
+                            self.write_at(token, &text, indent + 1);
+                            self.frames.push(Frame::CaseElse(indent));
+                            self.pending = true;
+                        }
+                        _ => self.write(token, &text),
+                    }
+                    return;
+                }
+                "repeat" => {
+                    self.write(token, &text);
+                    let indent = self.line_indent();
+                    self.frames.push(Frame::Repeat(indent));
+                    self.pending = true;
+                    return;
+                }
+                "until" => {
+                    self.pop_statements();
+                    let indent = match self.frames.last().copied() {
+                        Some(Frame::Repeat(indent)) => {
+                            self.frames.pop();
+                            indent
+                        }
+                        _ => self.indentation(),
+                    };
+                    self.pending = true;
+                    self.write_at(token, &text, indent);
+                    return;
+                }
+                "case" => {
+                    self.write(token, &text);
+                    self.case_pending = true;
+                    return;
+                }
+                "of" if self.case_pending => {
+                    self.case_pending = false;
+                    self.write(token, &text);
+                    let indent = self.line_indent();
+                    self.frames.push(Frame::Case(indent));
+                    self.pending = true;
+                    return;
+                }
+                _ => {}
+            }
+        }
+
+        match lexeme {
+            "(" | "[" => self.depth += 1,
+            ")" | "]" => self.depth = self.depth.saturating_sub(1),
+            _ => {}
+        }
+        if self.depth > 0 {
+            return self.write(token, &text);
+        }
+
+        match lexeme {
+            ";" => {
+                self.write(token, &text);
+                if let Some(level) = self.header.take() {
+                    // Procedimentos declarados forward ou na interface de uma unit não têm corpo
+                    if next_is("forward") {
+                        self.header = Some(level);
+                        return;
+                    }
+                    if !self.interface {
+                        self.frames.push(Frame::Routine(level));
+                    }
+                }
+                self.pop_statements();
+                self.pending = true;
+            }
+            "forward" => {
+                self.write(token, &text);
+                self.header = None;
+            }
+            ":" if matches!(self.frames.last(), Some(Frame::Case(_))) => {
+                let indent = self.line_indent();
+                self.write(token, &text);
+                self.frames.push(Frame::Body(indent + 1));
+            }
+            "." if self
+                .previous
+                .is_some_and(|previous| previous.is_lexeme("end")) =>
+            {
+                self.write(token, &text);
+                self.pending = true;
+            }
+            _ => self.write(token, &text),
+        }
+    }
+
+    // Comentários no fim de uma linha do fonte continuam nela, os demais ocupam uma linha própria com
+    // a indentação do token seguinte
+    fn comment(&mut self, token: &'t Token) {
+        let trailing = self
+            .previous
+            .is_some_and(|previous| previous.get_line() == token.get_line());
+        if trailing {
+            self.line.push(' ');
+            self.line.push_str(token.get_lexeme());
+            self.previous = Some(token);
+            return;
+        }
+        self.comments.push(token);
+        self.pending = true;
+    }
+
+    fn open_body(&mut self, owner: usize, block: bool) {
+        let indent = if block { owner } else { owner + 1 };
+        self.frames.push(Frame::Body(indent));
+        self.pending = true;
+    }
+
+    fn pop_sections(&mut self) {
+        while let Some(Frame::Section(_)) = self.frames.last() {
+            self.frames.pop();
+        }
+    }
+
+    fn pop_bodies(&mut self) {
+        while let Some(Frame::Body(_)) = self.frames.last() {
+            self.frames.pop();
+        }
+    }
+
+    // Um ';' ou um 'end' encerra os comandos únicos e os if abertos
+    fn pop_statements(&mut self) {
+        while let Some(Frame::Body(_) | Frame::If(_)) = self.frames.last() {
+            self.frames.pop();
+        }
+    }
+
+    fn in_record(&self) -> bool {
+        self.frames
+            .iter()
+            .any(|frame| matches!(frame, Frame::Record(_)))
+    }
+
+    fn in_declarations(&self) -> bool {
+        matches!(self.frames.last(), None | Some(Frame::Routine(_)))
+    }
+
+    // Indentação de uma nova linha dentro da construção aberta mais interna
+    fn indentation(&self) -> usize {
+        match self.frames.last() {
+            None => 0,
+            Some(Frame::Routine(indent) | Frame::Body(indent) | Frame::If(indent)) => *indent,
+            Some(
+                Frame::Section(indent)
+                | Frame::Record(indent)
+                | Frame::Block(indent)
+                | Frame::Repeat(indent)
+                | Frame::Case(indent),
+            ) => indent + 1,
+            Some(Frame::CaseElse(indent)) => indent + 2,
+        }
+    }
+
+    fn line_indent(&self) -> usize {
+        let spaces = self.line.len() - self.line.trim_start().len();
+        spaces / INDENT.len()
+    }
+
+    // Escreve o token em uma linha própria
+    fn write_line(&mut self, token: &'t Token, text: &str) {
+        self.pending = true;
+        let indent = self.indentation();
+        self.write_at(token, text, indent);
+        self.pending = true;
+    }
+
+    fn write(&mut self, token: &'t Token, text: &str) {
+        let indent = self.indentation();
+        self.write_at(token, text, indent);
+    }
+
+    // Escreve o token, começando uma linha com a indentação dada quando há uma quebra pendente
+    fn write_at(&mut self, token: &'t Token, text: &str, indent: usize) {
+        if self.pending || self.line.is_empty() {
+            for comment in std::mem::take(&mut self.comments) {
+                self.break_line(comment);
+                self.line = INDENT.repeat(indent) + comment.get_lexeme();
+                self.previous = Some(comment);
+            }
+            self.break_line(token);
+            self.line = INDENT.repeat(indent);
+        } else if self.needs_space(token) {
+            self.line.push(' ');
+        }
+        self.line.push_str(text);
+
+        self.unary = (token.is_lexeme("-") || token.is_lexeme("+")) && self.is_unary_position();
+        self.prefix_caret = token.is_lexeme("^") && self.is_unary_position();
+        self.previous = Some(token);
+    }
+
+    // Linhas em branco do fonte entre declarações e comandos são mantidas, sem repetição
+    fn break_line(&mut self, token: &Token) {
+        self.pending = false;
+        if !self.line.trim().is_empty() {
+            self.lines.push(std::mem::take(&mut self.line));
+        }
+        let blank = self
+            .previous
+            .is_some_and(|previous| token.get_line() > previous.get_line() + 1);
+        if blank && self.lines.last().is_some_and(|line| !line.is_empty()) {
+            self.lines.push(String::new());
+        }
+    }
+
+    // Um sinal ou '^' é prefixo quando não vem depois de um operando
+    fn is_unary_position(&self) -> bool {
+        match self.previous {
+            None => true,
+            Some(previous) => {
+                let operand = previous.is_type_of(Identifier)
+                    || previous.is_type_of(Integer)
+                    || previous.is_type_of(Real)
+                    || previous.is_type_of(Boolean)
+                    || previous.is_type_of(Char)
+                    || previous.is_type_of(Text)
+                    || previous.is_lexeme(")")
+                    || previous.is_lexeme("]")
+                    || previous.is_lexeme("^")
+                    || (previous.is_type_of(Keyword)
+                        && (previous.is_lexeme("nil") || previous.is_lexeme("end")));
+                !operand
+            }
+        }
+    }
+
+    fn needs_space(&self, token: &Token) -> bool {
+        let Some(previous) = self.previous else {
+            return false;
+        };
+        if self.unary || self.prefix_caret {
+            return false;
+        }
+        if previous.is_type_of(Comment) {
+            return true;
+        }
+        let lexeme = token.get_lexeme();
+        if matches!(lexeme, ";" | "," | ")" | "]" | "." | ".." | ":") {
+            return false;
+        }
+        if matches!(previous.get_lexeme(), "(" | "[" | ".." | ".") {
+            return false;
+        }
+        let operand = previous.is_type_of(Identifier)
+            || previous.is_lexeme(")")
+            || previous.is_lexeme("]")
+            || previous.is_lexeme("^");
+        match lexeme {
+            "^" | "(" => !operand,
+            "[" => !operand && !previous.is_lexeme("array"),
+            _ => true,
+        }
+    }
+
+    fn finish(mut self) -> String {
+        for comment in std::mem::take(&mut self.comments) {
+            self.break_line(comment);
+            self.line = comment.get_lexeme().to_string();
+        }
+        if !self.line.trim().is_empty() {
+            self.lines.push(std::mem::take(&mut self.line));
+        }
+        let mut output = self
+            .lines
+            .iter()
+            .map(|line| line.trim_end())
+            .collect::<Vec<_>>()
+            .join("\n");
+        output.push('\n');
+        output
+    }
+}
+
+// Operadores escritos como palavras, como and, or e in
+fn is_word(token: &Token) -> bool {
+    (token.is_type_of(AdditiveOperators)
+        || token.is_type_of(MultiplicativeOperators)
+        || token.is_type_of(RelationalOperators))
+        && token.get_lexeme().chars().all(char::is_alphabetic)
+}
@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod formatter_tests {
+    use crate::common::token::Token;
+    use crate::common::token::TokenType::Comment;
+    use crate::formatter::format;
+    use crate::lexical::Scanner;
+    use std::fs;
+
+    fn tokens(input: &str) -> Vec<Token> {
+        let mut scanner = Scanner::new(input);
+        scanner.set_trivia(true);
+        scanner.init().unwrap()
+    }
+
+    fn reformat(input: &str) -> String {
+        format(&tokens(input))
+    }
+
+    #[test]
+    fn test_format() {
+        let input = "PROGRAM Teste; {cabeçalho}
+CONST N=10;
+TYPE ponto=RECORD x,y:REAL END;
+VAR a,b:INTEGER; p:^ponto;
+
+{ soma dois números }
+PROCEDURE soma(x:integer;y:integer); forward;
+procedure soma; BEGIN a:=x+y END;
+begin a:=1;b:=-a*(2+3);if a>b then b:=a else if a=b then begin a:=0;b:=0 end else a:=b;
+while a<10 do a:=a+1; {laço}
+repeat a:=a-1 until (a=0) OR NOT (b<5);
+case a of 1,2:b:=0; 3:b:=1 else b:=2 end;
+new(p); p^.x:=1.5; soma(a,b)
+END.";
+        let expected = "program Teste; {cabeçalho}
+const
+  N = 10;
+type
+  ponto = record
+    x, y: real
+  end;
+var
+  a, b: integer;
+  p: ^ponto;
+
+{ soma dois números }
+procedure soma(x: integer; y: integer); forward;
+procedure soma;
+begin
+  a := x + y
+end;
+begin
+  a := 1;
+  b := -a * (2 + 3);
+  if a > b then
+    b := a
+  else if a = b then
+  begin
+    a := 0;
+    b := 0
+  end
+  else
+    a := b;
+  while a < 10 do
+    a := a + 1; {laço}
+  repeat
+    a := a - 1
+  until (a = 0) or not (b < 5);
+  case a of
+    1, 2: b := 0;
+    3: b := 1
+    else
+      b := 2
+  end;
+  new(p);
+  p^.x := 1.5;
+  soma(a, b)
+end.
+";
+        assert_eq!(reformat(input), expected);
+        assert_eq!(reformat(expected), expected);
+    }
+
+    #[test]
+    fn test_format_unit() {
+        let input = "unit u; interface procedure p(x: integer); procedure q;
+implementation procedure p; begin end; procedure q; begin p(1) end; end.";
+        let expected = "unit u;
+interface
+procedure p(x: integer);
+procedure q;
+implementation
+procedure p;
+begin
+end;
+procedure q;
+begin
+  p(1)
+end;
+end.
+";
+        assert_eq!(reformat(input), expected);
+    }
+
+    // O programa formatado tem os mesmos tokens e comentários, a menos da caixa das palavras reservadas
+    #[test]
+    fn test_format_fixtures() {
+        for path in [
+            "tests/Test2.pas",
+            "tests/Test5.pas",
+            "tests/units/geometria.pas",
+            "tests/units/lib/formas.pas",
+        ] {
+            let input = fs::read_to_string(path).unwrap();
+            let output = reformat(&input);
+            assert_eq!(reformat(&output), output, "{path}");
+
+            let lexemes = |tokens: Vec<Token>| -> Vec<(String, bool)> {
+                tokens
+                    .iter()
+                    .map(|token| {
+                        let lexeme = token.get_lexeme().to_ascii_lowercase();
+                        (lexeme, token.is_type_of(Comment))
+                    })
+                    .collect()
+            };
+            assert_eq!(lexemes(tokens(&input)), lexemes(tokens(&output)), "{path}");
+        }
+    }
+}
@@ -0,0 +1,397 @@
+mod test;
+
+// Gerador de programas aleatórios e válidos, guiado pela gramática, usado nos testes de
+// propriedade. Os programas sempre terminam: os laços são for com limites constantes, repeat que
+// executa uma vez e procedimentos que só chamam os declarados antes deles
+
+// xorshift64*, suficiente para gerar casos reproduzíveis a partir de uma semente
+pub struct Random(u64);
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        // Espalha os bits da semente, que não pode ser zero
+        Self(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    // Número em 0..limit
+    pub fn below(&mut self, limit: usize) -> usize {
+        (self.next() % limit as u64) as usize
+    }
+
+    // Verdadeiro com probabilidade 1 / odds
+    pub fn one_in(&mut self, odds: usize) -> bool {
+        self.below(odds) == 0
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Type {
+    Integer,
+    Real,
+    Boolean,
+    Char,
+}
+
+impl Type {
+    const ALL: [Type; 4] = [Type::Integer, Type::Real, Type::Boolean, Type::Char];
+
+    fn name(self) -> &'static str {
+        match self {
+            Type::Integer => "integer",
+            Type::Real => "real",
+            Type::Boolean => "boolean",
+            Type::Char => "char",
+        }
+    }
+}
+
+// Variáveis de controle dos for, k0, k1 e k2, uma por nível de aninhamento
+const COUNTERS: usize = 3;
+
+struct Generator {
+    random: Random,
+    text: String,
+    indent: usize,
+    variables: Vec<(String, Type)>, // Variáveis visíveis que podem ser lidas e atribuídas
+    procedures: Vec<(String, Vec<Type>)>,
+    depth: usize,
+}
+
+// Programa completo gerado a partir da semente
+pub fn program(seed: u64) -> String {
+    let mut generator = Generator::new(seed);
+    generator.program(seed);
+    generator.text
+}
+
+// Programa sem aninhamento, com listas longas de declarações e de comandos. As variáveis d0, d1...
+// só aumentam a lista de declarações, os comandos usam as declaradas depois delas
+pub fn flat_program(seed: u64, declarations: usize, statements: usize) -> String {
+    let mut generator = Generator::new(seed);
+    generator.line(&format!("program plano{seed};"));
+    generator.line("var");
+    for i in 0..declarations {
+        generator.line(&format!("    d{i}: integer;"));
+    }
+    generator.variables();
+    generator.line("begin");
+    generator.indent += 1;
+    // Na profundidade máxima só são gerados atribuições e writeln
+    generator.depth = COUNTERS;
+    for i in 0..statements {
+        generator.statement();
+        if i + 1 < statements {
+            generator.text.pop();
+            generator.text.push_str(";\n");
+        }
+    }
+    generator.indent -= 1;
+    generator.line("end.");
+    generator.text
+}
+
+impl Generator {
+    fn new(seed: u64) -> Self {
+        Self {
+            random: Random::new(seed),
+            text: String::new(),
+            indent: 0,
+            variables: vec![],
+            procedures: vec![],
+            depth: 0,
+        }
+    }
+
+    fn line(&mut self, text: &str) {
+        self.text.push_str(&"    ".repeat(self.indent));
+        self.text.push_str(text);
+        self.text.push('\n');
+    }
+
+    fn program(&mut self, seed: u64) {
+        self.line(&format!("program gerado{seed};"));
+        if self.random.one_in(2) {
+            self.line("const");
+            let value = self.random.below(10);
+            self.line(&format!("    limite = {value};"));
+        }
+        self.line("var");
+        self.line("    k0, k1, k2: integer;");
+        self.variables();
+        for i in 0..self.random.below(3) {
+            self.procedure(i);
+        }
+        self.compound();
+        self.text.pop();
+        self.text.push_str(".\n");
+    }
+
+    // Uma a três variáveis de cada tipo, como i0, r0, b0 e c0
+    fn variables(&mut self) {
+        for (i, kind) in Type::ALL.iter().enumerate() {
+            let count = 1 + self.random.below(3);
+            let names: Vec<String> = (0..count)
+                .map(|j| format!("{}{j}", ["i", "r", "b", "c"][i]))
+                .collect();
+            self.line(&format!("    {}: {};", names.join(", "), kind.name()));
+            for name in names {
+                self.variables.push((name, *kind));
+            }
+        }
+    }
+
+    fn procedure(&mut self, index: usize) {
+        let parameters: Vec<Type> = (0..self.random.below(3))
+            .map(|_| *self.random.pick(&Type::ALL))
+            .collect();
+        let name = format!("p{index}");
+        let declared: Vec<String> = parameters
+            .iter()
+            .enumerate()
+            .map(|(i, kind)| format!("a{i}: {}", kind.name()))
+            .collect();
+        if declared.is_empty() {
+            self.line(&format!("procedure {name};"));
+        } else {
+            self.line(&format!("procedure {name}({});", declared.join("; ")));
+        }
+
+        let outer = self.variables.len();
+        for (i, kind) in parameters.iter().enumerate() {
+            self.variables.push((format!("a{i}"), *kind));
+        }
+        // Variáveis de controle locais, para que os for do procedimento não alterem os de quem chama
+        self.line("var");
+        self.line("    k0, k1, k2: integer;");
+        if self.random.one_in(2) {
+            self.line("    local: integer;");
+            self.variables.push(("local".to_string(), Type::Integer));
+        }
+        self.compound();
+        self.text.pop();
+        self.text.push_str(";\n");
+        self.variables.truncate(outer);
+        self.procedures.push((name, parameters));
+    }
+
+    fn compound(&mut self) {
+        self.line("begin");
+        self.indent += 1;
+        let count = 1 + self.random.below(4);
+        for i in 0..count {
+            self.statement();
+            if i + 1 < count {
+                self.text.pop();
+                self.text.push_str(";\n");
+            }
+        }
+        self.indent -= 1;
+        self.line("end");
+    }
+
+    fn statement(&mut self) {
+        self.depth += 1;
+        // Nos níveis mais profundos apenas comandos simples, para limitar o tamanho do programa
+        let choice = if self.depth > 3 {
+            self.random.below(3)
+        } else {
+            self.random.below(8)
+        };
+        match choice {
+            0 | 1 => self.assignment(),
+            2 => self.call(),
+            3 => {
+                let condition = self.expression(Type::Boolean, 0);
+                self.line(&format!("if {condition} then"));
+                self.nested();
+                if self.random.one_in(2) {
+                    self.line("else");
+                    self.nested();
+                }
+                self.text.pop();
+            }
+            4 if self.depth <= COUNTERS => {
+                let counter = format!("k{}", self.depth - 1);
+                let (start, end) = (self.random.below(3), self.random.below(5));
+                let direction = if self.random.one_in(2) {
+                    "to"
+                } else {
+                    "downto"
+                };
+                self.line(&format!("for {counter} := {start} {direction} {end} do"));
+                self.variables.push((counter, Type::Integer));
+                self.nested();
+                self.variables.pop();
+                self.text.pop();
+            }
+            5 => {
+                self.line("repeat");
+                self.nested();
+                self.line("until true");
+                self.text.pop();
+            }
+            6 => self.case(),
+            _ => self.compound_statement(),
+        }
+        self.text.push('\n');
+        self.depth -= 1;
+    }
+
+    // Comando dentro de um if ou laço, indentado
+    fn nested(&mut self) {
+        self.indent += 1;
+        self.statement();
+        self.indent -= 1;
+    }
+
+    fn compound_statement(&mut self) {
+        self.compound();
+        self.text.pop();
+    }
+
+    fn assignment(&mut self) {
+        // As variáveis de controle dos for não podem ser atribuídas
+        let assignable: Vec<(String, Type)> = self
+            .variables
+            .iter()
+            .filter(|(name, _)| !name.starts_with('k'))
+            .cloned()
+            .collect();
+        let (name, kind) = self.random.pick(&assignable).clone();
+        let value = self.expression(kind, 0);
+        self.line(&format!("{name} := {value}"));
+        self.text.pop();
+    }
+
+    fn call(&mut self) {
+        if self.procedures.is_empty() || self.random.one_in(2) {
+            let count = 1 + self.random.below(3);
+            let arguments: Vec<String> = (0..count)
+                .map(|_| {
+                    let kind = *self.random.pick(&Type::ALL);
+                    self.expression(kind, 1)
+                })
+                .collect();
+            self.line(&format!("writeln({})", arguments.join(", ")));
+        } else {
+            let (name, parameters) = self.random.pick(&self.procedures).clone();
+            let arguments: Vec<String> = parameters
+                .iter()
+                .map(|kind| self.expression(*kind, 1))
+                .collect();
+            if arguments.is_empty() {
+                self.line(&name);
+            } else {
+                self.line(&format!("{name}({})", arguments.join(", ")));
+            }
+        }
+        self.text.pop();
+    }
+
+    fn case(&mut self) {
+        let selector = self.expression(Type::Integer, 1);
+        self.line(&format!("case {selector} of"));
+        self.indent += 1;
+        let count = 1 + self.random.below(3);
+        for label in 0..count {
+            // Rótulos pares, alguns como o intervalo até o ímpar seguinte
+            let first = 2 * label;
+            if self.random.one_in(2) {
+                self.line(&format!("{first}..{}:", first + 1));
+            } else {
+                self.line(&format!("{first}:"));
+            }
+            self.nested();
+            if label + 1 < count {
+                self.text.pop();
+                self.text.push_str(";\n");
+            }
+        }
+        self.indent -= 1;
+        if self.random.one_in(2) {
+            self.line("else");
+            self.nested();
+        }
+        self.line("end");
+        self.text.pop();
+    }
+
+    fn variable(&mut self, kind: Type) -> Option<String> {
+        let names: Vec<&String> = self
+            .variables
+            .iter()
+            .filter(|(_, variable)| *variable == kind)
+            .map(|(name, _)| name)
+            .collect();
+        (!names.is_empty()).then(|| self.random.pick(&names).to_string())
+    }
+
+    // Expressão do tipo pedido, cada vez mais simples com a profundidade
+    fn expression(&mut self, kind: Type, depth: usize) -> String {
+        let leaf = depth >= 3 || self.random.one_in(depth + 2);
+        if leaf {
+            if self.random.one_in(2) {
+                if let Some(name) = self.variable(kind) {
+                    return name;
+                }
+            }
+            return self.literal(kind);
+        }
+        let operand = |generator: &mut Self, kind| generator.expression(kind, depth + 1);
+        match kind {
+            Type::Integer => {
+                let operator = self.random.pick(&["+", "-", "*"]);
+                let (left, right) = (operand(self, kind), operand(self, kind));
+                format!("({left} {operator} {right})")
+            }
+            Type::Real => {
+                let operator = self.random.pick(&["+", "-", "*", "/"]);
+                let left_kind = *self.random.pick(&[Type::Integer, Type::Real]);
+                let left = operand(self, left_kind);
+                // Um divisor constante pode ser zero, o que a verificação rejeita
+                let right = match *operator {
+                    "/" => self
+                        .variable(Type::Real)
+                        .unwrap_or_else(|| "1.5".to_string()),
+                    _ => operand(self, Type::Real),
+                };
+                format!("({left} {operator} {right})")
+            }
+            Type::Boolean => match self.random.below(3) {
+                0 => format!("not {}", operand(self, kind)),
+                1 => {
+                    let operator = self.random.pick(&["and", "or"]);
+                    let (left, right) = (operand(self, kind), operand(self, kind));
+                    format!("({left} {operator} {right})")
+                }
+                _ => {
+                    let operator = self.random.pick(&["=", "<>", "<", ">", "<=", ">="]);
+                    let compared = *self.random.pick(&[Type::Integer, Type::Char]);
+                    let (left, right) = (operand(self, compared), operand(self, compared));
+                    format!("({left} {operator} {right})")
+                }
+            },
+            Type::Char => self.literal(kind),
+        }
+    }
+
+    fn literal(&mut self, kind: Type) -> String {
+        match kind {
+            Type::Integer => self.random.below(20).to_string(),
+            Type::Real => format!("{}.{}", self.random.below(10), self.random.below(100)),
+            Type::Boolean => self.random.pick(&["true", "false"]).to_string(),
+            Type::Char => format!("'{}'", (b'a' + self.random.below(26) as u8) as char),
+        }
+    }
+}
@@ -0,0 +1,183 @@
+#[cfg(test)]
+mod generator_tests {
+    use crate::dump::{self, Format};
+    use crate::formatter::format;
+    use crate::generator::{flat_program, program, Random};
+    use crate::interpreter::Interpreter;
+    use crate::lexical::Scanner;
+    use crate::syntactic::Parser;
+    use std::io;
+    use std::panic;
+
+    const CASES: u64 = 200;
+
+    // Palavras usadas para trocar tokens dos programas gerados
+    const VOCABULARY: &str =
+        "begin end if then else for do case of var procedure repeat until ; : := ( ) , . .. + i0 '";
+
+    fn lexemes(source: &str, trivia: bool) -> Vec<String> {
+        let mut scanner = Scanner::new(source);
+        scanner.set_trivia(trivia);
+        let tokens = scanner.init().unwrap();
+        tokens.iter().map(|t| t.get_lexeme().to_string()).collect()
+    }
+
+    // Árvore sintática do programa verificado, sem posições, e a quantidade de avisos
+    fn checked(source: &str) -> Result<(String, usize), String> {
+        let tokens = Scanner::new(source).init().map_err(|e| e.to_string())?;
+        let mut parser = Parser::new(&tokens);
+        parser.check().map_err(|e| e.to_string())?;
+        let tree = dump::program(parser.get_program().unwrap(), Format::Tree);
+        Ok((tree, parser.get_warnings().len()))
+    }
+
+    // O analisador não pode entrar em pânico, qualquer que seja a entrada
+    fn analyze(source: &str) {
+        let result = panic::catch_unwind(|| {
+            if let Ok(tokens) = Scanner::new(source).init() {
+                let _ = Parser::new(&tokens).check();
+            }
+        });
+        assert!(result.is_ok(), "The analysis panicked on:\n{source}");
+    }
+
+    #[test]
+    fn test_generated_programs_are_valid() {
+        for seed in 0..CASES {
+            let source = program(seed);
+            let result = checked(&source);
+            assert!(result.is_ok(), "{}\n{source}", result.unwrap_err());
+        }
+    }
+
+    // As listas de comandos e declarações são analisadas em laços, o tamanho delas não esgota a
+    // pilha da thread do teste
+    #[test]
+    fn test_long_programs() {
+        let source = flat_program(0, 1_000, 10_000);
+        let result = checked(&source);
+        assert!(result.is_ok(), "{}", result.unwrap_err());
+    }
+
+    #[test]
+    fn test_format_roundtrip() {
+        for seed in 0..CASES {
+            let source = program(seed);
+            let tokens = {
+                let mut scanner = Scanner::new(&source);
+                scanner.set_trivia(true);
+                scanner.init().unwrap()
+            };
+            let formatted = format(&tokens);
+            assert_eq!(
+                checked(&formatted),
+                checked(&source),
+                "The formatted program differs:\n{formatted}"
+            );
+            assert_eq!(lexemes(&formatted, true), lexemes(&source, true));
+            let tokens = {
+                let mut scanner = Scanner::new(&formatted);
+                scanner.set_trivia(true);
+                scanner.init().unwrap()
+            };
+            assert_eq!(format(&tokens), formatted, "Formatting is not idempotent");
+        }
+    }
+
+    #[test]
+    fn test_mutated_programs_do_not_panic() {
+        let vocabulary: Vec<&str> = VOCABULARY.split_whitespace().collect();
+        let mut random = Random::new(u64::MAX);
+        for seed in 0..CASES {
+            let mut tokens = lexemes(&program(seed), false);
+            for _ in 0..8 {
+                let index = random.below(tokens.len());
+                match random.below(4) {
+                    0 => {
+                        tokens.remove(index);
+                    }
+                    1 => tokens.insert(index, tokens[index].to_owned()),
+                    2 => {
+                        let next = (index + 1) % tokens.len();
+                        tokens.swap(index, next)
+                    }
+                    _ => tokens[index] = random.pick(&vocabulary).to_string(),
+                }
+                analyze(&tokens.join(" "));
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_bytes_do_not_panic() {
+        let mut random = Random::new(7);
+        for seed in 0..CASES {
+            let mut bytes = program(seed).into_bytes();
+            for _ in 0..4 {
+                let index = random.below(bytes.len());
+                bytes[index] = random.below(128) as u8;
+            }
+            analyze(&String::from_utf8_lossy(&bytes));
+        }
+    }
+
+    // Todo programa aceito pela verificação executa sem pânico; estouros de inteiro são erros
+    #[test]
+    fn test_checked_programs_run() {
+        for seed in 0..CASES {
+            let source = program(seed);
+            let tokens = Scanner::new(&source).init().unwrap();
+            let mut parser = Parser::new(&tokens);
+            parser.check().unwrap();
+            let program = parser.get_program().unwrap();
+            let result = panic::catch_unwind(|| {
+                let mut interpreter = Interpreter::default();
+                interpreter.set_output(Box::new(io::sink()));
+                interpreter.run(program)
+            });
+            assert!(result.is_ok(), "The interpreter panicked on:\n{source}");
+        }
+    }
+
+    // O interpretador confere os tipos de novo durante a execução: um programa aceito pela
+    // verificação, mesmo depois de uma mutação, só pode falhar por estouros e limites
+    #[test]
+    fn test_checker_agrees_with_interpreter() {
+        let vocabulary: Vec<&str> = VOCABULARY.split_whitespace().collect();
+        let mut random = Random::new(11);
+        for seed in 0..CASES {
+            let mut tokens = lexemes(&program(seed), false);
+            // Uma única mutação, que não consegue criar laços sem fim
+            let index = random.below(tokens.len());
+            match random.below(3) {
+                0 => {
+                    tokens.remove(index);
+                }
+                1 => tokens[index] = random.pick(&vocabulary).to_string(),
+                _ => {}
+            }
+            let source = tokens.join(" ");
+            let Ok(tokens) = Scanner::new(&source).init() else {
+                continue;
+            };
+            let mut parser = Parser::new(&tokens);
+            if parser.check().is_err() {
+                continue;
+            }
+            let program = parser.get_program().unwrap();
+            let result = panic::catch_unwind(|| {
+                let mut interpreter = Interpreter::default();
+                interpreter.set_output(Box::new(io::sink()));
+                interpreter.run(program)
+            });
+            match result {
+                Ok(Err(error)) => assert!(
+                    !error.starts_with("Invalid operation") && !error.starts_with("Unknown"),
+                    "The checker accepted a program that fails with '{error}':\n{source}"
+                ),
+                Ok(Ok(())) => {}
+                Err(_) => panic!("The interpreter panicked on:\n{source}"),
+            }
+        }
+    }
+}
@@ -0,0 +1,924 @@
+mod test;
+pub mod value;
+
+use crate::common::types::Type;
+use crate::common::utils::{MAX_INTEGER, MAX_SET_SIZE};
+use crate::syntactic::ast::{
+    Declaration, Expression, ExpressionKind, Procedure, Program, Statement,
+};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::io;
+use std::io::Write;
+use std::rc::Rc;
+use value::Bitset;
+use value::Value;
+
+// Quantidade máxima de ativações de procedimentos ao mesmo tempo, além dela a execução é interrompida
+const MAX_DEPTH: usize = 200;
+
+// Escopo em execução: o programa, uma unit ou a ativação de um procedimento
+#[derive(Default)]
+struct Frame {
+    variables: HashMap<String, Value>,
+    constants: HashMap<String, Value>,
+    types: HashMap<String, Type>,
+    procedures: HashMap<String, Rc<Procedure>>,
+    link: Option<usize>, // Escopo onde o procedimento foi declarado, onde os nomes livres são buscados
+}
+
+// Como um comando termina, break e continue param no laço e exit no procedimento
+#[derive(PartialEq)]
+enum Flow {
+    Normal,
+    Break,
+    Continue,
+    Exit,
+}
+
+// Posição de uma variável, de um elemento ou de um campo, usada nas atribuições
+struct Place {
+    root: Root,
+    path: Vec<Step>,
+}
+
+enum Root {
+    Variable(usize, String), // Escopo e nome
+    Heap(usize),
+}
+
+enum Step {
+    Element(usize),
+    Field(String),
+}
+
+// Executa a árvore sintática montada pelo analisador, que já foi verificada
+pub struct Interpreter {
+    frames: Vec<Frame>,
+    heap: Vec<Option<Value>>, // Memória dinâmica, as posições liberadas por dispose ficam vazias
+    enumerations: HashMap<String, Vec<String>>,
+    initialized: Vec<String>, // Units já inicializadas
+    output: Box<dyn Write>,   // Destino de write e writeln
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Self {
+            frames: vec![Frame::default()],
+            heap: vec![],
+            enumerations: HashMap::new(),
+            initialized: vec![],
+            output: Box::new(io::stdout()),
+        }
+    }
+}
+
+impl Interpreter {
+    pub fn set_output(&mut self, output: Box<dyn Write>) {
+        self.output = output;
+    }
+
+    fn current(&self) -> usize {
+        self.frames.len() - 1
+    }
+
+    // Executa o programa depois de inicializar as units que ele usa
+    pub fn run(&mut self, program: &Program) -> Result<(), String> {
+        for unit in &program.units {
+            self.initialize(unit)?;
+        }
+        self.enter();
+        self.declare(&program.declarations);
+        let result = self.execute(&program.body);
+        let _ = self.output.flush();
+        result
+    }
+
+    // Cada unit é inicializada uma única vez, depois das units que ela usa
+    fn initialize(&mut self, unit: &Program) -> Result<(), String> {
+        if self.initialized.contains(&unit.name) {
+            return Ok(());
+        }
+        self.initialized.push(unit.name.to_owned());
+        for used in &unit.units {
+            self.initialize(used)?;
+        }
+        self.enter();
+        self.declare(&unit.declarations);
+        self.execute(&unit.body)
+    }
+
+    // Abre um escopo dentro do atual, como o de uma unit ou do programa
+    fn enter(&mut self) {
+        let link = self.current();
+        self.frames.push(Frame {
+            link: Some(link),
+            ..Frame::default()
+        });
+    }
+
+    // Adiciona as declarações ao escopo atual, as variáveis começam com o valor padrão do tipo
+    pub fn declare(&mut self, declarations: &[Declaration]) {
+        let frame = self.current();
+        for declaration in declarations {
+            match declaration {
+                Declaration::Constant(name, value) => {
+                    let value = Value::from(value);
+                    self.frames[frame].constants.insert(name.to_owned(), value);
+                }
+                Declaration::Type(name, definition) => {
+                    let definition = definition.to_owned();
+                    self.frames[frame].types.insert(name.to_owned(), definition);
+                }
+                Declaration::Enumeration(name, constants) => {
+                    for (position, constant) in constants.iter().enumerate() {
+                        let value = Value::Enumerated(name.to_owned(), position as i64);
+                        self.frames[frame]
+                            .constants
+                            .insert(constant.to_owned(), value);
+                    }
+                    // Ponteiros para a enumeração a guardam pelo nome
+                    let definition = Type::Enumeration(name.to_owned(), constants.to_owned());
+                    self.frames[frame].types.insert(name.to_owned(), definition);
+                    self.enumerations
+                        .insert(name.to_owned(), constants.to_owned());
+                }
+                Declaration::Variable(name, variable_type) => {
+                    let value = self.initial(variable_type);
+                    self.frames[frame].variables.insert(name.to_owned(), value);
+                }
+                Declaration::Procedure(procedure) => {
+                    let name = procedure.name.to_owned();
+                    self.frames[frame]
+                        .procedures
+                        .insert(name, Rc::clone(procedure));
+                }
+            }
+        }
+    }
+
+    pub fn execute(&mut self, statements: &[Statement]) -> Result<(), String> {
+        self.block(statements).map(|_| ())
+    }
+
+    // Busca a partir do escopo atual, seguindo os escopos onde os procedimentos foram declarados
+    fn find<'a, T>(&'a self, get: impl Fn(&'a Frame) -> Option<T>) -> Option<(usize, T)> {
+        let mut index = Some(self.current());
+        while let Some(frame) = index {
+            if let Some(found) = get(&self.frames[frame]) {
+                return Some((frame, found));
+            }
+            index = self.frames[frame].link;
+        }
+        None
+    }
+
+    // Valor inicial de uma variável do tipo
+    fn initial(&self, value_type: &Type) -> Value {
+        match value_type {
+            Type::Integer => Value::Integer(0),
+            Type::Real => Value::Real(0.0),
+            Type::Boolean => Value::Boolean(false),
+            Type::Char => Value::Char('\0'),
+            Type::String => Value::Text(String::new()),
+            Type::Enumeration(name, _) => Value::Enumerated(name.to_owned(), 0),
+            Type::Subrange(host, low, _) => self
+                .initial(host)
+                .with_ordinal(*low)
+                .unwrap_or(Value::Integer(*low)),
+            Type::Array(index, element) => {
+                let (low, high) = self.range(index).unwrap_or((0, -1));
+                let length = usize::try_from(high - low + 1).unwrap_or_default();
+                Value::Array(low, vec![self.initial(element); length])
+            }
+            Type::Set(_) => Value::Set(Bitset::default()),
+            Type::Record(fields) => Value::Record(
+                fields
+                    .iter()
+                    .map(|(name, field_type)| (name.to_owned(), self.initial(field_type)))
+                    .collect(),
+            ),
+            Type::Pointer(_) => Value::Pointer(None),
+            Type::Named(name) => match self.find(|frame| frame.types.get(name)) {
+                Some((_, definition)) => self.initial(definition),
+                None => Value::Integer(0),
+            },
+            _ => Value::Integer(0),
+        }
+    }
+
+    // Menor e maior ordinal de um tipo usado como índice ou subintervalo
+    fn range(&self, value_type: &Type) -> Option<(i64, i64)> {
+        match value_type {
+            Type::Named(name) => {
+                let (_, definition) = self.find(|frame| frame.types.get(name))?;
+                definition.range()
+            }
+            _ => value_type.range(),
+        }
+    }
+
+    // Conversões implícitas da atribuição e a verificação dos limites de subintervalos e dos
+    // elementos de conjuntos
+    fn coerce(
+        &self,
+        value: Value,
+        value_type: &Type,
+        line: usize,
+        column: usize,
+    ) -> Result<Value, String> {
+        match (value_type, value) {
+            (Type::Real, Value::Integer(value)) => Ok(Value::Real(value as f64)),
+            (Type::String, Value::Char(value)) => Ok(Value::Text(value.to_string())),
+            (_, value) => {
+                if let (Type::Subrange(_, low, high), Some(ordinal)) = (value_type, value.ordinal())
+                {
+                    if ordinal < *low || ordinal > *high {
+                        return Err(format!(
+                            "Value {} is out of the range '{}' at line {} column {}.",
+                            self.show(&value, value_type),
+                            value_type,
+                            line,
+                            column
+                        ));
+                    }
+                }
+                if let (Type::Set(base), Value::Set(elements)) = (value_type, &value) {
+                    let (low, high) = self.range(base).unwrap_or((0, MAX_SET_SIZE - 1));
+                    if let Some(element) = elements.iter().find(|e| *e < low || *e > high) {
+                        let element = self.initial(base).with_ordinal(element);
+                        return Err(format!(
+                            "Set element {} is out of the range '{}' at line {} column {}.",
+                            element.map_or(String::new(), |element| self.show(&element, base)),
+                            base,
+                            line,
+                            column
+                        ));
+                    }
+                }
+                Ok(value)
+            }
+        }
+    }
+
+    fn place(&self, expression: &Expression) -> Result<Place, String> {
+        let (line, column) = (expression.line, expression.column);
+        match &expression.kind {
+            ExpressionKind::Variable(name) => {
+                match self.find(|frame| frame.variables.contains_key(name).then_some(())) {
+                    Some((frame, _)) => Ok(Place {
+                        root: Root::Variable(frame, name.to_owned()),
+                        path: vec![],
+                    }),
+                    None => Err(format!(
+                        "Unknown variable '{name}' at line {line} column {column}."
+                    )),
+                }
+            }
+            ExpressionKind::Index(array, index) => {
+                let mut place = self.place(array)?;
+                let position = self.evaluate(index)?;
+                let Value::Array(low, elements) = self.read(&place) else {
+                    return Err(Self::invalid(expression));
+                };
+                let offset = position.ordinal().unwrap_or_default() - low;
+                if offset < 0 || offset >= elements.len() as i64 {
+                    let index_type = match &array.value_type {
+                        Type::Array(index, _) => index,
+                        _ => &Type::Unknown,
+                    };
+                    return Err(format!(
+                        "Index {} is out of the range '{}' at line {} column {}.",
+                        self.show(&position, index_type),
+                        index_type,
+                        line,
+                        column
+                    ));
+                }
+                place.path.push(Step::Element(offset as usize));
+                Ok(place)
+            }
+            ExpressionKind::Field(record, name) => {
+                let mut place = self.place(record)?;
+                place.path.push(Step::Field(name.to_owned()));
+                Ok(place)
+            }
+            ExpressionKind::Dereference(pointer) => {
+                let place = self.place(pointer)?;
+                match self.read(&place) {
+                    Value::Pointer(Some(address)) if self.heap[*address].is_some() => Ok(Place {
+                        root: Root::Heap(*address),
+                        path: vec![],
+                    }),
+                    Value::Pointer(Some(_)) => Err(format!(
+                        "Dereference of a disposed pointer at line {line} column {column}."
+                    )),
+                    _ => Err(format!(
+                        "Dereference of a nil pointer at line {line} column {column}."
+                    )),
+                }
+            }
+            _ => Err(Self::invalid(expression)),
+        }
+    }
+
+    // As posições são montadas por 'place' a partir de uma árvore já verificada
+    fn read(&self, place: &Place) -> &Value {
+        let mut value = match &place.root {
+            Root::Variable(frame, name) => &self.frames[*frame].variables[name],
+            Root::Heap(address) => self.heap[*address].as_ref().unwrap(),
+        };
+        for step in &place.path {
+            value = match (step, value) {
+                (Step::Element(offset), Value::Array(_, elements)) => &elements[*offset],
+                (Step::Field(name), Value::Record(fields)) => {
+                    &fields.iter().find(|(field, _)| field == name).unwrap().1
+                }
+                _ => unreachable!("Invalid selector in a checked program."),
+            };
+        }
+        value
+    }
+
+    fn write(&mut self, place: &Place) -> &mut Value {
+        let mut value = match &place.root {
+            Root::Variable(frame, name) => self.frames[*frame].variables.get_mut(name).unwrap(),
+            Root::Heap(address) => self.heap[*address].as_mut().unwrap(),
+        };
+        for step in &place.path {
+            value = match (step, value) {
+                (Step::Element(offset), Value::Array(_, elements)) => &mut elements[*offset],
+                (Step::Field(name), Value::Record(fields)) => {
+                    &mut fields
+                        .iter_mut()
+                        .find(|(field, _)| field == name)
+                        .unwrap()
+                        .1
+                }
+                _ => unreachable!("Invalid selector in a checked program."),
+            };
+        }
+        value
+    }
+
+    fn invalid(expression: &Expression) -> String {
+        format!(
+            "Invalid operation at line {} column {}.",
+            expression.line, expression.column
+        )
+    }
+
+    fn integer(value: i64, expression: &Expression) -> Result<Value, String> {
+        if !(-MAX_INTEGER - 1..=MAX_INTEGER).contains(&value) {
+            return Err(format!(
+                "Integer overflow at line {} column {}.",
+                expression.line, expression.column
+            ));
+        }
+        Ok(Value::Integer(value))
+    }
+
+    pub fn evaluate(&self, expression: &Expression) -> Result<Value, String> {
+        match &expression.kind {
+            ExpressionKind::Literal(value) => Ok(Value::from(value)),
+            ExpressionKind::Nil => Ok(Value::Pointer(None)),
+            ExpressionKind::Variable(name) => {
+                let found = self.find(|frame| {
+                    frame
+                        .variables
+                        .get(name)
+                        .or_else(|| frame.constants.get(name))
+                });
+                match found {
+                    Some((_, value)) => Ok(value.to_owned()),
+                    None => Err(format!(
+                        "Unknown variable '{}' at line {} column {}.",
+                        name, expression.line, expression.column
+                    )),
+                }
+            }
+            ExpressionKind::Index(..)
+            | ExpressionKind::Field(..)
+            | ExpressionKind::Dereference(..) => {
+                let place = self.place(expression)?;
+                Ok(self.read(&place).to_owned())
+            }
+            ExpressionKind::Call(name, arguments) => {
+                let arguments = arguments
+                    .iter()
+                    .map(|argument| self.evaluate(argument))
+                    .collect::<Result<Vec<_>, _>>()?;
+                self.function(name, &arguments, expression)
+            }
+            ExpressionKind::Unary(operator, operand) => {
+                match (operator.as_str(), self.evaluate(operand)?) {
+                    ("-", Value::Integer(value)) => Self::integer(-value, expression),
+                    ("-", Value::Real(value)) => Ok(Value::Real(-value)),
+                    ("not", Value::Boolean(value)) => Ok(Value::Boolean(!value)),
+                    _ => Err(Self::invalid(expression)),
+                }
+            }
+            ExpressionKind::Binary(operator, left, right) => {
+                self.binary(operator, left, right, expression)
+            }
+            ExpressionKind::Conversion(operand) => {
+                let value = self.evaluate(operand)?;
+                self.coerce(
+                    value,
+                    &expression.value_type,
+                    expression.line,
+                    expression.column,
+                )
+            }
+            ExpressionKind::Set(elements) => {
+                let mut set = Bitset::default();
+                for (low, high) in elements {
+                    let low = self.evaluate(low)?.ordinal().unwrap_or_default();
+                    let high = match high {
+                        Some(high) => self.evaluate(high)?.ordinal().unwrap_or_default(),
+                        None => low,
+                    };
+                    if !(low..=high).all(|ordinal| set.insert(ordinal)) {
+                        return Err(format!(
+                            "Set element out of the range 0..{} at line {} column {}.",
+                            MAX_SET_SIZE - 1,
+                            expression.line,
+                            expression.column
+                        ));
+                    }
+                }
+                Ok(Value::Set(set))
+            }
+        }
+    }
+
+    fn binary(
+        &self,
+        operator: &str,
+        left: &Expression,
+        right: &Expression,
+        expression: &Expression,
+    ) -> Result<Value, String> {
+        // Os operadores lógicos avaliam o segundo operando apenas quando necessário
+        if operator == "and" || operator == "or" {
+            let left = self.evaluate(left)?.boolean();
+            if left == Some(operator == "or") {
+                return Ok(Value::Boolean(operator == "or"));
+            }
+            return match self.evaluate(right)?.boolean() {
+                Some(right) => Ok(Value::Boolean(right)),
+                None => Err(Self::invalid(expression)),
+            };
+        }
+
+        let (left, right) = (self.evaluate(left)?, self.evaluate(right)?);
+        match operator {
+            "+" | "-" | "*" | "/" => self.arithmetic(operator, left, right, expression),
+            _ => Self::compare(operator, &left, &right, expression).map(Value::Boolean),
+        }
+    }
+
+    fn arithmetic(
+        &self,
+        operator: &str,
+        left: Value,
+        right: Value,
+        expression: &Expression,
+    ) -> Result<Value, String> {
+        let zero = || {
+            format!(
+                "Division by zero at line {} column {}.",
+                expression.line, expression.column
+            )
+        };
+        match (left, right) {
+            (Value::Set(a), Value::Set(b)) => Ok(Value::Set(match operator {
+                "+" => a.union(&b),
+                "-" => a.difference(&b),
+                _ => a.intersection(&b),
+            })),
+            (Value::Integer(a), Value::Integer(b)) => match operator {
+                "+" => Self::integer(a + b, expression),
+                "-" => Self::integer(a - b, expression),
+                "*" => Self::integer(a * b, expression),
+                // A verificação de tipos considera integer o resultado da divisão entre inteiros
+                _ if b == 0 => Err(zero()),
+                _ => Self::integer(a / b, expression),
+            },
+            (a, b) if operator == "+" && a.text().is_some() && b.text().is_some() => {
+                Ok(Value::Text(a.text().unwrap() + &b.text().unwrap()))
+            }
+            (a, b) => {
+                let (Some(a), Some(b)) = (a.real(), b.real()) else {
+                    return Err(Self::invalid(expression));
+                };
+                match operator {
+                    "+" => Ok(Value::Real(a + b)),
+                    "-" => Ok(Value::Real(a - b)),
+                    "*" => Ok(Value::Real(a * b)),
+                    _ if b == 0.0 => Err(zero()),
+                    _ => Ok(Value::Real(a / b)),
+                }
+            }
+        }
+    }
+
+    fn compare(
+        operator: &str,
+        left: &Value,
+        right: &Value,
+        expression: &Expression,
+    ) -> Result<bool, String> {
+        if operator == "in" {
+            let Value::Set(set) = right else {
+                return Err(Self::invalid(expression));
+            };
+            return Ok(left.ordinal().is_some_and(|value| set.contains(value)));
+        }
+        if let (Value::Set(a), Value::Set(b)) = (left, right) {
+            // Igualdade, continência (<=) e contenção (>=)
+            return Ok(match operator {
+                "=" => a == b,
+                "<>" => a != b,
+                "<=" => a.is_subset(b),
+                _ => b.is_subset(a),
+            });
+        }
+
+        let ordering = if let (Some(a), Some(b)) = (left.ordinal(), right.ordinal()) {
+            Some(a.cmp(&b))
+        } else if let (Some(a), Some(b)) = (left.real(), right.real()) {
+            a.partial_cmp(&b)
+        } else if let (Some(a), Some(b)) = (left.text(), right.text()) {
+            Some(a.cmp(&b))
+        } else if left == right {
+            Some(Ordering::Equal)
+        } else {
+            None
+        };
+        Ok(match operator {
+            "=" => ordering == Some(Ordering::Equal),
+            "<>" => ordering != Some(Ordering::Equal),
+            "<" => ordering == Some(Ordering::Less),
+            ">" => ordering == Some(Ordering::Greater),
+            "<=" => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+            ">=" => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+            _ => return Err(Self::invalid(expression)),
+        })
+    }
+
+    // Funções pré-definidas, a sobrecarga é escolhida pelos valores dos argumentos
+    fn function(
+        &self,
+        name: &str,
+        arguments: &[Value],
+        expression: &Expression,
+    ) -> Result<Value, String> {
+        let (line, column) = (expression.line, expression.column);
+        let real = |index: usize| arguments[index].real().unwrap_or_default();
+        let text = |index: usize| arguments[index].text().unwrap_or_default();
+        let invalid = |value: &Value| {
+            format!(
+                "Invalid argument {} for '{}' at line {} column {}.",
+                self.show(value, &Type::Unknown),
+                name,
+                line,
+                column
+            )
+        };
+        // Reais fora do intervalo do integer não podem ser convertidos por trunc e round
+        let truncated = |value: f64| {
+            if value.is_finite() {
+                Self::integer(value as i64, expression)
+            } else {
+                Err(invalid(&arguments[0]))
+            }
+        };
+
+        match (name, arguments) {
+            ("abs", [Value::Integer(value)]) => Self::integer(value.abs(), expression),
+            ("abs", _) => Ok(Value::Real(real(0).abs())),
+            ("sqr", [Value::Integer(value)]) => Self::integer(value * value, expression),
+            ("sqr", _) => Ok(Value::Real(real(0) * real(0))),
+            ("sqrt", _) if real(0) < 0.0 => Err(invalid(&arguments[0])),
+            ("sqrt", _) => Ok(Value::Real(real(0).sqrt())),
+            ("sin", _) => Ok(Value::Real(real(0).sin())),
+            ("cos", _) => Ok(Value::Real(real(0).cos())),
+            ("exp", _) => Ok(Value::Real(real(0).exp())),
+            ("ln", _) if real(0) <= 0.0 => Err(invalid(&arguments[0])),
+            ("ln", _) => Ok(Value::Real(real(0).ln())),
+            ("trunc", _) => truncated(real(0).trunc()),
+            ("round", _) => truncated(real(0).round()),
+            ("odd", [Value::Integer(value)]) => Ok(Value::Boolean(value % 2 != 0)),
+            ("ord", [value]) => Ok(Value::Integer(value.ordinal().unwrap_or_default())),
+            // Os ordinais de char vão de 0 a 255, como nos conjuntos e índices
+            ("chr", [Value::Integer(value)]) => match Type::Char.range() {
+                Some((low, high)) if (low..=high).contains(value) => {
+                    Ok(Value::Char(char::from(*value as u8)))
+                }
+                _ => Err(invalid(&arguments[0])),
+            },
+            ("succ" | "pred", [value]) => {
+                let ordinal = value.ordinal().unwrap_or_default();
+                let ordinal = if name == "succ" {
+                    ordinal + 1
+                } else {
+                    ordinal - 1
+                };
+                let limit = match value {
+                    Value::Integer(_) => Some((-MAX_INTEGER - 1, MAX_INTEGER)),
+                    Value::Boolean(_) => Some((0, 1)),
+                    Value::Enumerated(enumeration, _) => self
+                        .enumerations
+                        .get(enumeration)
+                        .map(|constants| (0, constants.len() as i64 - 1)),
+                    _ => None,
+                };
+                let inside = limit.is_none_or(|(low, high)| (low..=high).contains(&ordinal));
+                match value.with_ordinal(ordinal).filter(|_| inside) {
+                    Some(result) => Ok(result),
+                    None => Err(format!(
+                        "'{name}' of {} at line {line} column {column} is out of the range of its type.",
+                        self.show(value, &Type::Unknown)
+                    )),
+                }
+            }
+            ("length", _) => Ok(Value::Integer(text(0).chars().count() as i64)),
+            ("copy", [_, Value::Integer(index), Value::Integer(count)]) => {
+                let skip = usize::try_from(index - 1).unwrap_or_default();
+                let take = usize::try_from(*count).unwrap_or_default();
+                Ok(Value::Text(text(0).chars().skip(skip).take(take).collect()))
+            }
+            ("pos", _) => {
+                let (pattern, value) = (text(0), text(1));
+                let position = match value.find(&pattern) {
+                    Some(offset) if !pattern.is_empty() => value[..offset].chars().count() + 1,
+                    _ => 0,
+                };
+                Ok(Value::Integer(position as i64))
+            }
+            _ => Err(format!(
+                "Unknown function '{name}' at line {line} column {column}."
+            )),
+        }
+    }
+
+    fn block(&mut self, statements: &[Statement]) -> Result<Flow, String> {
+        for statement in statements {
+            let flow = self.statement(statement)?;
+            if flow != Flow::Normal {
+                return Ok(flow);
+            }
+        }
+        Ok(Flow::Normal)
+    }
+
+    fn condition(&self, expression: &Expression) -> Result<bool, String> {
+        self.evaluate(expression)?
+            .boolean()
+            .ok_or_else(|| Self::invalid(expression))
+    }
+
+    fn statement(&mut self, statement: &Statement) -> Result<Flow, String> {
+        match statement {
+            Statement::Assignment(target, value) => {
+                let value = self.evaluate(value)?;
+                let place = self.place(target)?;
+                let value = self.coerce(value, &target.value_type, target.line, target.column)?;
+                *self.write(&place) = value;
+            }
+            Statement::Call(name, arguments, line, column) => {
+                self.call(name, arguments, *line, *column)?;
+            }
+            Statement::If(condition, then, otherwise) => {
+                if self.condition(condition)? {
+                    return self.statement(then);
+                } else if let Some(otherwise) = otherwise {
+                    return self.statement(otherwise);
+                }
+            }
+            Statement::While(condition, body) => {
+                while self.condition(condition)? {
+                    match self.statement(body)? {
+                        Flow::Break => break,
+                        Flow::Exit => return Ok(Flow::Exit),
+                        Flow::Normal | Flow::Continue => {}
+                    }
+                }
+            }
+            Statement::Repeat(body, condition) => loop {
+                match self.block(body)? {
+                    Flow::Break => break,
+                    Flow::Exit => return Ok(Flow::Exit),
+                    Flow::Normal | Flow::Continue => {}
+                }
+                if self.condition(condition)? {
+                    break;
+                }
+            },
+            Statement::For {
+                variable,
+                start,
+                end,
+                downto,
+                body,
+            } => {
+                let first = self.evaluate(start)?;
+                let last = self.evaluate(end)?.ordinal().unwrap_or_default();
+                let place = self.place(variable)?;
+                let step = if *downto { -1 } else { 1 };
+                let mut ordinal = first.ordinal().unwrap_or_default();
+                while (*downto && ordinal >= last) || (!*downto && ordinal <= last) {
+                    let value = first
+                        .with_ordinal(ordinal)
+                        .unwrap_or(Value::Integer(ordinal));
+                    let value =
+                        self.coerce(value, &variable.value_type, variable.line, variable.column)?;
+                    *self.write(&place) = value;
+                    match self.statement(body)? {
+                        Flow::Break => break,
+                        Flow::Exit => return Ok(Flow::Exit),
+                        Flow::Normal | Flow::Continue => {}
+                    }
+                    ordinal += step;
+                }
+            }
+            Statement::Case {
+                selector,
+                elements,
+                otherwise,
+            } => {
+                let selected = self.evaluate(selector)?.ordinal();
+                let element = elements.iter().find(|(labels, _)| {
+                    labels.iter().any(|(low, high)| {
+                        selected.is_some_and(|value| (*low..=*high).contains(&value))
+                    })
+                });
+                if let Some((_, body)) = element {
+                    return self.statement(body);
+                } else if let Some(otherwise) = otherwise {
+                    return self.block(otherwise);
+                }
+            }
+            Statement::Block(statements) => return self.block(statements),
+            Statement::Break => return Ok(Flow::Break),
+            Statement::Continue => return Ok(Flow::Continue),
+            Statement::Exit => return Ok(Flow::Exit),
+        }
+        Ok(Flow::Normal)
+    }
+
+    fn call(
+        &mut self,
+        name: &str,
+        arguments: &[Expression],
+        line: usize,
+        column: usize,
+    ) -> Result<(), String> {
+        let Some((declared, procedure)) =
+            self.find(|frame| frame.procedures.get(name).map(Rc::clone))
+        else {
+            return self.builtin(name, arguments, line, column);
+        };
+        if self.frames.len() > MAX_DEPTH {
+            return Err(format!(
+                "Stack overflow in the call to '{name}' at line {line} column {column}."
+            ));
+        }
+
+        // Os argumentos são avaliados no escopo de quem chama e passados por valor
+        let mut frame = Frame {
+            link: Some(declared),
+            ..Frame::default()
+        };
+        for ((parameter, parameter_type), argument) in procedure.parameters.iter().zip(arguments) {
+            let value = self.evaluate(argument)?;
+            let value = self.coerce(value, parameter_type, line, column)?;
+            frame.variables.insert(parameter.to_owned(), value);
+        }
+
+        self.frames.push(frame);
+        self.declare(&procedure.declarations);
+        let result = self.block(&procedure.body);
+        self.frames.pop();
+        result.map(|_| ())
+    }
+
+    // Procedimentos pré-definidos new, dispose, write e writeln
+    fn builtin(
+        &mut self,
+        name: &str,
+        arguments: &[Expression],
+        line: usize,
+        column: usize,
+    ) -> Result<(), String> {
+        if name == "write" || name == "writeln" {
+            let mut text = String::new();
+            for argument in arguments {
+                let value = self.evaluate(argument)?;
+                match value.text() {
+                    Some(value) => text.push_str(&value),
+                    None => text.push_str(&self.show(&value, &argument.value_type)),
+                }
+            }
+            if name == "writeln" {
+                text.push('\n');
+            }
+            return self.output.write_all(text.as_bytes()).map_err(|e| {
+                format!("Unable to write the output at line {line} column {column}: {e}.")
+            });
+        }
+        let Some(pointer) = arguments
+            .first()
+            .filter(|_| name == "new" || name == "dispose")
+        else {
+            return Err(format!(
+                "Procedure '{name}' called at line {line} column {column} has no body."
+            ));
+        };
+        let place = self.place(pointer)?;
+        if name == "new" {
+            let value = match &pointer.value_type {
+                Type::Pointer(target) => self.initial(target),
+                _ => Value::Pointer(None),
+            };
+            self.heap.push(Some(value));
+            *self.write(&place) = Value::Pointer(Some(self.heap.len() - 1));
+            return Ok(());
+        }
+        match self.read(&place) {
+            Value::Pointer(Some(address)) if self.heap[*address].is_some() => {
+                let address = *address;
+                self.heap[address] = None;
+                Ok(())
+            }
+            _ => Err(format!(
+                "Dispose of an invalid pointer at line {line} column {column}."
+            )),
+        }
+    }
+
+    // Texto de um valor, com os nomes das constantes enumeradas
+    pub fn show(&self, value: &Value, value_type: &Type) -> String {
+        let quote = |text: String| format!("'{}'", text.replace('\'', "''"));
+        match value {
+            Value::Integer(value) => value.to_string(),
+            Value::Real(value) => format!("{value:?}"),
+            Value::Boolean(value) => value.to_string(),
+            Value::Char(value) if value.is_control() => format!("#{}", *value as u32),
+            Value::Char(value) => quote(value.to_string()),
+            Value::Text(value) => quote(value.to_owned()),
+            Value::Enumerated(enumeration, position) => self
+                .enumerations
+                .get(enumeration)
+                .and_then(|constants| constants.get(*position as usize))
+                .cloned()
+                .unwrap_or_else(|| position.to_string()),
+            Value::Set(elements) => {
+                let base = match value_type {
+                    Type::Set(base) => base,
+                    _ => &Type::Integer,
+                };
+                let sample = self.initial(base);
+                let elements: Vec<String> = elements
+                    .iter()
+                    .map(|element| match sample.with_ordinal(element) {
+                        Some(value) => self.show(&value, base),
+                        None => element.to_string(),
+                    })
+                    .collect();
+                format!("[{}]", elements.join(", "))
+            }
+            Value::Array(_, elements) => {
+                let element_type = match value_type {
+                    Type::Array(_, element) => element,
+                    _ => &Type::Unknown,
+                };
+                let elements: Vec<String> = elements
+                    .iter()
+                    .map(|element| self.show(element, element_type))
+                    .collect();
+                format!("[{}]", elements.join(", "))
+            }
+            Value::Record(fields) => {
+                let types = match value_type {
+                    Type::Record(types) => types.as_slice(),
+                    _ => &[],
+                };
+                let fields: Vec<String> = fields
+                    .iter()
+                    .map(|(name, value)| {
+                        let field_type = types
+                            .iter()
+                            .find(|(field, _)| field == name)
+                            .map_or(&Type::Unknown, |(_, field_type)| field_type);
+                        format!("{name}: {}", self.show(value, field_type))
+                    })
+                    .collect();
+                format!("({})", fields.join("; "))
+            }
+            Value::Pointer(None) => "nil".to_string(),
+            Value::Pointer(Some(address)) => format!("@{address}"),
+        }
+    }
+}
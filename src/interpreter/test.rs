@@ -0,0 +1,201 @@
+#[cfg(test)]
+mod interpreter_tests {
+    use crate::interpreter::Interpreter;
+    use crate::lexical::Scanner;
+    use crate::syntactic::interactive::Entry;
+    use crate::syntactic::Parser;
+
+    // Executa as entradas em ordem e mostra o valor da última, que deve ser uma expressão
+    fn run(inputs: &[&str]) -> Result<String, String> {
+        let mut parser = Parser::interactive();
+        let mut interpreter = Interpreter::default();
+        let mut shown = String::new();
+        for input in inputs {
+            let tokens = Scanner::new(input).init().map_err(|e| e.to_string())?;
+            match parser.feed(&tokens).map_err(|e| e.to_string())? {
+                Entry::Declarations(declarations) => interpreter.declare(&declarations),
+                Entry::Statements(statements) => interpreter.execute(&statements)?,
+                Entry::Expression(expression) => {
+                    let value = interpreter.evaluate(&expression)?;
+                    shown = interpreter.show(&value, &expression.value_type);
+                }
+            }
+        }
+        Ok(shown)
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(run(&["1 + 2 * 3"]), Ok("7".to_string()));
+        assert_eq!(run(&["8 / 2 * 2"]), Ok("8".to_string()));
+        assert_eq!(run(&["10 - 4 - 3"]), Ok("3".to_string()));
+        assert_eq!(run(&["-(2 + 3) * 2"]), Ok("-10".to_string()));
+        assert_eq!(run(&["1.5 * 2"]), Ok("3.0".to_string()));
+        assert_eq!(run(&["sqr(3) + abs(-2)"]), Ok("11".to_string()));
+        assert_eq!(run(&["'ab' + 'c'"]), Ok("'abc'".to_string()));
+        assert_eq!(run(&["(1 < 2) and not (3 = 4)"]), Ok("true".to_string()));
+    }
+
+    #[test]
+    fn test_loops_and_conditions() {
+        let declarations = "var i, total: integer;";
+        let inputs = [
+            declarations,
+            "total := 0; for i := 1 to 10 do total := total + i",
+            "total",
+        ];
+        assert_eq!(run(&inputs), Ok("55".to_string()));
+
+        let inputs = [
+            declarations,
+            "total := 0; i := 0;
+            while true do
+            begin
+                i := i + 1;
+                if i > 5 then break;
+                if odd(i) then continue;
+                total := total + i
+            end",
+            "total",
+        ];
+        assert_eq!(run(&inputs), Ok("6".to_string()));
+
+        let inputs = [
+            declarations,
+            "total := 1; repeat total := total * 2 until total > 100",
+            "total",
+        ];
+        assert_eq!(run(&inputs), Ok("128".to_string()));
+
+        let inputs = [
+            declarations,
+            "total := 0; for i := 3 downto 1 do case i of 1: total := total + 1; 2, 3: total := total + 10 end",
+            "total",
+        ];
+        assert_eq!(run(&inputs), Ok("21".to_string()));
+
+        let inputs = [
+            declarations,
+            "total := 0; for i := 1 to 9 do case i of 1..3: total := total + 1; 4, 6..8: total := total + 10 else total := total + 100 end",
+            "total",
+        ];
+        assert_eq!(run(&inputs), Ok("243".to_string()));
+    }
+
+    #[test]
+    fn test_procedures() {
+        let inputs = [
+            "var total: integer;",
+            "procedure somar(valor: integer);
+            begin
+                if valor = 0 then exit;
+                total := total + valor;
+                somar(valor - 1)
+            end;",
+            "total := 0; somar(4)",
+            "total",
+        ];
+        assert_eq!(run(&inputs), Ok("10".to_string()));
+
+        let inputs = ["procedure infinito; begin infinito end;", "infinito"];
+        let error = run(&inputs).unwrap_err();
+        assert!(error.starts_with("Stack overflow in the call to 'infinito'"));
+    }
+
+    #[test]
+    fn test_structured_values() {
+        let inputs = [
+            "type cor = (vermelho, verde, azul);
+            ponto = record x, y: integer end;",
+            "var a: array[1..3] of integer; p: ponto; c: set of cor;",
+            "a[2] := 7; p.y := 3; c := [vermelho, azul] + [verde]",
+        ];
+        let mut with = inputs.to_vec();
+        with.push("a");
+        assert_eq!(run(&with), Ok("[0, 7, 0]".to_string()));
+        let mut with = inputs.to_vec();
+        with.push("p");
+        assert_eq!(run(&with), Ok("(x: 0; y: 3)".to_string()));
+        let mut with = inputs.to_vec();
+        with.push("c");
+        assert_eq!(run(&with), Ok("[vermelho, verde, azul]".to_string()));
+        let mut with = inputs.to_vec();
+        with.push("succ(vermelho) in c - [verde]");
+        assert_eq!(run(&with), Ok("false".to_string()));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let shown = |input: &str| run(&[input]).unwrap();
+        assert_eq!(shown("[1, 3..5, 255] + [0]"), "[0, 1, 3, 4, 5, 255]");
+        assert_eq!(shown("[1..10] - [2..9]"), "[1, 10]");
+        assert_eq!(shown("[1..10] * [5, 70]"), "[5]");
+        assert_eq!(shown("[2, 3] <= [1..4]"), "true");
+        assert_eq!(shown("[1..4] >= [0, 2]"), "false");
+        assert_eq!(shown("[64, 128] = [128, 64]"), "true");
+        assert_eq!(shown("200 in [199..201]"), "true");
+        assert_eq!(shown("[5..3]"), "[]");
+    }
+
+    #[test]
+    fn test_pointers() {
+        let inputs = [
+            "type lista = ^no; no = record valor: integer; proximo: lista end;",
+            "var l: lista;",
+            "new(l); l^.valor := 1; new(l^.proximo); l^.proximo^.valor := 2",
+            "l^.valor + l^.proximo^.valor",
+        ];
+        assert_eq!(run(&inputs), Ok("3".to_string()));
+
+        let inputs = ["type p = ^integer;", "var x: p;", "x := nil; x^ := 1"];
+        let error = run(&inputs).unwrap_err();
+        assert_eq!(error, "Dereference of a nil pointer at line 1 column 12.");
+
+        let inputs = [
+            "type p = ^integer;",
+            "var x: p;",
+            "new(x); dispose(x)",
+            "x^",
+        ];
+        let error = run(&inputs).unwrap_err();
+        assert!(error.starts_with("Dereference of a disposed pointer"));
+    }
+
+    #[test]
+    fn test_runtime_errors() {
+        let inputs = ["var x: integer;", "x := 0", "10 / x"];
+        let error = run(&inputs).unwrap_err();
+        assert_eq!(error, "Division by zero at line 1 column 4.");
+
+        let inputs = ["var x: integer;", "x := 2147483647", "x * 2"];
+        let error = run(&inputs).unwrap_err();
+        assert_eq!(error, "Integer overflow at line 1 column 3.");
+
+        let inputs = [
+            "var a: array[1..3] of integer; i: integer;",
+            "i := 4; a[i] := 1",
+        ];
+        let error = run(&inputs).unwrap_err();
+        assert!(error.starts_with("Index 4 is out of the range '1..3'"));
+
+        let inputs = ["var d: 1..9; i: integer;", "i := 10; d := i"];
+        let error = run(&inputs).unwrap_err();
+        assert!(error.starts_with("Value 10 is out of the range '1..9'"));
+
+        let inputs = ["var s: set of 0..9; i: integer;", "i := 20; s := [1, i]"];
+        let error = run(&inputs).unwrap_err();
+        assert!(error.starts_with("Set element 20 is out of the range '0..9'"));
+        let inputs = [
+            "var s: set of 0..9; i: integer;",
+            "i := 9; s := [0..i]",
+            "5 in s",
+        ];
+        assert_eq!(run(&inputs), Ok("true".to_string()));
+
+        let inputs = ["var i: integer;", "i := 300", "chr(i)"];
+        let error = run(&inputs).unwrap_err();
+        assert_eq!(error, "Invalid argument 300 for 'chr' at line 1 column 1.");
+        let inputs = ["var i: integer;", "i := 255", "ord(chr(i))"];
+        assert_eq!(run(&inputs), Ok("255".to_string()));
+    }
+}
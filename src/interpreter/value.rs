@@ -0,0 +1,119 @@
+use crate::common::utils::MAX_SET_SIZE;
+use crate::syntactic::constant;
+
+// Valor de uma variável durante a execução
+#[derive(PartialEq, Debug, Clone)]
+pub enum Value {
+    Integer(i64),
+    Real(f64),
+    Boolean(bool),
+    Char(char),
+    Text(String),
+    Enumerated(String, i64), // Tipo enumerado e posição
+    Set(Bitset),             // Ordinais dos elementos
+    Array(i64, Vec<Value>),  // Ordinal do primeiro índice e os elementos
+    Record(Vec<(String, Value)>),
+    Pointer(Option<usize>), // Posição na memória dinâmica, None é nil
+}
+
+// Mapa de bits de um conjunto, um bit para cada ordinal entre 0 e MAX_SET_SIZE - 1
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub struct Bitset([u64; (MAX_SET_SIZE / 64) as usize]);
+
+impl Bitset {
+    // Falha com ordinais fora do intervalo do mapa
+    pub fn insert(&mut self, ordinal: i64) -> bool {
+        if !(0..MAX_SET_SIZE).contains(&ordinal) {
+            return false;
+        }
+        self.0[ordinal as usize / 64] |= 1 << (ordinal % 64);
+        true
+    }
+
+    pub fn contains(&self, ordinal: i64) -> bool {
+        (0..MAX_SET_SIZE).contains(&ordinal)
+            && self.0[ordinal as usize / 64] & 1 << (ordinal % 64) != 0
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self(std::array::from_fn(|i| self.0[i] | other.0[i]))
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        Self(std::array::from_fn(|i| self.0[i] & !other.0[i]))
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self(std::array::from_fn(|i| self.0[i] & other.0[i]))
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.difference(other) == Self::default()
+    }
+
+    // Ordinais dos elementos, em ordem crescente
+    pub fn iter(&self) -> impl Iterator<Item = i64> + '_ {
+        (0..MAX_SET_SIZE).filter(|ordinal| self.contains(*ordinal))
+    }
+}
+
+impl Value {
+    pub fn ordinal(&self) -> Option<i64> {
+        match self {
+            Value::Integer(value) | Value::Enumerated(_, value) => Some(*value),
+            Value::Boolean(value) => Some(*value as i64),
+            Value::Char(value) => Some(*value as i64),
+            _ => None,
+        }
+    }
+
+    // Valor do mesmo tipo ordinal na posição indicada, usado por succ, pred e pelo for
+    pub fn with_ordinal(&self, ordinal: i64) -> Option<Value> {
+        let value = match self {
+            Value::Integer(_) => Value::Integer(ordinal),
+            Value::Enumerated(enumeration, _) => Value::Enumerated(enumeration.to_owned(), ordinal),
+            Value::Boolean(_) => Value::Boolean(ordinal != 0),
+            Value::Char(_) => Value::Char(char::from_u32(u32::try_from(ordinal).ok()?)?),
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    pub fn real(&self) -> Option<f64> {
+        match self {
+            Value::Integer(value) => Some(*value as f64),
+            Value::Real(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn text(&self) -> Option<String> {
+        match self {
+            Value::Char(value) => Some(value.to_string()),
+            Value::Text(value) => Some(value.to_owned()),
+            _ => None,
+        }
+    }
+
+    pub fn boolean(&self) -> Option<bool> {
+        match self {
+            Value::Boolean(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl From<&constant::Value> for Value {
+    fn from(value: &constant::Value) -> Self {
+        match value {
+            constant::Value::Integer(value) => Value::Integer(*value),
+            constant::Value::Real(value) => Value::Real(*value),
+            constant::Value::Boolean(value) => Value::Boolean(*value),
+            constant::Value::Char(value) => Value::Char(*value),
+            constant::Value::Text(value) => Value::Text(value.to_owned()),
+            constant::Value::Enumerated(_, enumeration, position) => {
+                Value::Enumerated(enumeration.to_string(), *position)
+            }
+        }
+    }
+}
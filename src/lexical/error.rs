@@ -1,3 +1,4 @@
+use crate::common::diagnostic::Diagnostic;
 use std::error::Error;
 use std::fmt;
 
@@ -30,6 +31,12 @@ impl fmt::Display for InvalidCharError {
 
 impl Error for InvalidCharError {}
 
+impl From<InvalidCharError> for Diagnostic {
+    fn from(error: InvalidCharError) -> Self {
+        Diagnostic::new(error.to_string(), error.line, error.column)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct InvalidStateError {
     pub message: String,
@@ -58,3 +65,46 @@ impl fmt::Display for InvalidStateError {
 }
 
 impl Error for InvalidStateError {}
+
+impl From<InvalidStateError> for Diagnostic {
+    fn from(error: InvalidStateError) -> Self {
+        Diagnostic::new(error.to_string(), error.line, error.column)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InvalidLiteralError {
+    pub literal: String,
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl InvalidLiteralError {
+    pub fn new(literal: &str, message: &str, line: usize, column: usize) -> Self {
+        Self {
+            literal: literal.to_string(),
+            message: message.to_string(),
+            line,
+            column,
+        }
+    }
+}
+
+impl fmt::Display for InvalidLiteralError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            f,
+            "Invalid literal '{}' at line {}, column {}.\n{}.",
+            self.literal, self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for InvalidLiteralError {}
+
+impl From<InvalidLiteralError> for Diagnostic {
+    fn from(error: InvalidLiteralError) -> Self {
+        Diagnostic::new(error.to_string(), error.line, error.column)
+    }
+}
@@ -1,59 +1,104 @@
 pub mod error;
 mod test;
 
-use crate::common::token::{Token, TokenType};
-use crate::common::utils::is_keyword;
-use crate::lexical::error::{InvalidCharError, InvalidStateError};
-use std::error::Error;
+use crate::common::diagnostic::Diagnostic;
+use crate::common::token::{Lexeme, Span, Token, TokenType};
+use crate::common::utils::{is_keyword, parse_integer, unquote, MAX_INTEGER};
+use crate::lexical::error::{InvalidCharError, InvalidLiteralError, InvalidStateError};
 use std::iter::Peekable;
-use std::str::Chars;
+use std::str::CharIndices;
 
+// Analisador léxico incremental: cada chamada de next percorre a entrada até completar o próximo
+// token, cujo lexema é um trecho da própria entrada
 pub struct Scanner<'s> {
-    input: Peekable<Chars<'s>>,
-    tokens: Vec<Token>,
+    source: &'s str,
+    input: Peekable<CharIndices<'s>>,
     current_state: usize,
-    identifier_buffer: String,
+    start: usize,        // Byte onde começa o lexema em análise
+    start_column: usize, // Coluna onde começa o lexema em análise
     line: usize,
     column: usize,
+    pending: Option<Lexeme<'s>>, // Token completado pela última transição
+    finished: bool,
+    directives: Vec<(usize, String)>, // Comentários de diretiva, como {$warnings off}, com a sua linha
+    trivia: bool,                     // Mantém os comentários como tokens, usado pelo formatador
 }
 
 impl<'s> Scanner<'s> {
     pub fn new(input: &'s str) -> Self {
         Self {
-            input: input.chars().peekable(),
-            tokens: Vec::new(),
+            source: input,
+            input: input.char_indices().peekable(),
             current_state: 0,
-            identifier_buffer: String::new(),
+            start: 0,
+            start_column: 1,
             line: 1,
             column: 1,
+            pending: None,
+            finished: false,
+            directives: Vec::new(),
+            trivia: false,
         }
     }
 
-    pub fn init(&mut self) -> Result<Vec<Token>, Box<dyn Error + Send + Sync + 'static>> {
-        // println!("{:?}", self.input);
+    pub fn set_trivia(&mut self, value: bool) {
+        self.trivia = value;
+    }
+
+    // Diretivas encontradas até o ponto em que a entrada foi analisada
+    pub fn get_directives(&self) -> &[(usize, String)] {
+        &self.directives
+    }
+
+    // Analisa a entrada inteira, com os tokens que o analisador sintático guarda
+    pub fn init(&mut self) -> Result<Vec<Token>, Diagnostic> {
+        self.by_ref()
+            .map(|lexeme| lexeme.map(Token::from))
+            .collect()
+    }
+}
+
+impl<'s> Iterator for Scanner<'s> {
+    type Item = Result<Lexeme<'s>, Diagnostic>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
         loop {
-            self.transition()?;
-            if self.input.peek().is_none() {
-                break;
+            let Some((offset, current)) = self.input.next() else {
+                self.finished = true;
+                let unclosed = match self.current_state {
+                    15 => "comment",
+                    16 => "string",
+                    _ => return None,
+                };
+                return Some(Err(self.unclosed(unclosed)));
+            };
+            if let Err(error) = self.transition(offset, current) {
+                self.finished = true;
+                return Some(Err(error));
+            }
+            if let Some(lexeme) = self.pending.take() {
+                return Some(Ok(lexeme));
             }
         }
-
-        Ok(self.tokens.clone())
     }
 }
 
 impl<'s> Scanner<'s> {
-    fn transition(&mut self) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
-        let current = match self.input.next() {
-            None => {
-                return Err(Box::from("EOF"));
-            }
-            Some(value) => value,
-        };
+    #[allow(clippy::redundant_guards)]
+    fn transition(&mut self, offset: usize, current: char) -> Result<(), Diagnostic> {
         let next = match self.input.peek() {
             None => ' ',
-            Some(value) => *value,
+            Some((_, value)) => *value,
         };
+        // Fim do lexema caso ele termine neste caractere
+        let end = offset + current.len_utf8();
+        if self.current_state == 0 {
+            self.start = offset;
+            self.start_column = self.column;
+        }
         match self.current_state {
             0 => match current {
                 c if c == '\n' => {
@@ -62,93 +107,48 @@ impl<'s> Scanner<'s> {
                 }
                 c if c.is_whitespace() => {}
                 c if c.is_alphabetic() || c == '_' => {
-                    self.identifier_buffer.push(c);
                     if !(next.is_alphanumeric() || next == '_') {
-                        self.tokens.push(Token::new(
-                            &self.identifier_buffer,
-                            TokenType::Identifier,
-                            self.line,
-                            self.column - self.identifier_buffer.len() + 1,
-                        ));
-                        self.identifier_buffer = String::new();
-                        self.current_state = 0;
+                        self.push_word(end);
                     } else {
                         self.current_state = 1;
                     }
                 }
-                c if c.is_ascii_digit() => {
-                    self.identifier_buffer.push(current);
-                    if next == '.' {
-                        self.current_state = 3;
-                    } else if !next.is_ascii_digit() {
-                        self.tokens.push(Token::new(
-                            &self.identifier_buffer,
-                            TokenType::Integer,
+                c if c.is_ascii_digit() => match self.integer_transition(next) {
+                    Some(state) => self.current_state = state,
+                    None => self.push_integer(end)?,
+                },
+                '$' => {
+                    if !next.is_ascii_hexdigit() {
+                        return Err(InvalidLiteralError::new(
+                            "$",
+                            "Expected a hexadecimal digit after '$'",
                             self.line,
-                            self.column - self.identifier_buffer.len() + 1,
-                        ));
-                        self.identifier_buffer = String::new();
-                        self.current_state = 0;
-                    } else {
-                        self.current_state = 2;
+                            self.column,
+                        )
+                        .into());
                     }
+                    self.current_state = 8;
                 }
                 c if c == '+' => {
-                    self.tokens.push(Token::new(
-                        "+",
-                        TokenType::AdditiveOperators,
-                        self.line,
-                        self.column,
-                    ));
-                    self.current_state = 0;
+                    self.emit(TokenType::AdditiveOperators, end);
                 }
                 c if c == '-' => {
-                    self.tokens.push(Token::new(
-                        "-",
-                        TokenType::AdditiveOperators,
-                        self.line,
-                        self.column,
-                    ));
-                    self.current_state = 0;
+                    self.emit(TokenType::AdditiveOperators, end);
                 }
                 c if c == '*' => {
-                    self.tokens.push(Token::new(
-                        "*",
-                        TokenType::MultiplicativeOperators,
-                        self.line,
-                        self.column,
-                    ));
-                    self.current_state = 0;
+                    self.emit(TokenType::MultiplicativeOperators, end);
                 }
                 c if c == '/' => {
-                    self.tokens.push(Token::new(
-                        "/",
-                        TokenType::MultiplicativeOperators,
-                        self.line,
-                        self.column,
-                    ));
-                    self.current_state = 0;
+                    self.emit(TokenType::MultiplicativeOperators, end);
                 }
                 c if c == '=' => {
-                    self.tokens.push(Token::new(
-                        "=",
-                        TokenType::RelationalOperators,
-                        self.line,
-                        self.column,
-                    ));
-                    self.current_state = 0;
+                    self.emit(TokenType::RelationalOperators, end);
                 }
                 c if c == '>' => {
                     if next == '=' {
                         self.current_state = 9;
                     } else {
-                        self.tokens.push(Token::new(
-                            ">",
-                            TokenType::RelationalOperators,
-                            self.line,
-                            self.column - 1,
-                        ));
-                        self.current_state = 0;
+                        self.emit(TokenType::RelationalOperators, end);
                     }
                 }
                 c if c == '<' => {
@@ -157,226 +157,247 @@ impl<'s> Scanner<'s> {
                     } else if next == '>' {
                         self.current_state = 12;
                     } else {
-                        self.tokens.push(Token::new(
-                            "<",
-                            TokenType::RelationalOperators,
-                            self.line,
-                            self.column - 1,
-                        ));
-                        self.current_state = 0;
+                        self.emit(TokenType::RelationalOperators, end);
                     }
                 }
                 c if c == ':' => {
                     if next == '=' {
                         self.current_state = 14;
                     } else {
-                        self.tokens.push(Token::new(
-                            ":",
-                            TokenType::Delimiter,
-                            self.line,
-                            self.column,
-                        ));
-                        self.current_state = 0;
+                        self.emit(TokenType::Delimiter, end);
                     }
                 }
                 c if c == ',' => {
-                    self.tokens.push(Token::new(
-                        ",",
-                        TokenType::Delimiter,
-                        self.line,
-                        self.column,
-                    ));
-                    self.current_state = 0;
+                    self.emit(TokenType::Delimiter, end);
                 }
                 c if c == '.' => {
-                    self.tokens.push(Token::new(
-                        ".",
-                        TokenType::Delimiter,
-                        self.line,
-                        self.column,
-                    ));
-                    self.current_state = 0;
+                    if next == '.' {
+                        self.current_state = 18;
+                    } else {
+                        self.emit(TokenType::Delimiter, end);
+                    }
+                }
+                c if c == '^' => {
+                    self.emit(TokenType::Delimiter, end);
+                }
+                c if c == '[' => {
+                    self.emit(TokenType::Delimiter, end);
+                }
+                c if c == ']' => {
+                    self.emit(TokenType::Delimiter, end);
                 }
                 c if c == ';' => {
-                    self.tokens.push(Token::new(
-                        ";",
-                        TokenType::Delimiter,
-                        self.line,
-                        self.column,
-                    ));
-                    self.current_state = 0;
+                    self.emit(TokenType::Delimiter, end);
                 }
                 c if c == '(' => {
-                    self.tokens.push(Token::new(
-                        "(",
-                        TokenType::Delimiter,
-                        self.line,
-                        self.column,
-                    ));
-                    self.current_state = 0;
+                    self.emit(TokenType::Delimiter, end);
                 }
                 c if c == ')' => {
-                    self.tokens.push(Token::new(
-                        ")",
-                        TokenType::Delimiter,
-                        self.line,
-                        self.column,
-                    ));
-                    self.current_state = 0;
+                    self.emit(TokenType::Delimiter, end);
                 }
                 c if c == '{' => {
                     self.current_state = 15;
                 }
-                t => return Err(Box::from(InvalidCharError::new(t, self.line, self.column))),
+                c if c == '\'' => {
+                    self.current_state = 16;
+                }
+                t => return Err(InvalidCharError::new(t, self.line, self.column).into()),
             },
             1 => {
-                self.identifier_buffer.push(current);
-                if !(current.is_alphanumeric() || current == '_')
-                    || !(next.is_alphanumeric() || next == '_')
-                {
-                    if is_keyword(&self.identifier_buffer) {
-                        self.tokens.push(Token::new(
-                            &self.identifier_buffer,
-                            TokenType::Keyword,
-                            self.line,
-                            self.column - self.identifier_buffer.len() + 1,
-                        ))
-                    } else if self.identifier_buffer == "and" {
-                        self.tokens.push(Token::new(
-                            &self.identifier_buffer,
-                            TokenType::MultiplicativeOperators,
-                            self.line,
-                            self.column - self.identifier_buffer.len() + 1,
-                        ))
-                    } else if self.identifier_buffer == "or" {
-                        self.tokens.push(Token::new(
-                            &self.identifier_buffer,
-                            TokenType::AdditiveOperators,
-                            self.line,
-                            self.column - self.identifier_buffer.len() + 1,
-                        ))
-                    } else if self.identifier_buffer == "true" || self.identifier_buffer == "false"
-                    {
-                        self.tokens.push(Token::new(
-                            &self.identifier_buffer,
-                            TokenType::Boolean,
-                            self.line,
-                            self.column - self.identifier_buffer.len() + 1,
-                        ))
-                    } else {
-                        self.tokens.push(Token::new(
-                            &self.identifier_buffer,
-                            TokenType::Identifier,
-                            self.line,
-                            self.column - self.identifier_buffer.len() + 1,
-                        ))
-                    };
-                    self.identifier_buffer = String::new();
-                    self.current_state = 0;
+                if !(next.is_alphanumeric() || next == '_') {
+                    self.push_word(end);
                 }
             }
-            2 => {
-                self.identifier_buffer.push(current);
-                if current == '.' || next == '.' {
-                    self.current_state = 3;
+            2 => match self.integer_transition(next) {
+                Some(state) => self.current_state = state,
+                None => self.push_integer(end)?,
+            },
+            3 => {
+                // O ponto só chega aqui quando é seguido de um dígito, '1.' e '1..10' não são reais
+                self.current_state = 4;
+            }
+            4 => {
+                if self.is_exponent(next) {
+                    self.current_state = 5;
                 } else if !next.is_ascii_digit() {
-                    self.tokens.push(Token::new(
-                        &self.identifier_buffer,
-                        TokenType::Integer,
-                        self.line,
-                        self.column - self.identifier_buffer.len() + 1,
-                    ));
-                    self.identifier_buffer = String::new();
-                    self.current_state = 0;
+                    self.emit(TokenType::Real, end);
                 }
             }
-            3 => {
-                self.identifier_buffer.push(current);
-                if next.is_ascii_digit() {
-                    self.current_state = 4;
-                } else {
-                    self.tokens.push(Token::new(
-                        &self.identifier_buffer,
-                        TokenType::Real,
-                        self.line,
-                        self.column + 1 - self.identifier_buffer.len(),
-                    ));
-                    self.identifier_buffer = String::new();
-                    self.current_state = 0;
-                }
+            5 => {
+                self.current_state = if next == '+' || next == '-' { 6 } else { 7 };
             }
-            4 => {
-                self.identifier_buffer.push(current);
+            6 => {
+                self.current_state = 7;
+            }
+            7 => {
                 if !next.is_ascii_digit() {
-                    self.tokens.push(Token::new(
-                        &self.identifier_buffer,
-                        TokenType::Real,
-                        self.line,
-                        self.column + 1 - self.identifier_buffer.len(),
-                    ));
-                    self.identifier_buffer = String::new();
-                    self.current_state = 0;
+                    self.emit(TokenType::Real, end);
+                }
+            }
+            8 => {
+                if !next.is_ascii_hexdigit() {
+                    self.push_integer(end)?;
                 }
             }
             9 => {
                 if current == '=' {
-                    self.tokens.push(Token::new(
-                        ">=",
-                        TokenType::RelationalOperators,
-                        self.line,
-                        self.column - 1,
-                    ));
-                    self.current_state = 0;
+                    self.emit(TokenType::RelationalOperators, end);
                 }
             }
             11 => {
                 if current == '=' {
-                    self.tokens.push(Token::new(
-                        "<=",
-                        TokenType::RelationalOperators,
-                        self.line,
-                        self.column - 1,
-                    ));
-                    self.current_state = 0;
+                    self.emit(TokenType::RelationalOperators, end);
                 }
             }
             12 => {
                 if current == '>' {
-                    self.tokens.push(Token::new(
-                        "<>",
-                        TokenType::RelationalOperators,
-                        self.line,
-                        self.column - 1,
-                    ));
-                    self.current_state = 0;
+                    self.emit(TokenType::RelationalOperators, end);
                 }
             }
             14 => {
-                self.tokens.push(Token::new(
-                    ":=",
-                    TokenType::Assignment,
-                    self.line,
-                    self.column - 1,
-                ));
-                self.current_state = 0;
+                self.emit(TokenType::Assignment, end);
             }
             15 => {
                 if current == '}' {
+                    let content = &self.source[self.start + 1..offset];
+                    if let Some(directive) = content.strip_prefix('$') {
+                        self.directives
+                            .push((self.line, directive.trim().to_ascii_lowercase()));
+                    }
+                    if self.trivia {
+                        self.emit(TokenType::Comment, end);
+                    }
                     self.current_state = 0;
                 } else if current == '\n' {
-                    return Err(Box::from(format!("Unclosed comment at line {}", self.line)));
+                    return Err(self.unclosed("comment"));
                 }
             }
+            16 => {
+                if current == '\n' {
+                    return Err(self.unclosed("string"));
+                } else if current == '\'' && next == '\'' {
+                    self.current_state = 17;
+                } else if current == '\'' {
+                    self.push_text(end);
+                }
+            }
+            17 => {
+                // Segunda aspa de um escape ''
+                self.current_state = 16;
+            }
+            18 => {
+                self.emit(TokenType::Delimiter, end);
+            }
             _ => {
-                return Err(Box::from(InvalidStateError::new(
+                return Err(InvalidStateError::new(
                     "Reached an invalid end state on lexial analysis.",
                     self.line,
                     self.column,
-                )))
+                )
+                .into())
             }
         }
         self.column += 1;
 
         Ok(())
     }
+
+    // Comentário ou texto que não termina na linha em que começa
+    fn unclosed(&self, what: &str) -> Diagnostic {
+        let mut error = Diagnostic::new(
+            format!("Unclosed {what} at line {}", self.line),
+            self.line,
+            self.start_column,
+        );
+        error.span = Span {
+            start: self.start,
+            end: self.start + 1,
+        };
+        error
+    }
+
+    // Próximo estado depois de um dígito da parte inteira, ou None quando o inteiro termina
+    fn integer_transition(&self, next: char) -> Option<usize> {
+        if next.is_ascii_digit() {
+            Some(2)
+        } else if next == '.' && self.second_next().is_ascii_digit() {
+            Some(3)
+        } else if self.is_exponent(next) {
+            Some(5)
+        } else {
+            None
+        }
+    }
+
+    // 'e' só inicia um expoente quando é seguido por dígitos, com ou sem sinal
+    fn is_exponent(&self, next: char) -> bool {
+        if next != 'e' && next != 'E' {
+            return false;
+        }
+        let mut ahead = self.input.clone().map(|(_, c)| c);
+        ahead.next();
+        match ahead.next() {
+            Some('+') | Some('-') => ahead.next().is_some_and(|c| c.is_ascii_digit()),
+            Some(c) => c.is_ascii_digit(),
+            None => false,
+        }
+    }
+
+    fn second_next(&self) -> char {
+        self.input.clone().nth(1).map_or(' ', |(_, c)| c)
+    }
+
+    // Completa o token que começa em start e termina em end, voltando ao estado inicial
+    fn emit(&mut self, category: TokenType, end: usize) {
+        let span = Span {
+            start: self.start,
+            end,
+        };
+        let lexeme = Lexeme::new(self.source, category, self.line, self.start_column, span);
+        self.pending = Some(lexeme);
+        self.current_state = 0;
+    }
+
+    // Palavras reservadas, operadores escritos como palavras, booleanos e identificadores
+    fn push_word(&mut self, end: usize) {
+        let word = &self.source[self.start..end];
+        let category = if is_keyword(word) {
+            TokenType::Keyword
+        } else if word.eq_ignore_ascii_case("and") {
+            TokenType::MultiplicativeOperators
+        } else if word.eq_ignore_ascii_case("or") {
+            TokenType::AdditiveOperators
+        } else if word.eq_ignore_ascii_case("in") {
+            TokenType::RelationalOperators
+        } else if word.eq_ignore_ascii_case("true") || word.eq_ignore_ascii_case("false") {
+            TokenType::Boolean
+        } else {
+            TokenType::Identifier
+        };
+        self.emit(category, end);
+    }
+
+    fn push_integer(&mut self, end: usize) -> Result<(), Diagnostic> {
+        let literal = &self.source[self.start..end];
+        if parse_integer(literal).is_none() {
+            return Err(InvalidLiteralError::new(
+                literal,
+                &format!("Integer literal is out of range, the maximum value is {MAX_INTEGER}"),
+                self.line,
+                self.start_column,
+            )
+            .into());
+        }
+        self.emit(TokenType::Integer, end);
+        Ok(())
+    }
+
+    fn push_text(&mut self, end: usize) {
+        let category = if unquote(&self.source[self.start..end]).chars().count() == 1 {
+            TokenType::Char
+        } else {
+            TokenType::Text
+        };
+        self.emit(category, end);
+    }
 }
@@ -23,6 +23,17 @@ mod lexical_tests {
             .all(|token| token.is_type_of(TokenType::Keyword)))
     }
 
+    #[test]
+    fn test_keywords_ignore_case() {
+        let input = "PROGRAM Var BEGIN End";
+        let mut scanner = Scanner::new(input);
+        let tokens = scanner.init().unwrap();
+        assert!(tokens
+            .iter()
+            .all(|token| token.is_type_of(TokenType::Keyword)));
+        assert_eq!(tokens[0].get_lexeme(), "PROGRAM");
+    }
+
     #[test]
     fn test_valid_identifier() {
         let input = "gabriel souza9 _cruz";
@@ -45,7 +56,7 @@ mod lexical_tests {
 
     #[test]
     fn test_reals() {
-        let input = "1. 2.5";
+        let input = "1.0 2.5 1.5e-3 2E10 3.0e+2";
         let mut scanner = Scanner::new(input);
         let tokens = scanner.init().unwrap();
         tokens
@@ -53,9 +64,154 @@ mod lexical_tests {
             .for_each(|token| assert!(token.is_type_of(TokenType::Real)))
     }
 
+    #[test]
+    fn test_integer_followed_by_dot() {
+        let input = "1. 1..10";
+        let mut scanner = Scanner::new(input);
+        let tokens = scanner.init().unwrap();
+        let types: Vec<TokenType> = tokens.iter().map(|token| token.get_type()).collect();
+        assert_eq!(tokens[3].get_lexeme(), "..");
+        assert_eq!(
+            types,
+            vec![
+                TokenType::Integer,
+                TokenType::Delimiter,
+                TokenType::Integer,
+                TokenType::Delimiter,
+                TokenType::Integer
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hexadecimal_integers() {
+        let input = "$FF $0a $7FFFFFFF";
+        let mut scanner = Scanner::new(input);
+        let tokens = scanner.init().unwrap();
+        assert_eq!(tokens.len(), 3);
+        assert!(tokens
+            .iter()
+            .all(|token| token.is_type_of(TokenType::Integer)))
+    }
+
+    #[test]
+    fn test_integer_out_of_range() {
+        assert!(Scanner::new("2147483647").init().is_ok());
+        assert!(Scanner::new("2147483648").init().is_err());
+        assert!(Scanner::new("$100000000").init().is_err());
+        assert!(Scanner::new("$").init().is_err());
+    }
+
+    #[test]
+    fn test_text_literals() {
+        let input = "'a' '''' 'abc' '' 'it''s'";
+        let mut scanner = Scanner::new(input);
+        let tokens = scanner.init().unwrap();
+        let types: Vec<TokenType> = tokens.iter().map(|token| token.get_type()).collect();
+        assert_eq!(
+            types,
+            vec![
+                TokenType::Char,
+                TokenType::Char,
+                TokenType::Text,
+                TokenType::Text,
+                TokenType::Text
+            ]
+        );
+        assert_eq!(tokens[4].get_lexeme(), "'it''s'");
+    }
+
+    #[test]
+    fn test_unclosed_text() {
+        assert!(Scanner::new("'abc").init().is_err());
+        assert!(Scanner::new("'abc\n'").init().is_err());
+    }
+
+    #[test]
+    fn test_unclosed_comment() {
+        let error = Scanner::new("x := 1 { sem fim").init().unwrap_err();
+        assert_eq!(error.to_string(), "Unclosed comment at line 1");
+        assert!(Scanner::new("x {\n}").init().is_err());
+        assert!(Scanner::new("x {}").init().is_ok());
+    }
+
+    #[test]
+    fn test_directives() {
+        let input = "{ comentário }\n{$WARNINGS OFF} x\n{$warnings on}";
+        let mut scanner = Scanner::new(input);
+        let tokens = scanner.init().unwrap();
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(
+            scanner.get_directives(),
+            [
+                (2, "warnings off".to_string()),
+                (3, "warnings on".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trivia() {
+        let input = "x {nota}\n{$warnings off} y";
+        let mut scanner = Scanner::new(input);
+        assert_eq!(scanner.init().unwrap().len(), 2);
+
+        let mut scanner = Scanner::new(input);
+        scanner.set_trivia(true);
+        let tokens = scanner.init().unwrap();
+        let comments: Vec<_> = tokens
+            .iter()
+            .filter(|token| token.is_type_of(TokenType::Comment))
+            .map(|token| (token.get_lexeme(), token.get_line(), token.get_column()))
+            .collect();
+        assert_eq!(comments, [("{nota}", 1, 3), ("{$warnings off}", 2, 1)]);
+        assert_eq!(scanner.get_directives().len(), 1);
+    }
+
+    #[test]
+    fn test_streaming() {
+        let input = "if ação >= 1.5e3 then x := 'é' & y";
+        let mut scanner = Scanner::new(input);
+        let mut lexemes = vec![];
+        for lexeme in scanner.by_ref() {
+            match lexeme {
+                Ok(lexeme) => lexemes.push(lexeme),
+                Err(error) => {
+                    assert!(error.to_string().contains("'&'"));
+                    break;
+                }
+            }
+        }
+        assert!(scanner.next().is_none());
+
+        let positions: Vec<_> = lexemes
+            .iter()
+            .map(|lexeme| (lexeme.get_lexeme(), lexeme.get_column()))
+            .collect();
+        assert_eq!(
+            positions,
+            [
+                ("if", 1),
+                ("ação", 4),
+                (">=", 9),
+                ("1.5e3", 12),
+                ("then", 18),
+                ("x", 23),
+                (":=", 25),
+                ("'é'", 28)
+            ]
+        );
+        for lexeme in &lexemes {
+            let span = lexeme.get_span();
+            assert_eq!(&input[span.start..span.end], lexeme.get_lexeme());
+        }
+        assert_eq!(lexemes[1].get_type(), TokenType::Identifier);
+        assert_eq!(lexemes[7].get_type(), TokenType::Char);
+    }
+
     #[test]
     fn test_delimiters() {
-        let input = "; . : ( ) ,";
+        let input = "; . .. : ( ) [ ] ,";
         let mut scanner = Scanner::new(input);
         let tokens = scanner.init().unwrap();
         assert!(tokens
@@ -75,12 +231,14 @@ mod lexical_tests {
 
     #[test]
     fn test_relational_operators() {
-        let input = "= < > <= >= <>";
+        let input = "= < > <= >= <> in IN";
         let mut scanner = Scanner::new(input);
         let tokens = scanner.init().unwrap();
         assert!(tokens
             .iter()
-            .all(|token| token.is_type_of(TokenType::RelationalOperators)))
+            .all(|token| token.is_type_of(TokenType::RelationalOperators)));
+        let columns: Vec<_> = tokens.iter().map(|token| token.get_column()).collect();
+        assert_eq!(columns, [1, 3, 5, 7, 10, 13, 16, 19]);
     }
 
     #[test]
@@ -104,6 +262,7 @@ mod lexical_tests {
     }
 
     #[test]
+    #[allow(clippy::redundant_pattern_matching)]
     fn test1() {
         let input = consume_file("tests/Test1.pas".to_string()).unwrap();
         let mut scanner = Scanner::new(&input);
@@ -117,6 +276,7 @@ mod lexical_tests {
     }
 
     #[test]
+    #[allow(clippy::redundant_pattern_matching)]
     fn test2() {
         let input = consume_file("tests/Test2.pas".to_string()).unwrap();
         let mut scanner = Scanner::new(&input);
@@ -0,0 +1,108 @@
+// Biblioteca do compilador: análise léxica, análise sintática com verificação de tipos, a árvore
+// sintática resultante e a geração de código. O binário compilador2 é apenas a linha de comando
+// sobre ela
+
+mod backend;
+mod cli;
+mod common;
+mod dump;
+mod formatter;
+#[cfg(test)]
+mod generator;
+mod interpreter;
+mod lexical;
+mod lsp;
+mod repl;
+mod syntactic;
+
+pub use backend::Target;
+pub use common::diagnostic::Diagnostic;
+pub use common::token::{Lexeme, Span, Token, TokenType};
+pub use common::types::Type;
+pub use lexical::Scanner;
+pub use syntactic::ast;
+pub use syntactic::constant::Value;
+pub use syntactic::Parser;
+
+// Entrada dos binários compilador2 e lsp na linha de comando, que não faz parte da API pública
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::cli::main;
+}
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+// Fase em que a compilação falhou. As falhas da análise levam o diagnóstico, com a posição
+#[derive(PartialEq, Debug, Clone)]
+pub enum Error {
+    Io(String),
+    Lexical(Diagnostic),
+    Syntactic(Diagnostic),
+    Unsupported(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(message) | Error::Unsupported(message) => write!(f, "{message}"),
+            Error::Lexical(diagnostic) | Error::Syntactic(diagnostic) => write!(f, "{diagnostic}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+// Programa verificado e os avisos da verificação, na ordem em que foram encontrados
+#[derive(PartialEq, Debug, Clone)]
+pub struct Checked {
+    pub program: ast::Program,
+    pub warnings: Vec<Diagnostic>,
+}
+
+// Tokens do código fonte, sem os comentários
+pub fn tokenize(source: &str) -> Result<Vec<Token>, Error> {
+    Scanner::new(source).init().map_err(Error::Lexical)
+}
+
+// Analisa e verifica os tipos dos tokens, as units usadas são procuradas no diretório atual
+pub fn parse(tokens: &[Token]) -> Result<ast::Program, Error> {
+    let mut parser = Parser::new(tokens);
+    parser.check().map_err(Error::Syntactic)?;
+    Ok(parser.get_program().cloned().unwrap_or_default())
+}
+
+// Verifica o código fonte, respeitando as diretivas {$warnings off} e {$warnings on}
+pub fn check(source: &str) -> Result<Checked, Error> {
+    verify(source, None)
+}
+
+// Verifica um arquivo, com as units usadas procuradas no diretório dele
+pub fn check_file(path: &Path) -> Result<Checked, Error> {
+    let source = fs::read_to_string(path)
+        .map_err(|e| Error::Io(format!("Unable to read '{}': {e}", path.display())))?;
+    verify(&source, Some(path))
+}
+
+// Verifica o programa e gera o código do alvo, os alvos sem backend falham com Unsupported
+pub fn compile(source: &str, target: Target) -> Result<String, Error> {
+    let checked = check(source)?;
+    backend::generate(&checked.program, target)
+        .ok_or_else(|| Error::Unsupported(backend::unavailable(target)))
+}
+
+fn verify(source: &str, path: Option<&Path>) -> Result<Checked, Error> {
+    let mut scanner = Scanner::new(source);
+    let tokens = scanner.init().map_err(Error::Lexical)?;
+    let mut parser = Parser::new(&tokens);
+    parser.set_directives(scanner.get_directives());
+    if let Some(path) = path {
+        parser.set_source_path(path);
+    }
+    parser.check().map_err(Error::Syntactic)?;
+    Ok(Checked {
+        program: parser.get_program().cloned().unwrap_or_default(),
+        warnings: parser.get_warnings().to_vec(),
+    })
+}
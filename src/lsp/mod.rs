@@ -0,0 +1,561 @@
+mod test;
+
+use crate::common::diagnostic::Diagnostic;
+use crate::common::json::Json;
+use crate::common::symbol::{Symbol, SymbolKind};
+use crate::common::token::Token;
+use crate::lexical::Scanner;
+use crate::syntactic::Parser;
+use std::io;
+use std::io::{BufRead, Read, Write};
+use std::path::PathBuf;
+
+// Sincronização do texto completo do documento a cada mudança
+const FULL_SYNC: usize = 1;
+
+// Severidades de diagnóstico do protocolo
+const ERROR: usize = 1;
+const WARNING: usize = 2;
+
+// Maior conteúdo aceito em uma mensagem, para que um Content-Length qualquer não reserve memória
+const MAX_CONTENT_LENGTH: usize = 16 * 1024 * 1024;
+
+// Código de erro do protocolo para mensagens inválidas
+const INVALID_REQUEST: i32 = -32600;
+
+// Tipos de símbolo do protocolo usados em textDocument/documentSymbol
+const FUNCTION: usize = 12;
+const VARIABLE: usize = 13;
+
+// Documento aberto no editor e o resultado da sua última análise
+struct Document {
+    uri: String,
+    text: String,
+    diagnostics: Vec<(usize, Diagnostic)>, // Severidade e diagnóstico
+    declarations: Vec<Symbol>,
+    references: Vec<(Token, Symbol)>,
+}
+
+#[derive(Default)]
+pub struct Server {
+    documents: Vec<Document>,
+    shutdown: bool,
+    exit: Option<i32>,
+}
+
+// Atende o protocolo pela entrada e saída padrão até receber 'exit', retornando o código de saída
+pub fn serve() -> i32 {
+    let mut input = io::stdin().lock();
+    let mut output = io::stdout().lock();
+    let mut server = Server::default();
+    loop {
+        let message = match read_message(&mut input) {
+            Ok(Some(message)) => message,
+            // Uma mensagem inválida é respondida com um erro, sem encerrar o servidor
+            Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+                let error = error_response(Json::Null, INVALID_REQUEST, &e.to_string());
+                if write_message(&mut output, &error).is_err() {
+                    return 1;
+                }
+                continue;
+            }
+            Ok(None) | Err(_) => return 1,
+        };
+        let messages = match Json::parse(&message) {
+            Ok(request) => server.handle(&request),
+            Err(e) => vec![error_response(Json::Null, -32700, &e)],
+        };
+        for message in messages {
+            if write_message(&mut output, &message).is_err() {
+                return 1;
+            }
+        }
+        if let Some(code) = server.exit {
+            return code;
+        }
+    }
+}
+
+// Lê uma mensagem no formato 'Content-Length: n\r\n\r\n<conteúdo>'
+fn read_message(input: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+    let Some(length) = length else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Missing Content-Length header.",
+        ));
+    };
+    if length > MAX_CONTENT_LENGTH {
+        // O conteúdo é descartado sem ser guardado, para que a próxima mensagem possa ser lida
+        io::copy(&mut Read::take(&mut *input, length as u64), &mut io::sink())?;
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Content-Length {length} exceeds the maximum of {MAX_CONTENT_LENGTH} bytes."),
+        ));
+    }
+    let mut content = vec![0; length];
+    input.read_exact(&mut content)?;
+    String::from_utf8(content)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn write_message(output: &mut impl Write, message: &Json) -> io::Result<()> {
+    let content = message.to_string();
+    write!(
+        output,
+        "Content-Length: {}\r\n\r\n{}",
+        content.len(),
+        content
+    )?;
+    output.flush()
+}
+
+fn response(id: Json, result: Json) -> Json {
+    Json::object([("jsonrpc", "2.0".into()), ("id", id), ("result", result)])
+}
+
+fn error_response(id: Json, code: i32, message: &str) -> Json {
+    Json::object([
+        ("jsonrpc", "2.0".into()),
+        ("id", id),
+        (
+            "error",
+            Json::object([
+                ("code", Json::Number(code as f64)),
+                ("message", message.into()),
+            ]),
+        ),
+    ])
+}
+
+fn notification(method: &str, params: Json) -> Json {
+    Json::object([
+        ("jsonrpc", "2.0".into()),
+        ("method", method.into()),
+        ("params", params),
+    ])
+}
+
+impl Server {
+    // Trata uma mensagem recebida e retorna as respostas e notificações a enviar
+    pub fn handle(&mut self, message: &Json) -> Vec<Json> {
+        let id = message.get("id").to_owned();
+        let params = message.get("params");
+        let method = message.get("method").as_str().unwrap_or_default();
+        let result = match method {
+            "initialize" => Self::capabilities(),
+            "shutdown" => {
+                self.shutdown = true;
+                Json::Null
+            }
+            "exit" => {
+                self.exit = Some(if self.shutdown { 0 } else { 1 });
+                return vec![];
+            }
+            "textDocument/didOpen" => {
+                let document = params.get("textDocument");
+                return self.update(
+                    document.get("uri").as_str().unwrap_or_default(),
+                    document.get("text").as_str().unwrap_or_default(),
+                );
+            }
+            "textDocument/didChange" => {
+                // O servidor pede sincronização completa, então a última mudança tem o texto inteiro
+                let changes = params.get("contentChanges").as_array().unwrap_or_default();
+                let Some(text) = changes
+                    .last()
+                    .and_then(|change| change.get("text").as_str())
+                else {
+                    return vec![];
+                };
+                let uri = params.get("textDocument").get("uri").as_str();
+                return self.update(uri.unwrap_or_default(), text);
+            }
+            "textDocument/didClose" => {
+                let uri = params.get("textDocument").get("uri").as_str();
+                let uri = uri.unwrap_or_default();
+                self.documents.retain(|document| document.uri != uri);
+                return vec![Self::publish(uri, vec![])];
+            }
+            "textDocument/definition" => self.definition(params),
+            "textDocument/hover" => self.hover(params),
+            "textDocument/references" => self.references(params),
+            "textDocument/documentSymbol" => self.document_symbols(params),
+            _ if id == Json::Null => return vec![], // Notificações desconhecidas são ignoradas
+            _ => return vec![error_response(id, -32601, "Method not found")],
+        };
+        if id == Json::Null {
+            return vec![];
+        }
+        vec![response(id, result)]
+    }
+
+    fn capabilities() -> Json {
+        Json::object([
+            (
+                "capabilities",
+                Json::object([
+                    ("textDocumentSync", FULL_SYNC.into()),
+                    ("definitionProvider", true.into()),
+                    ("hoverProvider", true.into()),
+                    ("referencesProvider", true.into()),
+                    ("documentSymbolProvider", true.into()),
+                ]),
+            ),
+            (
+                "serverInfo",
+                Json::object([("name", env!("CARGO_PKG_NAME").into())]),
+            ),
+        ])
+    }
+
+    fn update(&mut self, uri: &str, text: &str) -> Vec<Json> {
+        let document = Document::analyze(uri, text);
+        let diagnostics = document.diagnostics_json();
+        self.documents.retain(|document| document.uri != uri);
+        self.documents.push(document);
+        vec![Self::publish(uri, diagnostics)]
+    }
+
+    fn publish(uri: &str, diagnostics: Vec<Json>) -> Json {
+        notification(
+            "textDocument/publishDiagnostics",
+            Json::object([("uri", uri.into()), ("diagnostics", diagnostics.into())]),
+        )
+    }
+
+    // Documento e posição, em linha e coluna do analisador léxico, indicados pela requisição
+    fn target(&self, params: &Json) -> Option<(&Document, usize, usize)> {
+        let uri = params.get("textDocument").get("uri").as_str()?;
+        let document = self.documents.iter().find(|document| document.uri == uri)?;
+        let position = params.get("position");
+        let (line, column) = document.scanner_position(
+            position.get("line").as_usize()?,
+            position.get("character").as_usize()?,
+        );
+        Some((document, line, column))
+    }
+
+    fn definition(&self, params: &Json) -> Json {
+        self.target(params)
+            .and_then(|(document, line, column)| {
+                let symbol = document.symbol_at(line, column)?;
+                let token = symbol.as_token()?;
+                document
+                    .is_declared(&token)
+                    .then(|| document.location(&token))
+            })
+            .unwrap_or(Json::Null)
+    }
+
+    fn hover(&self, params: &Json) -> Json {
+        self.target(params)
+            .and_then(|(document, line, column)| {
+                let symbol = document.symbol_at(line, column)?;
+                Some(Json::object([(
+                    "contents",
+                    Json::object([
+                        ("kind", "markdown".into()),
+                        (
+                            "value",
+                            format!("```pascal\n{}\n```", describe(&symbol)).into(),
+                        ),
+                    ]),
+                )]))
+            })
+            .unwrap_or(Json::Null)
+    }
+
+    fn references(&self, params: &Json) -> Json {
+        let Some((document, line, column)) = self.target(params) else {
+            return Json::Null;
+        };
+        let Some(declaration) = document
+            .symbol_at(line, column)
+            .and_then(|symbol| symbol.as_token())
+        else {
+            return Json::Null;
+        };
+        let include = params.get("context").get("includeDeclaration").as_bool();
+
+        let mut tokens: Vec<&Token> = document
+            .references
+            .iter()
+            .filter(|(_, symbol)| symbol.as_token().is_some_and(|t| same(&t, &declaration)))
+            .map(|(token, _)| token)
+            .collect();
+        if include == Some(true) && document.is_declared(&declaration) {
+            tokens.push(&declaration);
+        }
+        tokens.sort_by_key(|token| (token.get_line(), token.get_column()));
+        tokens.dedup_by(|a, b| same(a, b));
+        tokens
+            .into_iter()
+            .map(|token| document.location(token))
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    fn document_symbols(&self, params: &Json) -> Json {
+        let uri = params.get("textDocument").get("uri").as_str();
+        let Some(document) = self
+            .documents
+            .iter()
+            .find(|document| Some(document.uri.as_str()) == uri)
+        else {
+            return Json::Null;
+        };
+        let mut symbols: Vec<(Token, usize)> = document
+            .declarations
+            .iter()
+            .filter_map(|symbol| {
+                let kind = match symbol.get_kind()? {
+                    SymbolKind::Procedure | SymbolKind::Function => FUNCTION,
+                    SymbolKind::Variable => VARIABLE,
+                    _ => return None,
+                };
+                Some((symbol.as_token()?, kind))
+            })
+            .collect();
+        symbols.sort_by_key(|(token, _)| (token.get_line(), token.get_column()));
+        symbols
+            .iter()
+            .map(|(token, kind)| {
+                Json::object([
+                    ("name", token.get_lexeme().into()),
+                    ("kind", (*kind).into()),
+                    ("location", document.location(token)),
+                ])
+            })
+            .collect::<Vec<_>>()
+            .into()
+    }
+}
+
+impl Document {
+    fn analyze(uri: &str, text: &str) -> Self {
+        let mut document = Document {
+            uri: uri.to_string(),
+            text: text.to_string(),
+            diagnostics: vec![],
+            declarations: vec![],
+            references: vec![],
+        };
+
+        let mut scanner = Scanner::new(text);
+        let tokens = match scanner.init() {
+            Ok(tokens) => tokens,
+            Err(e) => {
+                document.diagnostics.push((ERROR, e));
+                return document;
+            }
+        };
+        let mut parser = Parser::new(&tokens);
+        parser.set_directives(scanner.get_directives());
+        if let Some(path) = file_path(uri) {
+            parser.set_source_path(&path);
+        }
+        let result = parser.check();
+        // Os avisos das units usadas têm posições nos arquivos delas
+        for warning in parser.get_warnings() {
+            if warning.file.is_none() {
+                document.diagnostics.push((WARNING, warning.to_owned()));
+            }
+        }
+        if let Err(e) = result {
+            document.diagnostics.push((ERROR, e));
+        }
+        document.declarations = parser.get_declarations();
+        document.references = parser.get_references().to_vec();
+        document
+    }
+
+    // Os diagnósticos sem posição, como o fim inesperado do arquivo, são indicados na última
+    // linha. Sem o trecho, é destacada a palavra que começa na posição
+    fn diagnostics_json(&self) -> Vec<Json> {
+        self.diagnostics
+            .iter()
+            .map(|(severity, diagnostic)| {
+                let (line, column) = diagnostic
+                    .position
+                    .unwrap_or((self.text.lines().count().max(1), 1));
+                let span = diagnostic.span;
+                let length = match self.text.get(span.start..span.end) {
+                    Some(text) if !text.is_empty() => text.chars().count(),
+                    _ => self.word_length(line, column),
+                };
+                Json::object([
+                    ("range", self.range(line, column, length)),
+                    ("severity", (*severity).into()),
+                    ("source", env!("CARGO_PKG_NAME").into()),
+                    ("message", diagnostic.message.as_str().into()),
+                ])
+            })
+            .collect()
+    }
+
+    // Símbolo declarado ou referenciado no identificador que contém a posição
+    fn symbol_at(&self, line: usize, column: usize) -> Option<Symbol> {
+        let contains = |token: &Token| {
+            token.get_line() == line
+                && token.get_column() <= column
+                && column < token.get_column() + token.get_lexeme().chars().count()
+        };
+        self.references
+            .iter()
+            .find(|(token, _)| contains(token))
+            .map(|(_, symbol)| symbol)
+            .or_else(|| {
+                self.declarations
+                    .iter()
+                    .find(|symbol| symbol.as_token().is_some_and(|token| contains(&token)))
+            })
+            .cloned()
+    }
+
+    fn is_declared(&self, token: &Token) -> bool {
+        self.declarations
+            .iter()
+            .any(|symbol| symbol.as_token().is_some_and(|t| same(&t, token)))
+    }
+
+    fn location(&self, token: &Token) -> Json {
+        let length = token.get_lexeme().chars().count();
+        Json::object([
+            ("uri", self.uri.as_str().into()),
+            (
+                "range",
+                self.range(token.get_line(), token.get_column(), length),
+            ),
+        ])
+    }
+
+    fn range(&self, line: usize, column: usize, length: usize) -> Json {
+        Json::object([
+            ("start", self.lsp_position(line, column)),
+            ("end", self.lsp_position(line, column + length)),
+        ])
+    }
+
+    // O analisador léxico conta linhas e colunas a partir de 1 e em caracteres, o protocolo conta a
+    // partir de 0 e em unidades UTF-16
+    fn lsp_position(&self, line: usize, column: usize) -> Json {
+        let text = self
+            .text
+            .lines()
+            .nth(line.saturating_sub(1))
+            .unwrap_or_default();
+        let character: usize = text
+            .chars()
+            .take(column.saturating_sub(1))
+            .map(char::len_utf16)
+            .sum();
+        Json::object([
+            ("line", line.saturating_sub(1).into()),
+            ("character", character.into()),
+        ])
+    }
+
+    fn scanner_position(&self, line: usize, character: usize) -> (usize, usize) {
+        let text = self.text.lines().nth(line).unwrap_or_default();
+        let mut units = 0;
+        let column = text
+            .chars()
+            .take_while(|c| {
+                units += c.len_utf16();
+                units <= character
+            })
+            .count();
+        (line + 1, column + 1)
+    }
+
+    // Tamanho da palavra que começa na posição, usado para destacar o trecho de um diagnóstico
+    fn word_length(&self, line: usize, column: usize) -> usize {
+        let text = self
+            .text
+            .lines()
+            .nth(line.saturating_sub(1))
+            .unwrap_or_default();
+        let word = text
+            .chars()
+            .skip(column.saturating_sub(1))
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .count();
+        word.max(1)
+    }
+}
+
+fn same(a: &Token, b: &Token) -> bool {
+    a.get_line() == b.get_line() && a.get_column() == b.get_column() && a == b
+}
+
+// Caminho local de uma URI 'file://', necessário para encontrar as units usadas
+fn file_path(uri: &str) -> Option<PathBuf> {
+    let path = uri.strip_prefix("file://")?;
+    let mut bytes = vec![];
+    let mut chars = path.bytes();
+    while let Some(byte) = chars.next() {
+        if byte == b'%' {
+            let hex: Vec<u8> = chars.by_ref().take(2).collect();
+            let hex = std::str::from_utf8(&hex).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    String::from_utf8(bytes).ok().map(PathBuf::from)
+}
+
+// Declaração do símbolo como seria escrita no programa, mostrada no hover
+fn describe(symbol: &Symbol) -> String {
+    let name = symbol.as_token().unwrap_or_default();
+    let name = name.get_lexeme();
+    let symbol_type = symbol.get_type().unwrap_or_default();
+    let parameters = || {
+        let mut parameters: Vec<String> = symbol
+            .get_parameters()
+            .iter()
+            .map(|parameter| {
+                format!(
+                    "{}: {}",
+                    parameter.as_token().unwrap_or_default().get_lexeme(),
+                    parameter.type_name()
+                )
+            })
+            .collect();
+        if symbol.is_variadic() {
+            parameters.push("...".to_string());
+        }
+        if parameters.is_empty() {
+            String::new()
+        } else {
+            format!("({})", parameters.join("; "))
+        }
+    };
+    match symbol.get_kind() {
+        Some(SymbolKind::Program) => format!("program {name}"),
+        Some(SymbolKind::Variable) | None => format!("var {name}: {symbol_type}"),
+        Some(SymbolKind::Constant) => format!("const {name}: {symbol_type}"),
+        Some(SymbolKind::Type) => format!("type {name} = {symbol_type}"),
+        Some(SymbolKind::Procedure) => format!("procedure {name}{}", parameters()),
+        Some(SymbolKind::Function) => {
+            format!("function {name}{}: {}", parameters(), symbol.type_name())
+        }
+    }
+}
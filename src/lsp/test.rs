@@ -0,0 +1,212 @@
+#[cfg(test)]
+mod lsp_tests {
+    use crate::common::json::Json;
+    use crate::lsp::{read_message, Server};
+    use std::io::{Cursor, ErrorKind};
+
+    const URI: &str = "file:///tmp/teste.pas";
+
+    fn request(id: usize, method: &str, params: Json) -> Json {
+        Json::object([
+            ("jsonrpc", "2.0".into()),
+            ("id", id.into()),
+            ("method", method.into()),
+            ("params", params),
+        ])
+    }
+
+    fn open(server: &mut Server, text: &str) -> Json {
+        let message = Json::object([
+            ("jsonrpc", "2.0".into()),
+            ("method", "textDocument/didOpen".into()),
+            (
+                "params",
+                Json::object([(
+                    "textDocument",
+                    Json::object([("uri", URI.into()), ("text", text.into())]),
+                )]),
+            ),
+        ]);
+        server.handle(&message).remove(0)
+    }
+
+    fn at(line: usize, character: usize) -> Json {
+        Json::object([
+            ("textDocument", Json::object([("uri", URI.into())])),
+            (
+                "position",
+                Json::object([("line", line.into()), ("character", character.into())]),
+            ),
+            (
+                "context",
+                Json::object([("includeDeclaration", true.into())]),
+            ),
+        ])
+    }
+
+    fn start(location: &Json) -> (usize, usize) {
+        let start = location.get("range").get("start");
+        (
+            start.get("line").as_usize().unwrap(),
+            start.get("character").as_usize().unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_json() {
+        let input = r#"{"a": [1, -2.5e1, true, null], "b": "x\"\né😀"}"#;
+        let value = Json::parse(input).unwrap();
+        assert_eq!(value.get("a").as_array().unwrap()[1], Json::Number(-25.0));
+        assert_eq!(value.get("b").as_str(), Some("x\"\né😀"));
+        assert_eq!(value.get("c"), &Json::Null);
+        assert_eq!(Json::parse(&value.to_string()).unwrap(), value);
+        assert!(Json::parse("{\"a\": }").is_err());
+    }
+
+    #[test]
+    fn test_read_message() {
+        let mut input = Cursor::new("Content-Length: 2\r\n\r\n{}Content-Length: 2\r\n\r\n[]");
+        assert_eq!(read_message(&mut input).unwrap(), Some("{}".to_string()));
+        assert_eq!(read_message(&mut input).unwrap(), Some("[]".to_string()));
+        assert_eq!(read_message(&mut input).unwrap(), None);
+
+        // O tamanho informado pelo cliente não é reservado antes de ser verificado
+        let mut input = Cursor::new("Content-Length: 999999999999\r\n\r\n{}");
+        let error = read_message(&mut input).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert!(error.to_string().contains("exceeds the maximum"));
+        let mut input = Cursor::new("\r\n{}");
+        assert!(read_message(&mut input).is_err());
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let mut server = Server::default();
+        let published = open(
+            &mut server,
+            "program teste;\nvar x: integer;\nbegin\n  x := y\nend.",
+        );
+        assert_eq!(
+            published.get("method").as_str(),
+            Some("textDocument/publishDiagnostics")
+        );
+        let diagnostics = published.get("params").get("diagnostics");
+        let diagnostic = &diagnostics.as_array().unwrap()[0];
+        assert_eq!(start(diagnostic), (3, 7));
+        assert_eq!(
+            diagnostic
+                .get("range")
+                .get("end")
+                .get("character")
+                .as_usize(),
+            Some(8)
+        );
+        assert_eq!(diagnostic.get("severity").as_usize(), Some(1));
+        assert!(diagnostic
+            .get("message")
+            .as_str()
+            .unwrap()
+            .contains("undeclared identifier 'y'"));
+
+        let published = open(&mut server, "program teste;\nvar x: integer;\nbegin\nend.");
+        let diagnostics = published.get("params").get("diagnostics");
+        let diagnostic = &diagnostics.as_array().unwrap()[0];
+        assert_eq!(diagnostic.get("severity").as_usize(), Some(2));
+        assert_eq!(start(diagnostic), (1, 4));
+        // A posição vem do erro, mesmo quando a mensagem não cita a coluna
+        let published = open(&mut server, "program teste;\nbegin end. { aberto");
+        let diagnostics = published.get("params").get("diagnostics");
+        let diagnostic = &diagnostics.as_array().unwrap()[0];
+        assert_eq!(
+            diagnostic.get("message").as_str(),
+            Some("Unclosed comment at line 2")
+        );
+        assert_eq!(start(diagnostic), (1, 11));
+
+        // O aviso da unit usada não é publicado no documento que a usa
+        let units = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/units");
+        let message = Json::object([
+            ("jsonrpc", "2.0".into()),
+            ("method", "textDocument/didOpen".into()),
+            (
+                "params",
+                Json::object([(
+                    "textDocument",
+                    Json::object([
+                        ("uri", format!("file://{units}/principal.pas").into()),
+                        (
+                            "text",
+                            "program p;\nuses avisos;\nbegin\n  saudar\nend.".into(),
+                        ),
+                    ]),
+                )]),
+            ),
+        ]);
+        let published = server.handle(&message).remove(0);
+        let diagnostics = published.get("params").get("diagnostics");
+        assert_eq!(diagnostics.as_array().map(|items| items.len()), Some(0));
+    }
+
+    #[test]
+    fn test_navigation() {
+        let mut server = Server::default();
+        let text = "program teste;
+var total: integer;
+procedure soma(valor: integer);
+begin
+  total := total + valor
+end;
+begin
+  total := 0;
+  soma(total)
+end.";
+        open(&mut server, text);
+
+        let response = server.handle(&request(1, "textDocument/definition", at(8, 8)));
+        assert_eq!(start(response[0].get("result")), (1, 4));
+
+        let response = server.handle(&request(2, "textDocument/hover", at(8, 3)));
+        let hover = response[0].get("result").get("contents").get("value");
+        assert_eq!(
+            hover.as_str(),
+            Some("```pascal\nprocedure soma(valor: integer)\n```")
+        );
+
+        let response = server.handle(&request(3, "textDocument/references", at(1, 6)));
+        let references: Vec<_> = response[0]
+            .get("result")
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(start)
+            .collect();
+        assert_eq!(references, [(1, 4), (4, 2), (4, 11), (7, 2), (8, 7)]);
+
+        let response = server.handle(&request(4, "textDocument/documentSymbol", at(0, 0)));
+        let symbols: Vec<_> = response[0]
+            .get("result")
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|symbol| {
+                (
+                    symbol.get("name").as_str().unwrap().to_string(),
+                    symbol.get("kind").as_usize().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            symbols,
+            [
+                ("total".to_string(), 13),
+                ("soma".to_string(), 12),
+                ("valor".to_string(), 13)
+            ]
+        );
+
+        let response = server.handle(&request(5, "textDocument/definition", at(8, 3)));
+        assert_eq!(start(response[0].get("result")), (2, 10));
+        let response = server.handle(&request(6, "unknown", Json::Null));
+        assert!(response[0].get("error") != &Json::Null);
+    }
+}
@@ -0,0 +1,173 @@
+mod test;
+
+use crate::common::token::Token;
+use crate::interpreter::Interpreter;
+use crate::lexical::Scanner;
+use crate::syntactic::interactive::Entry;
+use crate::syntactic::Parser;
+use std::io;
+use std::io::{BufRead, Write};
+
+const HELP: &str = "Declare constants, types, variables and procedures, or type commands and \
+expressions to run them.
+  :type <expression>   show the type of the expression
+  :tokens <text>       show the tokens of the text
+  :help                show this message
+  :quit                leave the session";
+
+// Sessão interativa: a tabela de símbolos do analisador e as variáveis do interpretador
+// permanecem entre as entradas
+pub struct Session {
+    parser: Parser,
+    interpreter: Interpreter,
+    pending: String, // Linhas de uma entrada que ainda não terminou, como um bloco begin aberto
+    warnings: usize, // Avisos do analisador já mostrados
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self {
+            parser: Parser::interactive(),
+            interpreter: Interpreter::default(),
+            pending: String::new(),
+            warnings: 0,
+        }
+    }
+}
+
+impl Session {
+    pub fn prompt(&self) -> &str {
+        if self.pending.is_empty() {
+            "> "
+        } else {
+            "| "
+        }
+    }
+
+    // Recebe uma linha e retorna o texto a mostrar, ou None enquanto a entrada continua
+    pub fn input(&mut self, line: &str) -> Option<String> {
+        if self.pending.is_empty() {
+            if let Some(command) = line.trim().strip_prefix(':') {
+                return Some(self.command(command));
+            }
+        }
+        self.pending.push_str(line);
+        self.pending.push('\n');
+        let text = std::mem::take(&mut self.pending);
+        let mut scanner = Scanner::new(&text);
+        let tokens = match scanner.init() {
+            Ok(tokens) => tokens,
+            Err(_) if text.trim().is_empty() => return Some(String::new()),
+            Err(e) => return Some(e.to_string()),
+        };
+        if Self::is_incomplete(&tokens) {
+            self.pending = text;
+            return None;
+        }
+
+        let result = self.parser.feed(&tokens);
+        let mut output: Vec<String> = self.parser.get_warnings()[self.warnings..]
+            .iter()
+            .map(|warning| format!("Warning: {warning}"))
+            .collect();
+        self.warnings = self.parser.get_warnings().len();
+
+        let shown = match result {
+            Err(e) => Err(e.to_string()),
+            Ok(Entry::Declarations(declarations)) => {
+                self.interpreter.declare(&declarations);
+                Ok(None)
+            }
+            Ok(Entry::Statements(statements)) => {
+                self.interpreter.execute(&statements).map(|_| None)
+            }
+            Ok(Entry::Expression(expression)) => self
+                .interpreter
+                .evaluate(&expression)
+                .map(|value| Some(self.interpreter.show(&value, &expression.value_type))),
+        };
+        match shown {
+            Ok(Some(value)) => output.push(value),
+            Ok(None) => {}
+            Err(e) => output.push(e),
+        }
+        Some(output.join("\n"))
+    }
+
+    fn command(&mut self, command: &str) -> String {
+        let (name, argument) = command.split_once(' ').unwrap_or((command, ""));
+        match name {
+            "type" => match Scanner::new(argument).init() {
+                Err(e) => e.to_string(),
+                Ok(tokens) => self
+                    .parser
+                    .type_of(&tokens)
+                    .map_or_else(|e| e.to_string(), |found| found.to_string()),
+            },
+            "tokens" => match Scanner::new(argument).init() {
+                Err(e) => e.to_string(),
+                Ok(tokens) => tokens
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            },
+            "help" => HELP.to_string(),
+            _ => format!("Unknown command ':{name}', type :help to see the commands."),
+        }
+    }
+
+    // A entrada continua enquanto houver um bloco aberto, um procedimento ainda sem corpo ou um
+    // último token que exige continuação, como em 'while x > 0 do'
+    fn is_incomplete(tokens: &[Token]) -> bool {
+        let mut open = vec![]; // Blocos abertos, marcando os que começam com begin
+        let mut headers = 0;
+        let mut bodies = 0;
+        for token in tokens {
+            let lexeme = token.get_lexeme().to_ascii_lowercase();
+            match lexeme.as_str() {
+                "begin" | "case" | "record" | "repeat" => open.push(lexeme == "begin"),
+                "end" | "until" => {
+                    let body = open.pop() == Some(true) && open.is_empty();
+                    bodies += i32::from(body);
+                }
+                "procedure" => headers += 1,
+                "forward" => headers -= 1,
+                _ => {}
+            }
+        }
+        let declaration = tokens
+            .first()
+            .is_some_and(|token| token.is_lexeme("procedure"));
+        let continued = tokens.last().is_some_and(|token| {
+            ["do", "then", "else", "of", ":=", ","]
+                .iter()
+                .any(|lexeme| token.is_lexeme(lexeme))
+        });
+        !open.is_empty() || (declaration && bodies < headers) || continued
+    }
+}
+
+// Lê as entradas da entrada padrão até o fim ou até :quit
+pub fn run() -> i32 {
+    let mut session = Session::default();
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    println!("Type :help to see the commands.");
+    loop {
+        print!("{}", session.prompt());
+        let _ = io::stdout().flush();
+        let Some(Ok(line)) = lines.next() else {
+            println!();
+            return 0;
+        };
+        if session.prompt() == "> " && line.trim() == ":quit" {
+            return 0;
+        }
+        if let Some(output) = session.input(&line) {
+            if !output.is_empty() {
+                println!("{output}");
+            }
+        }
+    }
+}
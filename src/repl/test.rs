@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod repl_tests {
+    use crate::repl::Session;
+
+    #[test]
+    fn test_session_keeps_state() {
+        let mut session = Session::default();
+        assert_eq!(session.input("var x: integer"), Some(String::new()));
+        assert_eq!(session.input("x := 40 + 2"), Some(String::new()));
+        assert_eq!(session.input("x"), Some("42".to_string()));
+        assert_eq!(session.input(":type x * 1.0"), Some("real".to_string()));
+    }
+
+    #[test]
+    fn test_multiline_input() {
+        let mut session = Session::default();
+        session.input("var x: integer;");
+        assert_eq!(session.input("procedure dobrar;"), None);
+        assert_eq!(session.prompt(), "| ");
+        assert_eq!(session.input("begin"), None);
+        assert_eq!(session.input("  x := x * 2"), None);
+        assert_eq!(session.input("end;"), Some(String::new()));
+        assert_eq!(session.prompt(), "> ");
+
+        session.input("x := 3");
+        assert_eq!(session.input("while x < 20 do"), None);
+        assert_eq!(session.input("dobrar"), Some(String::new()));
+        assert_eq!(session.input("x"), Some("24".to_string()));
+    }
+
+    #[test]
+    fn test_errors_are_rolled_back() {
+        let mut session = Session::default();
+        let error = session.input("var x: integer; y: inexistente").unwrap();
+        assert!(error.contains("inexistente"));
+        let error = session.input("x").unwrap();
+        assert_eq!(
+            error,
+            "Use of the undeclared identifier 'x' at line 1 column 1."
+        );
+        assert_eq!(session.input("var x: boolean"), Some(String::new()));
+        assert_eq!(
+            session.input("x := not x"),
+            Some(
+                "Warning: Variable 'x' is read at line 1 column 10 before being assigned."
+                    .to_string()
+            )
+        );
+        assert_eq!(session.input("x"), Some("true".to_string()));
+    }
+
+    #[test]
+    fn test_commands() {
+        let mut session = Session::default();
+        let tokens = session.input(":tokens x := 1").unwrap();
+        assert_eq!(tokens.lines().count(), 3);
+        assert!(session.input(":help").unwrap().contains(":type"));
+        assert_eq!(
+            session.input(":nada"),
+            Some("Unknown command ':nada', type :help to see the commands.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_warnings_are_shown_once() {
+        let mut session = Session::default();
+        session.input("var x, y: integer;");
+        let output = session.input("x := y").unwrap();
+        assert!(output.starts_with("Warning: "));
+        assert_eq!(session.input("x"), Some("0".to_string()));
+    }
+}
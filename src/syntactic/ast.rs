@@ -0,0 +1,157 @@
+use crate::common::diagnostic::Diagnostic;
+use crate::common::token::Token;
+use crate::common::types::Type;
+use crate::syntactic::constant::Value;
+use crate::syntactic::Parser;
+use std::rc::Rc;
+
+// Árvore sintática abstrata, montada durante a análise. Os nomes são guardados em minúsculo e cada
+// expressão leva o tipo calculado pela verificação de tipos
+
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct Program {
+    pub name: String,
+    pub units: Vec<Program>, // Units usadas, na ordem da cláusula uses
+    pub declarations: Vec<Declaration>,
+    pub body: Vec<Statement>,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum Declaration {
+    Constant(String, Value),
+    Type(String, Type),               // Nome e definição
+    Enumeration(String, Vec<String>), // Nome do tipo e as suas constantes, na ordem
+    Variable(String, Type),
+    Procedure(Rc<Procedure>),
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Procedure {
+    pub name: String,
+    pub parameters: Vec<(String, Type)>,
+    pub declarations: Vec<Declaration>,
+    pub body: Vec<Statement>,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum Statement {
+    Assignment(Expression, Expression),
+    Call(String, Vec<Expression>, usize, usize), // Nome, argumentos e a posição da chamada
+    If(Expression, Box<Statement>, Option<Box<Statement>>),
+    While(Expression, Box<Statement>),
+    Repeat(Vec<Statement>, Expression),
+    For {
+        variable: Expression,
+        start: Expression,
+        end: Expression,
+        downto: bool,
+        body: Box<Statement>,
+    },
+    Case {
+        selector: Expression,
+        elements: Vec<(Vec<(i64, i64)>, Statement)>, // Intervalos ordinais dos rótulos de cada elemento
+        otherwise: Option<Vec<Statement>>,
+    },
+    Block(Vec<Statement>),
+    Break,
+    Continue,
+    Exit,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Expression {
+    pub kind: ExpressionKind,
+    pub value_type: Type,
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum ExpressionKind {
+    Literal(Value), // Literais e constantes declaradas, já substituídas pelo valor
+    Nil,
+    Variable(String),
+    Index(Box<Expression>, Box<Expression>),
+    Field(Box<Expression>, String),
+    Dereference(Box<Expression>),
+    Call(String, Vec<Expression>),
+    Unary(String, Box<Expression>),
+    Binary(String, Box<Expression>, Box<Expression>),
+    Set(Vec<(Expression, Option<Expression>)>), // Elementos e intervalos, como em [1, 3..5]
+    Conversion(Box<Expression>), // Conversão implícita para o tipo do nó, como de integer para real
+}
+
+// Montagem da árvore. As expressões são empilhadas como os seus tipos na pilha de controle, e os
+// comandos e declarações são agrupados pelo bloco ou procedimento que os contém
+impl Parser {
+    pub(super) fn push_expression(
+        &mut self,
+        kind: ExpressionKind,
+        value_type: &Type,
+        token: &Token,
+    ) {
+        self.expressions.push(Expression {
+            kind,
+            value_type: value_type.to_owned(),
+            line: token.get_line(),
+            column: token.get_column(),
+        });
+    }
+
+    pub(super) fn pop_expression(&mut self) -> Expression {
+        self.expressions
+            .pop()
+            .expect("Something went wrong while building the expression tree.")
+    }
+
+    pub(super) fn pop_statement(&mut self) -> Statement {
+        self.statements
+            .pop()
+            .expect("Something went wrong while building the statement tree.")
+    }
+
+    // Corpo do último comando composto analisado
+    pub(super) fn pop_block(&mut self) -> Vec<Statement> {
+        match self.statements.pop() {
+            Some(Statement::Block(body)) => body,
+            _ => vec![],
+        }
+    }
+
+    // Substitui a expressão do topo por uma que a contém, como um acesso a campo ou um 'not'
+    pub(super) fn wrap_expression(
+        &mut self,
+        build: impl FnOnce(Box<Expression>) -> ExpressionKind,
+        value_type: &Type,
+        token: &Token,
+    ) {
+        let inner = self.pop_expression();
+        self.push_expression(build(Box::new(inner)), value_type, token);
+    }
+
+    // Combina as duas expressões do topo em uma operação binária com o tipo já verificado
+    pub(super) fn combine(&mut self, operator: &Token) {
+        let right = self.pop_expression();
+        let left = self.pop_expression();
+        let value_type = self.control_type_stack.last().cloned().unwrap_or_default();
+        let kind = ExpressionKind::Binary(
+            operator.get_lexeme().to_ascii_lowercase(),
+            Box::new(left),
+            Box::new(right),
+        );
+        self.push_expression(kind, &value_type, operator);
+    }
+
+    // Variáveis declaradas em list_of_identifiers, que ainda esperam o tipo
+    pub(super) fn declare_variables(&mut self, symbol_type: &Type) -> Result<(), Diagnostic> {
+        let start = self.symbol_table.len().saturating_sub(self.amount);
+        for symbol in &self.symbol_table[start..] {
+            if let Some(token) = symbol.as_token() {
+                let name = token.get_lexeme().to_ascii_lowercase();
+                self.items
+                    .push(Declaration::Variable(name, symbol_type.to_owned()));
+            }
+        }
+        self.update_symbols_type(symbol_type)
+    }
+}
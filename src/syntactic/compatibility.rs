@@ -0,0 +1,247 @@
+use crate::common::builtins::Generic;
+use crate::common::symbol::Symbol;
+use crate::common::types::Type;
+use crate::syntactic::ast::{Expression, ExpressionKind};
+use crate::syntactic::Parser;
+use std::fmt;
+use std::fmt::Formatter;
+use std::mem;
+
+// Operando que recebe a conversão implícita
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Operand {
+    Left,
+    Right,
+    Argument(usize),
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Promotion {
+    pub operand: Operand,
+    pub from: Type,
+    pub to: Type,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum Compatibility {
+    Identical,
+    Promoted(Promotion),
+    Incompatible,
+}
+
+// Conversão registrada durante a análise, que também aparece na árvore como um nó Conversion
+#[derive(PartialEq, Debug, Clone)]
+pub struct Conversion {
+    pub promotion: Promotion,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Operand::Left => write!(f, "left operand"),
+            Operand::Right => write!(f, "right operand"),
+            Operand::Argument(index) => write!(f, "argument {index}"),
+        }
+    }
+}
+
+impl fmt::Display for Conversion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Conversion of the {} from '{}' to '{}' at line {} column {}",
+            self.promotion.operand, self.promotion.from, self.promotion.to, self.line, self.column
+        )
+    }
+}
+
+impl Compatibility {
+    fn promote(operand: Operand, from: &Type, to: &Type) -> Self {
+        Compatibility::Promoted(Promotion {
+            operand,
+            from: from.to_owned(),
+            to: to.to_owned(),
+        })
+    }
+
+    pub fn is_compatible(&self) -> bool {
+        *self != Compatibility::Incompatible
+    }
+}
+
+impl Parser {
+    // Compatibilidade de atribuição: o valor é convertido para o tipo do destino
+    pub(super) fn assignment_compatibility(&self, target: &Type, value: &Type) -> Compatibility {
+        let target = target.host();
+        let value = value.host();
+        if let Type::Set(_) = target {
+            return match self.set_operation_type(target, value) {
+                Some(_) => Compatibility::Identical,
+                None => Compatibility::Incompatible,
+            };
+        }
+        if let Type::Pointer(_) = target {
+            return match self.pointer_operation_type(target, value) {
+                Some(_) => Compatibility::Identical,
+                None => Compatibility::Incompatible,
+            };
+        }
+
+        if target == value {
+            Compatibility::Identical
+        } else if (*target == Type::Real && *value == Type::Integer)
+            || (*target == Type::String && *value == Type::Char)
+        {
+            Compatibility::promote(Operand::Right, value, target)
+        } else {
+            Compatibility::Incompatible
+        }
+    }
+
+    // Compatibilidade de comparação: o operando de tipo menor é convertido para o do outro
+    pub(super) fn comparison_compatibility(&self, left: &Type, right: &Type) -> Compatibility {
+        let left = left.host();
+        let right = right.host();
+        if self.set_operation_type(left, right).is_some()
+            || self.pointer_operation_type(left, right).is_some()
+        {
+            return Compatibility::Identical;
+        }
+
+        let numeric = self.numeric_compatibility(left, right);
+        if numeric.is_compatible() {
+            return numeric;
+        }
+
+        if left.is_textual() && right.is_textual() {
+            return if left == right {
+                Compatibility::Identical
+            } else if *left == Type::Char {
+                Compatibility::promote(Operand::Left, left, right)
+            } else {
+                Compatibility::promote(Operand::Right, right, left)
+            };
+        }
+
+        // Ordinais do mesmo tipo, como booleanos e constantes de uma mesma enumeração
+        if left == right && left.is_ordinal() {
+            return Compatibility::Identical;
+        }
+        Compatibility::Incompatible
+    }
+
+    // Operações entre inteiros e reais convertem o inteiro para real
+    pub(super) fn numeric_compatibility(&self, left: &Type, right: &Type) -> Compatibility {
+        let numeric = |value: &Type| matches!(value, Type::Integer | Type::Real);
+        if !numeric(left) || !numeric(right) {
+            Compatibility::Incompatible
+        } else if left == right {
+            Compatibility::Identical
+        } else if *left == Type::Integer {
+            Compatibility::promote(Operand::Left, left, right)
+        } else {
+            Compatibility::promote(Operand::Right, right, left)
+        }
+    }
+
+    // Compatibilidade de um argumento com o parâmetro, incluindo as famílias de tipos 'ordinal',
+    // 'pointer' e 'printable' usadas pelas rotinas pré-definidas
+    pub(super) fn parameter_compatibility(
+        &self,
+        parameter: &Symbol,
+        argument: &Type,
+        index: usize,
+    ) -> Compatibility {
+        let accepted = match parameter.get_generic() {
+            Some(Generic::Ordinal) => argument.is_ordinal(),
+            Some(Generic::Pointer) => matches!(argument, Type::Pointer(_)),
+            Some(Generic::Printable) => {
+                let host = argument.host();
+                host.is_ordinal() || *host == Type::Real || host.is_textual()
+            }
+            None => {
+                let parameter = parameter.get_type().unwrap_or_default();
+                return match self.assignment_compatibility(&parameter, argument) {
+                    Compatibility::Promoted(promotion) => Compatibility::Promoted(Promotion {
+                        operand: Operand::Argument(index + 1),
+                        ..promotion
+                    }),
+                    other => other,
+                };
+            }
+        };
+        if accepted {
+            Compatibility::Identical
+        } else {
+            Compatibility::Incompatible
+        }
+    }
+
+    // Os operandos são as últimas expressões montadas: o esquerdo e o direito de uma operação ou
+    // atribuição, ou os argumentos de uma chamada. O convertido fica dentro de um nó Conversion
+    pub(super) fn record_conversion(
+        &mut self,
+        compatibility: Compatibility,
+        operands: usize,
+        line: usize,
+        column: usize,
+    ) {
+        if let Compatibility::Promoted(promotion) = compatibility {
+            let position = match promotion.operand {
+                Operand::Left => 0,
+                Operand::Right => operands.saturating_sub(1),
+                Operand::Argument(index) => index - 1,
+            };
+            let index = (self.expressions.len() + position).checked_sub(operands);
+            if let Some(expression) = index.and_then(|index| self.expressions.get_mut(index)) {
+                let (line, column) = (expression.line, expression.column);
+                let placeholder = Expression {
+                    kind: ExpressionKind::Nil,
+                    value_type: Type::Unknown,
+                    line,
+                    column,
+                };
+                let operand = mem::replace(expression, placeholder);
+                *expression = Expression {
+                    kind: ExpressionKind::Conversion(Box::new(operand)),
+                    value_type: promotion.to.to_owned(),
+                    line,
+                    column,
+                };
+            }
+            self.conversions.push(Conversion {
+                promotion,
+                line,
+                column,
+            });
+        }
+    }
+
+    pub(super) fn is_assignable(&self, target: &Type, value: &Type) -> bool {
+        self.assignment_compatibility(target, value).is_compatible()
+    }
+
+    // Tipo resultante de uma operação entre conjuntos, o conjunto vazio '[]' é compatível com todos
+    pub(super) fn set_operation_type(&self, left: &Type, right: &Type) -> Option<Type> {
+        match (left, right) {
+            (Type::Set(a), Type::Set(b)) if a.host() == b.host() => Some(left.to_owned()),
+            (Type::Set(_), Type::EmptySet) => Some(left.to_owned()),
+            (Type::EmptySet, Type::Set(_)) => Some(right.to_owned()),
+            (Type::EmptySet, Type::EmptySet) => Some(left.to_owned()),
+            _ => None,
+        }
+    }
+
+    // 'nil' é compatível com qualquer ponteiro
+    pub(super) fn pointer_operation_type(&self, left: &Type, right: &Type) -> Option<Type> {
+        match (left, right) {
+            (Type::Pointer(_), Type::Pointer(_)) if left == right => Some(left.to_owned()),
+            (Type::Pointer(_), Type::Nil) => Some(left.to_owned()),
+            (Type::Nil, Type::Pointer(_)) => Some(right.to_owned()),
+            (Type::Nil, Type::Nil) => Some(left.to_owned()),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,430 @@
+use crate::common::diagnostic::Diagnostic;
+use crate::common::symbol::SymbolKind;
+use crate::common::token::Token;
+use crate::common::token::TokenType::*;
+use crate::common::types::Type;
+use crate::common::utils::{parse_integer, unquote, MAX_INTEGER};
+use crate::syntactic::Parser;
+use std::cmp::Ordering;
+
+// Valor de uma expressão conhecido em tempo de compilação
+#[derive(PartialEq, Debug, Clone)]
+pub enum Value {
+    Integer(i64),
+    Real(f64),
+    Boolean(bool),
+    Char(char),
+    Text(String),
+    Enumerated(String, Type, i64), // Nome da constante, tipo enumerado e posição
+}
+
+impl Value {
+    pub fn get_type(&self) -> Type {
+        match self {
+            Value::Integer(_) => Type::Integer,
+            Value::Real(_) => Type::Real,
+            Value::Boolean(_) => Type::Boolean,
+            Value::Char(_) => Type::Char,
+            Value::Text(_) => Type::String,
+            Value::Enumerated(_, enumeration, _) => enumeration.to_owned(),
+        }
+    }
+
+    pub fn ordinal(&self) -> Option<i64> {
+        match self {
+            Value::Integer(value) | Value::Enumerated(_, _, value) => Some(*value),
+            Value::Boolean(value) => Some(*value as i64),
+            Value::Char(value) => Some(*value as i64),
+            Value::Real(_) | Value::Text(_) => None,
+        }
+    }
+
+    // Texto guardado no campo 'value' da tabela de símbolos, ordinais guardam a posição
+    pub fn stored(&self) -> String {
+        match (self.ordinal(), self) {
+            (Some(ordinal), _) => ordinal.to_string(),
+            (None, Value::Real(value)) => value.to_string(),
+            (None, Value::Text(value)) => value.to_owned(),
+            (None, _) => String::new(),
+        }
+    }
+
+    // Token equivalente ao valor, usado nos diagnósticos e nos nomes de subintervalos
+    pub fn as_token(&self, line: usize, column: usize) -> Token {
+        let quote = |text: String| format!("'{}'", text.replace('\'', "''"));
+        match self {
+            Value::Integer(value) => Token::new(&value.to_string(), Integer, line, column),
+            Value::Real(value) => Token::new(&format!("{value:?}"), Real, line, column),
+            Value::Boolean(value) => Token::new(&value.to_string(), Boolean, line, column),
+            Value::Char(value) => Token::new(&quote(value.to_string()), Char, line, column),
+            Value::Text(value) => Token::new(&quote(value.to_owned()), Text, line, column),
+            Value::Enumerated(name, _, _) => Token::new(name, Identifier, line, column),
+        }
+    }
+}
+
+// Avaliador de expressões constantes. Percorre os tokens à frente sem consumi-los, seguindo a
+// mesma gramática de expression, e devolve None para as partes que dependem de variáveis
+struct Evaluator<'p> {
+    parser: &'p Parser,
+    position: usize,
+}
+
+impl Parser {
+    // Avalia a expressão à frente, retornando o seu valor, quando constante, e quantos tokens ela ocupa.
+    // Divisões por zero e estouros de integer e real entre constantes são erros
+    pub(super) fn evaluate_ahead(&self) -> Result<(Option<Value>, usize), Diagnostic> {
+        let mut evaluator = Evaluator {
+            parser: self,
+            position: 0,
+        };
+        let value = evaluator.expression()?;
+        Ok((value, evaluator.position))
+    }
+
+    pub(super) fn declared_value(&self, token: &Token) -> Option<Value> {
+        let symbol = self.lookup(token.get_lexeme())?;
+        if symbol.get_kind() != Some(SymbolKind::Constant) {
+            return None;
+        }
+        let value = symbol.get_value()?;
+        let value = match symbol.get_type()? {
+            Type::Real => Value::Real(value.parse().ok()?),
+            Type::String => Value::Text(value),
+            Type::Integer => Value::Integer(value.parse().ok()?),
+            Type::Boolean => Value::Boolean(value == "1"),
+            Type::Char => Value::Char(char::from_u32(value.parse().ok()?)?),
+            enumeration @ Type::Enumeration(..) => Value::Enumerated(
+                symbol.as_token()?.get_lexeme().to_ascii_lowercase(),
+                enumeration,
+                value.parse().ok()?,
+            ),
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+impl Evaluator<'_> {
+    fn peek(&self) -> Option<&Token> {
+        let buffer = &self.parser.tokens_buffer;
+        let index = buffer.len().checked_sub(self.position + 1)?;
+        buffer.get(index)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.peek()?.to_owned();
+        self.position += 1;
+        Some(token)
+    }
+
+    fn next_is(&self, lexeme: &str) -> bool {
+        self.peek().is_some_and(|token| token.is_lexeme(lexeme))
+    }
+
+    fn expression(&mut self) -> Result<Option<Value>, Diagnostic> {
+        let left = self.simple_expression()?;
+        if !self
+            .peek()
+            .is_some_and(|token| token.is_type_of(RelationalOperators))
+        {
+            return Ok(left);
+        }
+        let operator = self.advance().unwrap();
+        let right = self.simple_expression()?;
+        Ok(match (left, right) {
+            (Some(left), Some(right)) => Self::compare(&operator, &left, &right),
+            _ => None,
+        })
+    }
+
+    fn simple_expression(&mut self) -> Result<Option<Value>, Diagnostic> {
+        let signal = if self.next_is("+") || self.next_is("-") {
+            self.advance()
+        } else {
+            None
+        };
+        let mut value = self.term()?;
+        if let Some(signal) = signal.filter(|signal| signal.is_lexeme("-")) {
+            value = match value {
+                Some(Value::Integer(value)) => Some(Self::integer(-value, &signal)?),
+                Some(Value::Real(value)) => Some(Value::Real(-value)),
+                _ => None,
+            };
+        }
+
+        while self
+            .peek()
+            .is_some_and(|token| token.is_type_of(AdditiveOperators))
+        {
+            let operator = self.advance().unwrap();
+            let right = self.term()?;
+            value = Self::binary(&operator, value, right)?;
+        }
+        Ok(value)
+    }
+
+    fn term(&mut self) -> Result<Option<Value>, Diagnostic> {
+        let mut value = self.factor()?;
+        while self
+            .peek()
+            .is_some_and(|token| token.is_type_of(MultiplicativeOperators))
+        {
+            let operator = self.advance().unwrap();
+            let right = self.factor()?;
+            value = Self::binary(&operator, value, right)?;
+        }
+        Ok(value)
+    }
+
+    fn factor(&mut self) -> Result<Option<Value>, Diagnostic> {
+        let Some(token) = self.peek().cloned() else {
+            return Ok(None);
+        };
+        let value = match token.get_type() {
+            Integer => parse_integer(token.get_lexeme()).map(Value::Integer),
+            Real => match token.get_lexeme().parse() {
+                Ok(value) => Some(Self::real_value(value, &token)?),
+                Err(_) => None,
+            },
+            Boolean => Some(Value::Boolean(token.is_lexeme("true"))),
+            Char => unquote(token.get_lexeme()).chars().next().map(Value::Char),
+            Text => Some(Value::Text(unquote(token.get_lexeme()))),
+            Identifier => {
+                self.advance();
+                if self.next_is("(") && self.is_function(&token, "chr") {
+                    return self.chr(&token);
+                }
+                if self.skip_selectors() {
+                    return Ok(None);
+                }
+                return Ok(self.parser.declared_value(&token));
+            }
+            _ if token.is_lexeme("(") => {
+                self.advance();
+                let value = self.expression()?;
+                if self.next_is(")") {
+                    self.advance();
+                }
+                return Ok(value);
+            }
+            _ if token.is_lexeme("not") => {
+                self.advance();
+                return Ok(match self.factor()? {
+                    Some(Value::Boolean(value)) => Some(Value::Boolean(!value)),
+                    _ => None,
+                });
+            }
+            _ if token.is_lexeme("[") => {
+                self.skip_balanced();
+                return Ok(None);
+            }
+            _ if token.is_lexeme("nil") => None,
+            _ => return Ok(None),
+        };
+        self.advance();
+        Ok(value)
+    }
+
+    fn is_function(&self, token: &Token, name: &str) -> bool {
+        token.is_lexeme(name)
+            && self
+                .parser
+                .lookup(name)
+                .is_some_and(|symbol| symbol.get_kind() == Some(SymbolKind::Function))
+    }
+
+    // chr de um argumento constante, que precisa ser o ordinal de um char
+    fn chr(&mut self, function: &Token) -> Result<Option<Value>, Diagnostic> {
+        let start = self.position;
+        self.advance();
+        let argument = self.expression()?;
+        if !self.next_is(")") {
+            // Mais de um argumento, o erro é mostrado pela verificação da chamada
+            self.position = start;
+            self.skip_balanced();
+            return Ok(None);
+        }
+        self.advance();
+        let Some(Value::Integer(ordinal)) = argument else {
+            return Ok(None);
+        };
+        let (low, high) = Type::Char.range().unwrap_or_default();
+        match u8::try_from(ordinal) {
+            Ok(value) if (low..=high).contains(&ordinal) => {
+                Ok(Some(Value::Char(char::from(value))))
+            }
+            _ => Err(Diagnostic::at(
+                function,
+                format!(
+                    "Invalid argument {} for 'chr' at line {} column {}.",
+                    ordinal,
+                    function.get_line(),
+                    function.get_column()
+                ),
+            )),
+        }
+    }
+
+    // Pula argumentos e seletores depois de um identificador, retornando se havia algum
+    fn skip_selectors(&mut self) -> bool {
+        let mut found = false;
+        loop {
+            if self.next_is("(") || self.next_is("[") {
+                self.skip_balanced();
+            } else if self.next_is("^") {
+                self.advance();
+            } else if self.next_is(".")
+                && self
+                    .parser
+                    .tokens_buffer
+                    .len()
+                    .checked_sub(self.position + 2)
+                    .is_some_and(|index| self.parser.tokens_buffer[index].is_type_of(Identifier))
+            {
+                self.position += 2;
+            } else {
+                return found;
+            }
+            found = true;
+        }
+    }
+
+    fn skip_balanced(&mut self) {
+        let mut depth = 0;
+        while let Some(token) = self.advance() {
+            if token.is_lexeme("(") || token.is_lexeme("[") {
+                depth += 1;
+            } else if token.is_lexeme(")") || token.is_lexeme("]") {
+                depth -= 1;
+            }
+            if depth == 0 {
+                break;
+            }
+        }
+    }
+
+    fn integer(value: i64, operator: &Token) -> Result<Value, Diagnostic> {
+        if !(-MAX_INTEGER - 1..=MAX_INTEGER).contains(&value) {
+            return Err(Diagnostic::at(
+                operator,
+                format!(
+                    "Integer overflow in the constant expression at line {} column {}.",
+                    operator.get_line(),
+                    operator.get_column()
+                ),
+            ));
+        }
+        Ok(Value::Integer(value))
+    }
+
+    // Reais que não cabem em um f64 viram infinito, o que é um erro entre constantes
+    fn real_value(value: f64, operator: &Token) -> Result<Value, Diagnostic> {
+        if !value.is_finite() {
+            return Err(Diagnostic::at(
+                operator,
+                format!(
+                    "Real overflow in the constant expression at line {} column {}.",
+                    operator.get_line(),
+                    operator.get_column()
+                ),
+            ));
+        }
+        Ok(Value::Real(value))
+    }
+
+    fn binary(
+        operator: &Token,
+        left: Option<Value>,
+        right: Option<Value>,
+    ) -> Result<Option<Value>, Diagnostic> {
+        // A divisão por um zero constante é um erro mesmo quando o dividendo não é constante
+        let zero = matches!(right, Some(Value::Integer(0))) || right == Some(Value::Real(0.0));
+        if operator.is_lexeme("/") && zero {
+            return Err(Diagnostic::at(
+                operator,
+                format!(
+                    "Division by zero at line {} column {}.",
+                    operator.get_line(),
+                    operator.get_column()
+                ),
+            ));
+        }
+        let (Some(left), Some(right)) = (left, right) else {
+            return Ok(None);
+        };
+
+        let lexeme = operator.get_lexeme().to_ascii_lowercase();
+        let value = match (left, right) {
+            (Value::Integer(a), Value::Integer(b)) => match lexeme.as_str() {
+                "+" => Self::integer(a + b, operator)?,
+                "-" => Self::integer(a - b, operator)?,
+                "*" => Self::integer(a * b, operator)?,
+                // A verificação de tipos considera integer o resultado da divisão entre inteiros
+                "/" => Self::integer(a / b, operator)?,
+                _ => return Ok(None),
+            },
+            (Value::Boolean(a), Value::Boolean(b)) => match lexeme.as_str() {
+                "and" => Value::Boolean(a && b),
+                "or" => Value::Boolean(a || b),
+                _ => return Ok(None),
+            },
+            (a @ (Value::Char(_) | Value::Text(_)), b @ (Value::Char(_) | Value::Text(_)))
+                if lexeme == "+" =>
+            {
+                Value::Text(Self::text(&a).unwrap() + &Self::text(&b).unwrap())
+            }
+            (a, b) => {
+                let (Some(a), Some(b)) = (Self::real(&a), Self::real(&b)) else {
+                    return Ok(None);
+                };
+                match lexeme.as_str() {
+                    "+" => Self::real_value(a + b, operator)?,
+                    "-" => Self::real_value(a - b, operator)?,
+                    "*" => Self::real_value(a * b, operator)?,
+                    "/" => Self::real_value(a / b, operator)?,
+                    _ => return Ok(None),
+                }
+            }
+        };
+        Ok(Some(value))
+    }
+
+    fn compare(operator: &Token, left: &Value, right: &Value) -> Option<Value> {
+        let ordering = match (left, right) {
+            (Value::Enumerated(_, a, x), Value::Enumerated(_, b, y)) if a == b => x.cmp(y),
+            (Value::Boolean(a), Value::Boolean(b)) => a.cmp(b),
+            (Value::Char(a), Value::Char(b)) => a.cmp(b),
+            _ => match (Self::text(left), Self::text(right)) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                _ => Self::real(left)?.partial_cmp(&Self::real(right)?)?,
+            },
+        };
+        let result = match operator.get_lexeme() {
+            "=" => ordering == Ordering::Equal,
+            "<>" => ordering != Ordering::Equal,
+            "<" => ordering == Ordering::Less,
+            ">" => ordering == Ordering::Greater,
+            "<=" => ordering != Ordering::Greater,
+            ">=" => ordering != Ordering::Less,
+            _ => return None,
+        };
+        Some(Value::Boolean(result))
+    }
+
+    fn real(value: &Value) -> Option<f64> {
+        match value {
+            Value::Integer(value) => Some(*value as f64),
+            Value::Real(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    fn text(value: &Value) -> Option<String> {
+        match value {
+            Value::Char(value) => Some(value.to_string()),
+            Value::Text(value) => Some(value.to_owned()),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use crate::common::token::Token;
+use crate::syntactic::Parser;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// Árvore de derivação concreta: um nó por produção da gramática em README.md, com os tokens
+// consumidos como folhas
+#[derive(PartialEq, Debug, Clone)]
+pub struct Derivation {
+    pub label: String,
+    pub children: Vec<Derivation>,
+    pub terminal: bool,
+}
+
+impl Derivation {
+    fn new(label: &str, terminal: bool) -> Self {
+        Self {
+            label: label.to_string(),
+            children: vec![],
+            terminal,
+        }
+    }
+}
+
+// Produções ainda em análise, da mais externa à mais interna, e a raiz quando a análise termina
+#[derive(Default)]
+pub(super) struct Recorder {
+    open: Vec<Derivation>,
+    root: Option<Derivation>,
+}
+
+// Mantém o nó da produção aberto enquanto a função da produção executa, inclusive nos retornos
+// antecipados
+pub(super) struct Production(Option<Rc<RefCell<Recorder>>>);
+
+impl Drop for Production {
+    fn drop(&mut self) {
+        let Some(recorder) = &self.0 else {
+            return;
+        };
+        let mut recorder = recorder.borrow_mut();
+        let Some(node) = recorder.open.pop() else {
+            return;
+        };
+        match recorder.open.last_mut() {
+            Some(parent) => parent.children.push(node),
+            None => recorder.root = Some(node),
+        }
+    }
+}
+
+impl Parser {
+    // Guarda a árvore de derivação durante a análise, desligado por padrão
+    pub fn set_derivation(&mut self, value: bool) {
+        self.derivation = value.then(Rc::default);
+    }
+
+    // Árvore de derivação da última análise sem erros
+    pub fn get_derivation(&self) -> Option<Derivation> {
+        self.derivation.as_ref()?.borrow().root.clone()
+    }
+
+    // Uma análise com erro deixa apenas a parte da árvore derivada até ele
+    pub(super) fn discard_derivation(&self) {
+        if let Some(recorder) = &self.derivation {
+            recorder.borrow_mut().root = None;
+        }
+    }
+
+    pub(super) fn production(&self, label: &str) -> Production {
+        if let Some(recorder) = &self.derivation {
+            recorder
+                .borrow_mut()
+                .open
+                .push(Derivation::new(label, false));
+        }
+        Production(self.derivation.clone())
+    }
+
+    // Consome o próximo token, que vira uma folha da produção em análise
+    pub(super) fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens_buffer.pop()?;
+        if let Some(recorder) = &self.derivation {
+            if let Some(parent) = recorder.borrow_mut().open.last_mut() {
+                parent
+                    .children
+                    .push(Derivation::new(token.get_lexeme(), true));
+            }
+        }
+        Some(token)
+    }
+}
@@ -0,0 +1,169 @@
+use crate::common::symbol::Symbol::EOS;
+use crate::common::symbol::SymbolKind;
+use crate::common::token::Token;
+use crate::common::token::TokenType::{Assignment, Delimiter, Keyword};
+use crate::syntactic::Parser;
+
+// Análise de fluxo de dados feita durante a análise sintática.
+// Apenas as variáveis do escopo atual são acompanhadas: cada uma guarda se com certeza já recebeu
+// um valor, e ao final de um desvio o estado é a interseção dos caminhos possíveis.
+impl Parser {
+    pub(super) fn scope_start(&self) -> usize {
+        self.symbol_table
+            .iter()
+            .rposition(|item| item == &EOS)
+            .map_or(0, |index| index + 1)
+    }
+
+    fn variable_index(&self, token: &Token) -> Option<usize> {
+        let index = self.symbol_table.iter().rposition(|item| {
+            item.as_token()
+                .is_some_and(|declared| declared.is_lexeme(token.get_lexeme()))
+        })?;
+        (self.symbol_table[index].get_kind() == Some(SymbolKind::Variable)).then_some(index)
+    }
+
+    pub(super) fn mark_read(&mut self, token: &Token) {
+        let Some(index) = self.variable_index(token) else {
+            return;
+        };
+        let start = self.scope_start();
+        let symbol = &mut self.symbol_table[index];
+        symbol.set_read(true);
+        let declared = symbol.as_token().unwrap();
+        let declaration = (declared.get_line(), declared.get_column());
+        // O aviso é dado apenas na primeira leitura sem atribuição de cada variável
+        if index >= start && !symbol.is_assigned() && !self.unassigned_reads.contains(&declaration)
+        {
+            self.warn(
+                token,
+                format!(
+                    "Variable '{}' is read at line {} column {} before being assigned.",
+                    token.get_lexeme(),
+                    token.get_line(),
+                    token.get_column()
+                ),
+            );
+            self.unassigned_reads.push(declaration);
+        }
+    }
+
+    pub(super) fn mark_called(&mut self, token: &Token) {
+        let position = self.symbol_table.iter().rposition(|item| {
+            item.is_callable()
+                && item
+                    .as_token()
+                    .is_some_and(|declared| declared.is_lexeme(token.get_lexeme()))
+        });
+        if let Some(index) = position {
+            self.symbol_table[index].set_read(true);
+        }
+    }
+
+    pub(super) fn mark_assigned(&mut self, token: &Token) {
+        if let Some(index) = self.variable_index(token) {
+            self.symbol_table[index].set_written(true);
+            self.symbol_table[index].set_assigned(true);
+        }
+    }
+
+    pub(super) fn assigned_state(&self) -> Vec<bool> {
+        self.symbol_table[self.scope_start()..]
+            .iter()
+            .map(|symbol| symbol.is_assigned())
+            .collect()
+    }
+
+    pub(super) fn restore_assigned(&mut self, state: &[bool]) {
+        let start = self.scope_start();
+        for (symbol, assigned) in self.symbol_table[start..].iter_mut().zip(state) {
+            symbol.set_assigned(*assigned);
+        }
+    }
+
+    // Uma variável só está atribuída depois do desvio se estiver em todos os caminhos
+    pub(super) fn merge_assigned(states: &[Vec<bool>]) -> Vec<bool> {
+        let mut merged = states.first().cloned().unwrap_or_default();
+        for state in states.iter().skip(1) {
+            for (assigned, other) in merged.iter_mut().zip(state) {
+                *assigned = *assigned && *other;
+            }
+        }
+        merged
+    }
+
+    // Depois de exit, break e continue o caminho atual não continua, então não restringe a interseção
+    pub(super) fn mark_unreachable(&mut self) {
+        let start = self.scope_start();
+        for symbol in self.symbol_table[start..].iter_mut() {
+            symbol.set_assigned(true);
+        }
+    }
+
+    // Marca os parâmetros recebidos por um procedimento como atribuídos
+    pub(super) fn mark_parameters(&mut self) {
+        self.mark_unreachable();
+    }
+
+    // Avisa sobre variáveis nunca usadas, parâmetros nunca lidos e procedimentos nunca chamados a
+    // partir de 'start' no escopo atual, os primeiros 'parameters' símbolos são parâmetros
+    pub(super) fn check_unused(&mut self, start: usize, parameters: usize, routine: &str) {
+        let symbols = self.symbol_table[start..].to_vec();
+        for (position, symbol) in symbols.iter().enumerate() {
+            let token = symbol.as_token().unwrap();
+            let message = match symbol.get_kind() {
+                Some(SymbolKind::Variable) if position < parameters && !symbol.is_read() => {
+                    format!(
+                        "Parameter '{}' of procedure '{}' declared at line {} column {} is never read.",
+                        token.get_lexeme(),
+                        routine,
+                        token.get_line(),
+                        token.get_column()
+                    )
+                }
+                Some(SymbolKind::Variable)
+                    if position >= parameters && !symbol.is_read() && !symbol.is_written() =>
+                {
+                    format!(
+                        "Variable '{}' declared at line {} column {} is never used.",
+                        token.get_lexeme(),
+                        token.get_line(),
+                        token.get_column()
+                    )
+                }
+                Some(SymbolKind::Procedure) if !symbol.is_read() => format!(
+                    "Procedure '{}' declared at line {} column {} is never called.",
+                    token.get_lexeme(),
+                    token.get_line(),
+                    token.get_column()
+                ),
+                _ => continue,
+            };
+            self.warn(&token, message);
+        }
+    }
+
+    // Indica se o destino da atribuição à frente passa por um ponteiro, como em p^.valor := 1,
+    // o que lê a variável em vez de atribuí-la
+    pub(super) fn dereferences_ahead(&self) -> bool {
+        let mut depth = 0;
+        for token in self.tokens_buffer.iter().rev() {
+            if token.is_lexeme("[") || token.is_lexeme("(") {
+                depth += 1;
+            } else if token.is_lexeme("]") || token.is_lexeme(")") {
+                depth -= 1;
+            } else if depth == 0 {
+                if token.is_lexeme("^") {
+                    return true;
+                }
+                if token.is_type_of(Assignment)
+                    || token.is_type_of(Keyword)
+                    || (token.is_type_of(Delimiter) && token.is_lexeme(";"))
+                {
+                    return false;
+                }
+            }
+        }
+        false
+    }
+}
@@ -0,0 +1,198 @@
+use crate::common::builtins::prelude;
+use crate::common::diagnostic::Diagnostic;
+use crate::common::symbol::Symbol;
+use crate::common::symbol::Symbol::EOS;
+use crate::common::token::Token;
+use crate::common::token::TokenType::Delimiter;
+use crate::common::types::Type;
+use crate::syntactic::ast::{Declaration, Expression, Statement};
+use crate::syntactic::Parser;
+
+// Uma entrada do modo interativo, já verificada
+#[derive(PartialEq, Debug)]
+pub enum Entry {
+    Declarations(Vec<Declaration>),
+    Statements(Vec<Statement>),
+    Expression(Expression),
+}
+
+// Estado restaurado quando uma entrada tem erros, para que ela não deixe declarações pela metade
+struct Snapshot {
+    symbol_table: Vec<Symbol>,
+    warnings: usize,
+    conversions: usize,
+    declared: usize,
+    references: usize,
+    unassigned_reads: usize,
+}
+
+impl Parser {
+    // No modo interativo os escopos pré-definido e global ficam abertos entre as entradas
+    pub fn interactive() -> Self {
+        let mut parser = Parser::new(&[]);
+        parser.symbol_table.push(EOS);
+        parser.symbol_table.extend(prelude());
+        parser.symbol_table.push(EOS);
+        parser
+    }
+
+    // Analisa declarações, comandos separados por ';' ou uma expressão, cujo valor é mostrado
+    pub fn feed(&mut self, tokens: &[Token]) -> Result<Entry, Diagnostic> {
+        let Some(first) = tokens.first() else {
+            return Ok(Entry::Statements(vec![]));
+        };
+        let declaration = ["const", "type", "var", "procedure"]
+            .iter()
+            .any(|keyword| first.is_lexeme(keyword));
+        if declaration {
+            // O ';' depois da última declaração é opcional
+            let mut tokens = tokens.to_vec();
+            if !tokens.last().unwrap().is_lexeme(";") {
+                tokens.push(Self::end_of_input(&tokens, ";"));
+            }
+            return self
+                .attempt(&tokens, Self::declarations)
+                .map_err(|(e, _)| e);
+        }
+
+        // Chamadas de procedimento também são expressões para a gramática, mas sem valor
+        let snapshot = self.snapshot();
+        let expression = match self.attempt(tokens, Self::expression_entry) {
+            Ok(Entry::Expression(value)) if value.value_type == Type::Procedure => {
+                self.restore(snapshot);
+                None
+            }
+            Ok(entry) => return Ok(entry),
+            Err(error) => Some(error),
+        };
+        match self.attempt(tokens, Self::statements_entry) {
+            Ok(entry) => Ok(entry),
+            // Entre as duas tentativas, o erro mais adiante na entrada é o mais provável
+            Err((error, remaining)) => match expression {
+                Some((expression, left)) if left < remaining => Err(expression),
+                _ => Err(error),
+            },
+        }
+    }
+
+    // Tipo calculado pela verificação para uma expressão, sem alterar o estado da sessão
+    pub fn type_of(&mut self, tokens: &[Token]) -> Result<Type, Diagnostic> {
+        let snapshot = self.snapshot();
+        let result = self.attempt(tokens, Self::expression_entry);
+        self.restore(snapshot);
+        match result {
+            Ok(Entry::Expression(expression)) => Ok(expression.value_type),
+            Ok(_) => unreachable!(),
+            Err((error, _)) => Err(error),
+        }
+    }
+
+    // Analisa a entrada e, em caso de erro, desfaz o que ela alterou e informa quantos tokens
+    // ficaram sem análise
+    fn attempt(
+        &mut self,
+        tokens: &[Token],
+        production: fn(&mut Self) -> Result<Entry, Diagnostic>,
+    ) -> Result<Entry, (Diagnostic, usize)> {
+        let snapshot = self.snapshot();
+        let mut buffer = tokens.to_vec();
+        buffer.push(Self::end_of_input(tokens, "<end of input>"));
+        buffer.reverse();
+        self.tokens_buffer = buffer;
+
+        let result = production(self);
+        let remaining = self.tokens_buffer.len();
+        self.tokens_buffer.clear();
+        self.control_type_stack.clear();
+        self.expressions.clear();
+        self.statements.clear();
+        self.items.clear();
+        self.loop_depth = 0;
+        self.amount = 0;
+        self.pending_types = None;
+        result.map_err(|error| {
+            self.restore(snapshot);
+            (error, remaining)
+        })
+    }
+
+    // Marca o fim da entrada com um token que nenhuma produção aceita, nem mesmo o '.' depois de
+    // um registro
+    fn end_of_input(tokens: &[Token], lexeme: &str) -> Token {
+        let (line, column) = tokens.last().map_or((1, 1), |token| {
+            (
+                token.get_line(),
+                token.get_column() + token.get_lexeme().chars().count(),
+            )
+        });
+        Token::new(lexeme, Delimiter, line, column)
+    }
+
+    fn finish(&mut self) -> Result<(), Diagnostic> {
+        let next = self.peek()?;
+        if self.tokens_buffer.len() > 1 {
+            return Err(Diagnostic::at(
+                &next,
+                format!(
+                    "Unexpected '{}' at line {} column {}.",
+                    next.get_lexeme(),
+                    next.get_line(),
+                    next.get_column()
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    fn declarations(&mut self) -> Result<Entry, Diagnostic> {
+        loop {
+            let next = self.peek()?;
+            if next.is_lexeme("const") {
+                self.constants_declaration()?;
+            } else if next.is_lexeme("type") {
+                self.types_declaration()?;
+            } else if next.is_lexeme("var") {
+                self.vars_declaration()?;
+            } else if next.is_lexeme("procedure") {
+                self.subprograms_declaration()?;
+            } else {
+                break;
+            }
+        }
+        self.finish()?;
+        Ok(Entry::Declarations(std::mem::take(&mut self.items)))
+    }
+
+    fn statements_entry(&mut self) -> Result<Entry, Diagnostic> {
+        self.list_of_commands()?;
+        self.finish()?;
+        Ok(Entry::Statements(std::mem::take(&mut self.statements)))
+    }
+
+    fn expression_entry(&mut self) -> Result<Entry, Diagnostic> {
+        self.expression()?;
+        self.finish()?;
+        self.control_type_stack.pop();
+        Ok(Entry::Expression(self.pop_expression()))
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            symbol_table: self.symbol_table.to_owned(),
+            warnings: self.warnings.len(),
+            conversions: self.conversions.len(),
+            declared: self.declared.len(),
+            references: self.references.len(),
+            unassigned_reads: self.unassigned_reads.len(),
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.symbol_table = snapshot.symbol_table;
+        self.warnings.truncate(snapshot.warnings);
+        self.conversions.truncate(snapshot.conversions);
+        self.declared.truncate(snapshot.declared);
+        self.references.truncate(snapshot.references);
+        self.unassigned_reads.truncate(snapshot.unassigned_reads);
+    }
+}
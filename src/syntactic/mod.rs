@@ -1,20 +1,61 @@
-use crate::common::symbol::Symbol;
+pub mod ast;
+mod compatibility;
+pub mod constant;
+pub mod derivation;
+mod flow;
+pub mod interactive;
+mod test;
+mod units;
+
+use crate::common::builtins::{prelude, Generic};
+use crate::common::diagnostic::Diagnostic;
 use crate::common::symbol::Symbol::EOS;
+use crate::common::symbol::{Symbol, SymbolKind};
 use crate::common::token::TokenType::*;
 use crate::common::token::{Token, TokenType};
-use std::error::Error;
+use crate::common::types::Type;
+use crate::common::utils::{is_type, parse_integer, unquote, MAX_SET_SIZE};
+use ast::{Declaration, Expression, ExpressionKind, Procedure, Program, Statement};
+use compatibility::{Compatibility, Conversion};
+use constant::Value;
+use derivation::Recorder;
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use units::UnitLoader;
 
 pub struct Parser {
     tokens_buffer: Vec<Token>,
     symbol_table: Vec<Symbol>,
-    control_type_stack: Vec<String>,
+    control_type_stack: Vec<Type>,
     program_name: String,
     amount: usize,
+    strict_case: bool,
+    warnings: Vec<Diagnostic>,
+    pending_types: Option<Vec<Token>>, // Tipos apontados ainda não declarados na seção type atual
+    loop_depth: usize,                 // Quantidade de laços envolvendo o comando atual
+    conversions: Vec<Conversion>,      // Conversões implícitas de tipo, como de integer para real
+    directory: PathBuf, // Diretório do arquivo analisado, onde as units são procuradas
+    units: Rc<RefCell<UnitLoader>>,
+    exports: Vec<Symbol>, // Símbolos da seção interface, quando o arquivo é uma unit
+    unassigned_reads: Vec<(usize, usize)>, // Posição das declarações já avisadas por leitura sem atribuição
+    terminated: bool, // O último comando analisado não continua no seguinte, como exit
+    suppressed: Vec<(usize, usize)>, // Intervalos de linhas entre {$warnings off} e {$warnings on}
+    declared: Vec<(usize, usize)>, // Posição dos identificadores declarados neste arquivo
+    declarations: Vec<Symbol>, // Símbolos declarados neste arquivo cujo escopo já foi fechado
+    references: Vec<(Token, Symbol)>, // Cada uso de um identificador e o símbolo a que ele se refere
+    expressions: Vec<Expression>,     // Expressões montadas, acompanha a pilha de controle de tipos
+    set_elements: Vec<(Token, i64)>,  // Elementos constantes dos conjuntos montados, com o ordinal
+    statements: Vec<Statement>,       // Comandos montados e ainda não agrupados em um bloco
+    items: Vec<Declaration>, // Declarações montadas e ainda não agrupadas em um procedimento
+    used_units: Vec<Program>, // Árvores das units da cláusula uses
+    program: Option<Program>,
+    derivation: Option<Rc<RefCell<Recorder>>>, // Árvore de derivação, apenas quando pedida
 }
 
 impl Parser {
-    pub fn new(tokens: &Vec<Token>) -> Self {
-        let mut temp = tokens.to_owned();
+    pub fn new(tokens: &[Token]) -> Self {
+        let mut temp = tokens.to_vec();
         temp.reverse();
         Self {
             tokens_buffer: temp,
@@ -22,66 +63,443 @@ impl Parser {
             control_type_stack: vec![],
             program_name: "".to_string(),
             amount: 0,
+            strict_case: false,
+            warnings: vec![],
+            pending_types: None,
+            loop_depth: 0,
+            conversions: Vec::new(),
+            directory: PathBuf::new(),
+            units: Rc::default(),
+            exports: vec![],
+            unassigned_reads: vec![],
+            terminated: false,
+            suppressed: vec![],
+            declared: vec![],
+            declarations: vec![],
+            references: vec![],
+            expressions: vec![],
+            set_elements: vec![],
+            statements: vec![],
+            items: vec![],
+            used_units: vec![],
+            program: None,
+            derivation: None,
         }
     }
 
-    pub fn init(&mut self) -> Result<bool, Box<dyn Error + Send + Sync + 'static>> {
-        self.programa()?;
+    // As units usadas são procuradas no diretório do arquivo fonte
+    pub fn set_source_path(&mut self, path: &Path) {
+        self.directory = path.parent().map(Path::to_path_buf).unwrap_or_default();
+    }
+
+    // Diretórios adicionais onde procurar as units
+    pub fn set_unit_paths(&mut self, paths: Vec<PathBuf>) {
+        self.units = Rc::new(RefCell::new(UnitLoader::new(paths)));
+    }
+
+    // Ativa o lint que exige palavras reservadas em minúsculo e identificadores escritos como na declaração
+    pub fn set_strict_case(&mut self, value: bool) {
+        self.strict_case = value;
+    }
 
-        for item in self.symbol_table.iter().rev() {
-            println!("{item}")
+    // Os avisos podem ser desligados em trechos do programa com {$warnings off} e {$warnings on}
+    pub fn set_directives(&mut self, directives: &[(usize, String)]) {
+        let mut start = None;
+        for (line, directive) in directives {
+            match directive.split_whitespace().collect::<Vec<_>>()[..] {
+                ["warnings", "off"] => {
+                    start.get_or_insert(*line);
+                }
+                ["warnings", "on"] => {
+                    if let Some(start) = start.take() {
+                        self.suppressed.push((start, *line));
+                    }
+                }
+                _ => {}
+            }
         }
+        if let Some(start) = start {
+            self.suppressed.push((start, usize::MAX));
+        }
+    }
+
+    pub fn get_warnings(&self) -> &[Diagnostic] {
+        &self.warnings
+    }
+
+    // Símbolos declarados no arquivo, incluindo os dos escopos ainda abertos quando a análise falha
+    pub fn get_declarations(&self) -> Vec<Symbol> {
+        self.declarations
+            .iter()
+            .chain(self.symbol_table.iter())
+            .filter(|symbol| self.is_declared_here(symbol))
+            .cloned()
+            .collect()
+    }
+
+    pub fn get_references(&self) -> &[(Token, Symbol)] {
+        &self.references
+    }
 
-        Ok(true)
+    // Árvore sintática do programa ou da unit, disponível depois de uma análise sem erros
+    pub fn get_program(&self) -> Option<&Program> {
+        self.program.as_ref()
+    }
+
+    // Analisa o programa, a árvore sintática fica disponível em get_program
+    pub fn check(&mut self) -> Result<(), Diagnostic> {
+        if self.strict_case {
+            self.check_keywords_case();
+        }
+        let result = self.programa();
+        if result.is_err() {
+            self.discard_derivation();
+        }
+        result
     }
 }
 
 impl Parser {
-    fn programa(&mut self) -> Result<(), String> {
+    fn programa(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("programa");
+        if self.peek()?.is_lexeme("unit") {
+            return self.unit();
+        }
+
         self.program()?;
+        self.constants_declaration()?;
+        self.types_declaration()?;
         self.vars_declaration()?;
         self.subprograms_declaration()?;
+        self.check_forward_declarations()?;
         self.compound_command()?;
+        let body = self.pop_block();
 
         self.consume(Delimiter, ".")?;
-        self.remove_scope();
+        self.program = Some(Program {
+            name: self.program_name.to_ascii_lowercase(),
+            units: std::mem::take(&mut self.used_units),
+            declarations: std::mem::take(&mut self.items),
+            body,
+        });
+        self.check_unused(self.scope_start(), 0, &self.program_name.to_owned());
+        // Remove os escopos global, das units usadas e pré-definido
+        while !self.symbol_table.is_empty() {
+            self.remove_scope();
+        }
 
         Ok(())
     }
 
     // Aqui começa a produção de program
-    fn program(&mut self) -> Result<(), String> {
+    fn program(&mut self) -> Result<(), Diagnostic> {
         self.consume(Keyword, "program")?;
 
+        self.symbol_table.push(EOS); // Criação do escopo pré-definido
+        self.symbol_table.extend(prelude());
+        let token = self.consume_identifiers()?;
+        self.program_name = token.get_lexeme().to_string();
+        self.consume(Delimiter, ";")?;
+        self.uses_clause()?;
+
         self.symbol_table.push(EOS); // Criação do escopo global
+        let mut symbol = Symbol::new(token);
+        symbol.set_kind(SymbolKind::Program);
+        self.add_symbol(symbol)?;
+
+        Ok(())
+    }
+
+    // uses -> uses list_of_identifiers ; | ε, cada unit ganha um escopo entre o pré-definido e o global
+    fn uses_clause(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("uses_clause");
+        let next = self.peek()?;
+        if !(next.is_type_of(Keyword) && next.is_lexeme("uses")) {
+            return Ok(());
+        }
+        self.consume(Keyword, "uses")?;
+        loop {
+            let token = self.consume_identifiers()?;
+            let symbols = self.import_unit(&token)?;
+            self.symbol_table.push(EOS);
+            self.symbol_table.extend(symbols);
+            if !self.peek()?.is_lexeme(",") {
+                break;
+            }
+            self.consume(Delimiter, ",")?;
+        }
+        self.consume(Delimiter, ";")?;
+        Ok(())
+    }
+
+    // unit -> unit id ; interface uses declarations headers implementation declarations subprograms
+    //         [compound_command | end] .
+    fn unit(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("unit");
+        self.consume(Keyword, "unit")?;
+
+        self.symbol_table.push(EOS); // Criação do escopo pré-definido
+        self.symbol_table.extend(prelude());
         let token = self.consume_identifiers()?;
         self.program_name = token.get_lexeme().to_string();
-        self.add_symbol(Symbol::new(token))?;
+        self.consume(Delimiter, ";")?;
+        self.units.borrow_mut().enter(token.get_lexeme());
+
+        self.consume(Keyword, "interface")?;
+        self.uses_clause()?;
+        self.symbol_table.push(EOS); // Criação do escopo global
+        let mut symbol = Symbol::new(token);
+        symbol.set_kind(SymbolKind::Program);
+        self.add_symbol(symbol)?;
+
+        let start = self.symbol_table.len();
+        self.constants_declaration()?;
+        self.types_declaration()?;
+        self.vars_declaration()?;
+        self.procedure_headers()?;
+        let end = self.symbol_table.len();
+
+        // Os procedimentos da interface recebem o corpo na implementação, como os declarados forward
+        self.consume(Keyword, "implementation")?;
+        self.constants_declaration()?;
+        self.types_declaration()?;
+        self.vars_declaration()?;
+        self.subprograms_declaration()?;
+        self.check_forward_declarations()?;
+
+        let body = if self.peek()?.is_lexeme("begin") {
+            self.compound_command()?;
+            self.pop_block()
+        } else {
+            self.consume(Keyword, "end")?;
+            vec![]
+        };
+        self.consume(Delimiter, ".")?;
+        self.program = Some(Program {
+            name: self.program_name.to_ascii_lowercase(),
+            units: std::mem::take(&mut self.used_units),
+            declarations: std::mem::take(&mut self.items),
+            body,
+        });
+
+        // As variáveis da interface são usadas por quem importa a unit
+        self.check_unused(end, 0, &self.program_name.to_owned());
+        self.exports = self.symbol_table[start..end].to_vec();
+        self.units.borrow_mut().leave();
+        while !self.symbol_table.is_empty() {
+            self.remove_scope();
+        }
+
+        Ok(())
+    }
+
+    // Cabeçalhos de procedimentos da seção interface
+    fn procedure_headers(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("procedure_headers");
+        while self.peek()?.is_lexeme("procedure") {
+            self.consume(Keyword, "procedure")?;
+            let token = self.consume_identifiers()?;
+            let mut symbol = Symbol::new(token);
+            symbol.set_kind(SymbolKind::Procedure);
+            symbol.change_type(&Type::Procedure);
+            self.add_symbol(symbol)?;
+            let index = self.symbol_table.len() - 1;
+
+            self.symbol_table.push(EOS);
+            self.arguments()?;
+            let parameters = self.current_scope();
+            self.remove_scope();
+            self.consume(Delimiter, ";")?;
+
+            self.symbol_table[index].set_parameters(parameters);
+            self.symbol_table[index].set_forward(true);
+        }
+        Ok(())
+    }
+
+    // constants_declaration -> const constant_definition {constant_definition} | ε
+    fn constants_declaration(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("constants_declaration");
+        let next = self.peek()?;
+
+        if next.is_type_of(Keyword) && next.is_lexeme("const") {
+            self.consume(Keyword, "const")?;
+            self.constant_definition()?;
+            while self.peek()?.is_type_of(Identifier) {
+                self.constant_definition()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // constant_definition -> id = expression ;, a expressão precisa ser avaliada em tempo de compilação
+    fn constant_definition(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("constant_definition");
+        let token = self.consume_identifiers()?;
+        self.consume(RelationalOperators, "=")?;
+
+        let next = self.peek()?;
+        let (value, _) = self.evaluate_ahead()?;
+        self.expression()?;
+        self.control_type_stack.pop();
+        self.expressions.pop();
+        let value = match value {
+            None => {
+                return Err(Diagnostic::at(
+                    &next,
+                    format!(
+                    "The value of constant '{}' at line {} column {} is not a constant expression.",
+                    token.get_lexeme(),
+                    next.get_line(),
+                    next.get_column()
+                ),
+                ))
+            }
+            Some(value) => value,
+        };
+
+        let name = token.get_lexeme().to_ascii_lowercase();
+        let mut symbol = Symbol::new(token);
+        symbol.set_kind(SymbolKind::Constant);
+        symbol.change_type(&value.get_type());
+        symbol.set_value(&value.stored());
+        self.add_symbol(symbol)?;
+        self.items.push(Declaration::Constant(name, value));
+
+        self.consume(Delimiter, ";")?;
+        Ok(())
+    }
+
+    // Aqui começa a produção de types_declaration
+    fn types_declaration(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("types_declaration");
+        let next = self.peek()?;
+
+        if next.is_type_of(Keyword) && next.is_lexeme("type") {
+            self.consume(Keyword, "type")?;
+            self.pending_types = Some(vec![]);
+            self.type_definition()?;
+            self.list_of_types_declaration_prime()?;
+            self.check_pending_types()?;
+        }
+
+        Ok(())
+    }
+
+    // Um ponteiro pode apontar para um tipo declarado mais adiante na mesma seção, como em listas ligadas
+    fn check_pending_types(&mut self) -> Result<(), Diagnostic> {
+        for token in self.pending_types.take().unwrap_or_default() {
+            let declared = self
+                .lookup(token.get_lexeme())
+                .is_some_and(|symbol| symbol.get_kind() == Some(SymbolKind::Type));
+            if !declared {
+                return Err(Diagnostic::at(
+                    &token,
+                    format!(
+                        "Use of the undeclared type '{}' at line {} column {}.",
+                        token.get_lexeme(),
+                        token.get_line(),
+                        token.get_column()
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    // As produções com ' são recursivas à direita na gramática e viram laços aqui, para que a pilha
+    // não cresça com o tamanho das listas. Cada repetição abre o seu nó dentro do anterior, o que
+    // mantém a árvore de derivação igual à da gramática
+    fn list_of_types_declaration_prime(&mut self) -> Result<(), Diagnostic> {
+        let mut productions = vec![self.production("list_of_types_declaration'")];
+        while self.peek()?.is_type_of(Identifier) {
+            self.type_definition()?;
+            productions.push(self.production("list_of_types_declaration'"));
+        }
+        Ok(())
+    }
+
+    fn type_definition(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("type_definition");
+        let token = self.consume_identifiers()?;
+        let mut symbol = Symbol::new(token.to_owned());
+        symbol.set_kind(SymbolKind::Type);
+        self.add_symbol(symbol)?;
+        let index = self.symbol_table.len() - 1;
+
+        self.consume(RelationalOperators, "=")?;
+
+        let next = self.peek()?;
+        let definition = if next.is_lexeme("(") {
+            self.enumeration(&token)?
+        } else {
+            let definition = self.types()?;
+            let name = token.get_lexeme().to_ascii_lowercase();
+            self.items
+                .push(Declaration::Type(name, definition.to_owned()));
+            definition
+        };
+        self.symbol_table[index].change_type(&definition);
 
         self.consume(Delimiter, ";")?;
 
         Ok(())
     }
 
+    // As constantes de um tipo enumerado têm o nome do tipo como tipo e a posição como valor
+    fn enumeration(&mut self, name: &Token) -> Result<Type, Diagnostic> {
+        let name = name.get_lexeme().to_ascii_lowercase();
+        self.consume(Delimiter, "(")?;
+
+        let start = self.symbol_table.len();
+        let mut position = 0;
+        let mut constants = vec![];
+        loop {
+            let token = self.consume_identifiers()?;
+            constants.push(token.get_lexeme().to_ascii_lowercase());
+            let mut symbol = Symbol::new(token);
+            symbol.set_kind(SymbolKind::Constant);
+            symbol.set_value(&position.to_string());
+            self.add_symbol(symbol)?;
+            position += 1;
+
+            if !self.peek()?.is_lexeme(",") {
+                break;
+            }
+            self.consume(Delimiter, ",")?;
+        }
+
+        self.consume(Delimiter, ")")?;
+        let enumeration_type = Type::Enumeration(name.to_owned(), constants.to_owned());
+        for symbol in &mut self.symbol_table[start..] {
+            symbol.change_type(&enumeration_type);
+        }
+        self.items.push(Declaration::Enumeration(name, constants));
+        Ok(enumeration_type)
+    }
+
     // Aqui começa a produção de vars_declaration
-    fn vars_declaration(&mut self) -> Result<(), String> {
+    fn vars_declaration(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("vars_declaration");
         let var = self.peek()?;
 
-        if var.get_lexeme() == "var" {
-            self.tokens_buffer.pop();
+        if var.is_lexeme("var") {
+            self.advance();
             self.list_of_vars_declaration()?;
         }
 
         Ok(())
     }
 
-    fn list_of_vars_declaration(&mut self) -> Result<(), String> {
+    fn list_of_vars_declaration(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("list_of_vars_declaration");
         self.list_of_identifiers()?;
 
         self.consume(Delimiter, ":")?;
 
         let symbol_type = self.types()?;
-        self.update_symbols_type(&symbol_type);
+        self.declare_variables(&symbol_type)?;
 
         self.consume(Delimiter, ";")?;
 
@@ -90,24 +508,25 @@ impl Parser {
         Ok(())
     }
 
-    fn list_of_vars_declaration_prime(&mut self) -> Result<(), String> {
-        let next = self.peek()?;
-        if next.is_type_of(Identifier) {
+    fn list_of_vars_declaration_prime(&mut self) -> Result<(), Diagnostic> {
+        let mut productions = vec![self.production("list_of_vars_declaration'")];
+        while self.peek()?.is_type_of(Identifier) {
             self.list_of_identifiers()?;
 
             self.consume(Delimiter, ":")?;
 
             let symbol_type = self.types()?;
-            self.update_symbols_type(&symbol_type);
+            self.declare_variables(&symbol_type)?;
 
             self.consume(Delimiter, ";")?;
 
-            self.list_of_vars_declaration_prime()?;
+            productions.push(self.production("list_of_vars_declaration'"));
         }
         Ok(())
     }
 
-    fn list_of_identifiers(&mut self) -> Result<(), String> {
+    fn list_of_identifiers(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("list_of_identifiers");
         let token = self.consume_identifiers()?;
 
         self.amount += 1;
@@ -118,48 +537,291 @@ impl Parser {
         Ok(())
     }
 
-    fn list_of_identifiers_prime(&mut self) -> Result<(), String> {
-        let comma = self.peek()?;
-
-        if comma.get_lexeme() == "," {
-            self.tokens_buffer.pop();
+    fn list_of_identifiers_prime(&mut self) -> Result<(), Diagnostic> {
+        let mut productions = vec![self.production("list_of_identifiers'")];
+        while self.peek()?.is_lexeme(",") {
+            self.advance();
             let token = self.consume_identifiers()?;
             self.amount += 1;
             self.add_symbol(Symbol::new(token))?; // Adiciona o identificador à tabela de símbolos
 
-            self.list_of_identifiers_prime()?;
+            productions.push(self.production("list_of_identifiers'"));
         }
 
         Ok(())
     }
 
-    fn types(&mut self) -> Result<String, String> {
-        match self.tokens_buffer.pop() {
-            None => Err("Syntactic Error. Unexpected end of file.".to_string()),
+    fn types(&mut self) -> Result<Type, Diagnostic> {
+        let _production = self.production("type");
+        let next = self.peek()?;
+
+        if next.is_type_of(Keyword) && next.is_lexeme("array") {
+            return self.array_type();
+        } else if next.is_type_of(Keyword) && next.is_lexeme("set") {
+            return self.set_type();
+        } else if next.is_type_of(Keyword) && next.is_lexeme("record") {
+            return self.record_type();
+        } else if next.is_lexeme("^") {
+            return self.pointer_type();
+        } else if next.is_lexeme("(") {
+            return Err(Diagnostic::at(&next, format!(
+                "Enumerated types must be declared in a type section, found one at line {} column {}.",
+                next.get_line(),
+                next.get_column()
+            )));
+        } else if next.is_type_of(Identifier)
+            && !self.peek_second()?.is_lexeme("..")
+            && !self.is_constant(&next)
+        {
+            return self.named_type();
+        } else if !next.is_type_of(Keyword) {
+            return self.subrange_type();
+        }
+
+        match self.advance() {
+            None => Err("Syntactic Error. Unexpected end of file.".into()),
             Some(value) => {
                 if !value.is_type_of(Keyword) {
-                    return Err(format!("Expected an keyword: 'integer', 'real' or 'boolean'.\nInstead got '{}' of type '{}' at line {} column {}.",
-                    value.get_lexeme(), value.get_type(), value.get_line(), value.get_column()));
+                    return Err(Diagnostic::at(&value, format!("Expected an keyword: 'integer', 'real', 'boolean', 'char' or 'string'.\nInstead got '{}' of type '{}' at line {} column {}.",
+                    value.get_lexeme(), value.get_type(), value.get_line(), value.get_column())));
                 }
                 let lexeme = value.get_lexeme();
-                if !(lexeme == "integer" || lexeme == "real" || lexeme == "boolean") {
-                    return Err(format!(
-                        "Expected 'integer', 'real' or 'boolean' got '{}' at line {} column {}.",
+                if !is_type(lexeme) {
+                    return Err(Diagnostic::at(&value, format!(
+                        "Expected 'integer', 'real', 'boolean', 'char' or 'string' got '{}' at line {} column {}.",
                         lexeme,
                         value.get_line(),
                         value.get_column()
+                    )));
+                }
+                Ok(match lexeme.to_ascii_lowercase().as_str() {
+                    "integer" => Type::Integer,
+                    "real" => Type::Real,
+                    "boolean" => Type::Boolean,
+                    "char" => Type::Char,
+                    _ => Type::String,
+                })
+            }
+        }
+    }
+
+    fn named_type(&mut self) -> Result<Type, Diagnostic> {
+        let token = self.consume_identifiers()?;
+        let symbol = self.find_symbol(&Symbol::new(token.to_owned()))?;
+
+        if symbol.get_kind() != Some(SymbolKind::Type) {
+            return Err(Diagnostic::at(
+                &token,
+                format!(
+                    "'{}' at line {} column {} is not a type.",
+                    token.get_lexeme(),
+                    token.get_line(),
+                    token.get_column()
+                ),
+            ));
+        }
+        Ok(symbol.get_type().unwrap())
+    }
+
+    fn subrange_type(&mut self) -> Result<Type, Diagnostic> {
+        let (low_token, low_type, low) = self.constant()?;
+        let range = self.consume(Delimiter, "..")?;
+        let (high_token, high_type, high) = self.constant()?;
+
+        let low_type = low_type.host().to_owned();
+        if low_type != *high_type.host() || !low_type.is_ordinal() {
+            return Err(Diagnostic::at(
+                &range,
+                format!(
+                    "Invalid subrange at line {} column {}.\n\
+                    The bounds must be of the same ordinal type, got '{}' and '{}'.",
+                    range.get_line(),
+                    range.get_column(),
+                    low_type,
+                    high_type
+                ),
+            ));
+        }
+        if low > high {
+            return Err(Diagnostic::at(
+                &range,
+                format!(
+                    "Invalid subrange at line {} column {}.\n\
+                    The lower bound '{}' is greater than the upper bound '{}'.",
+                    range.get_line(),
+                    range.get_column(),
+                    low_token.get_lexeme(),
+                    high_token.get_lexeme()
+                ),
+            ));
+        }
+
+        Ok(Type::Subrange(Box::new(low_type), low, high))
+    }
+
+    fn array_type(&mut self) -> Result<Type, Diagnostic> {
+        self.consume(Keyword, "array")?;
+        let bracket = self.consume(Delimiter, "[")?;
+
+        let index = self.types()?;
+        if index == Type::Integer || !index.is_ordinal() {
+            return Err(Diagnostic::at(
+                &bracket,
+                format!(
+                    "Invalid array index type '{}' at line {} column {}.\n\
+                    The index must be a subrange, an enumeration, 'char' or 'boolean'.",
+                    index,
+                    bracket.get_line(),
+                    bracket.get_column()
+                ),
+            ));
+        }
+
+        self.consume(Delimiter, "]")?;
+        self.consume(Keyword, "of")?;
+        let element = self.types()?;
+
+        Ok(Type::Array(Box::new(index), Box::new(element)))
+    }
+
+    fn set_type(&mut self) -> Result<Type, Diagnostic> {
+        let set = self.consume(Keyword, "set")?;
+        self.consume(Keyword, "of")?;
+
+        let base = self.types()?;
+        let small = base
+            .range()
+            .is_some_and(|(low, high)| low >= 0 && high < MAX_SET_SIZE);
+        if !small {
+            return Err(Diagnostic::at(
+                &set,
+                format!(
+                    "Invalid set base type '{}' at line {} column {}.\n\
+                    The base type must be ordinal with values between 0 and {}.",
+                    base,
+                    set.get_line(),
+                    set.get_column(),
+                    MAX_SET_SIZE - 1
+                ),
+            ));
+        }
+
+        Ok(Type::Set(Box::new(base)))
+    }
+
+    fn record_type(&mut self) -> Result<Type, Diagnostic> {
+        self.consume(Keyword, "record")?;
+
+        let mut fields: Vec<(Token, Type)> = vec![];
+        while self.peek()?.is_type_of(Identifier) {
+            let mut names = vec![self.consume_identifiers()?];
+            while self.peek()?.is_lexeme(",") {
+                self.consume(Delimiter, ",")?;
+                names.push(self.consume_identifiers()?);
+            }
+            self.consume(Delimiter, ":")?;
+            let field_type = self.types()?;
+
+            for name in names {
+                if let Some((declared, _)) = fields.iter().find(|(field, _)| field == &name) {
+                    return Err(Diagnostic::at(
+                        declared,
+                        format!(
+                            "Field '{}' already declared in line {} column {}.",
+                            name.get_lexeme(),
+                            declared.get_line(),
+                            declared.get_column()
+                        ),
                     ));
                 }
-                Ok(lexeme.to_owned())
+                fields.push((name, field_type.to_owned()));
+            }
+
+            if !self.peek()?.is_lexeme(";") {
+                break;
+            }
+            self.consume(Delimiter, ";")?;
+        }
+        self.consume(Keyword, "end")?;
+
+        let fields = fields
+            .into_iter()
+            .map(|(name, field_type)| (name.get_lexeme().to_ascii_lowercase(), field_type))
+            .collect();
+        Ok(Type::Record(fields))
+    }
+
+    // Ponteiros para tipos nomeados guardam apenas o nome, o que permite tipos recursivos
+    fn pointer_type(&mut self) -> Result<Type, Diagnostic> {
+        self.consume(Delimiter, "^")?;
+
+        let next = self.peek()?;
+        if next.is_type_of(Identifier) {
+            let token = self.consume_identifiers()?;
+            match self.lookup(token.get_lexeme()) {
+                Some(symbol) if symbol.get_kind() == Some(SymbolKind::Type) => {}
+                _ => match self.pending_types.as_mut() {
+                    Some(pending) => pending.push(token.to_owned()),
+                    None => {
+                        return Err(Diagnostic::at(
+                            &token,
+                            format!(
+                                "Use of the undeclared type '{}' at line {} column {}.",
+                                token.get_lexeme(),
+                                token.get_line(),
+                                token.get_column()
+                            ),
+                        ))
+                    }
+                },
+            }
+            let name = token.get_lexeme().to_ascii_lowercase();
+            return Ok(Type::Pointer(Box::new(Type::Named(name))));
+        }
+
+        Ok(Type::Pointer(Box::new(self.types()?)))
+    }
+
+    // constant -> expression constante de tipo ordinal, como 1, -N ou N - 1
+    fn constant(&mut self) -> Result<(Token, Type, i64), Diagnostic> {
+        let _production = self.production("constant");
+        let next = self.peek()?;
+        let (value, length) = self.evaluate_ahead()?;
+        let Some((value, ordinal)) =
+            value.and_then(|value| Some((value.to_owned(), value.ordinal()?)))
+        else {
+            return Err(Diagnostic::at(
+                &next,
+                format!(
+                    "Expected an ordinal constant. Instead got '{}' of type {} at line {} column {}.",
+                    next.get_lexeme(),
+                    next.get_type(),
+                    next.get_line(),
+                    next.get_column()
+                ),
+            ));
+        };
+
+        // Os tokens da expressão já foram verificados pelo avaliador
+        let start = self.tokens_buffer.len() - length;
+        for token in self.tokens_buffer.split_off(start) {
+            if let Some(symbol) = self
+                .lookup(token.get_lexeme())
+                .filter(|_| token.is_type_of(Identifier))
+            {
+                self.references.push((token.to_owned(), symbol.to_owned()));
             }
         }
+        let token = Self::folded_token(next, &value, length);
+        Ok((token, value.get_type(), ordinal))
     }
 
     // Aqui começa a produção de subprograms_declaration
-    fn subprograms_declaration(&mut self) -> Result<(), String> {
+    fn subprograms_declaration(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("subprogram_declarations");
         let next = self.peek()?;
 
-        if next.is_type_of(Keyword) && next.get_lexeme() == "procedure" {
+        if next.is_type_of(Keyword) && next.is_lexeme("procedure") {
             self.subprogram_declaration()?;
 
             self.consume(Delimiter, ";")?;
@@ -169,29 +831,109 @@ impl Parser {
         Ok(())
     }
 
-    fn subprogram_declaration(&mut self) -> Result<(), String> {
+    fn subprogram_declaration(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("subprogram_declaration");
         self.consume(Keyword, "procedure")?;
 
         let token = self.consume_identifiers()?;
-        self.add_symbol(Symbol::new(token))?;
+        let mut symbol = Symbol::new(token.to_owned());
+        symbol.set_kind(SymbolKind::Procedure);
+        symbol.change_type(&Type::Procedure);
+
+        // Um procedimento declarado com 'forward' recebe o corpo aqui, sem uma nova entrada na tabela
+        let (index, declared) = match self.find_in_scope(&symbol) {
+            Some(index) if self.symbol_table[index].is_forward() => (index, true),
+            _ => {
+                self.add_symbol(symbol)?;
+                (self.symbol_table.len() - 1, false)
+            }
+        };
         self.symbol_table.push(EOS);
         self.arguments()?;
+        let parameters = self.current_scope();
 
         self.consume(Delimiter, ";")?;
 
+        let next = self.peek()?;
+        if next.is_type_of(Keyword) && next.is_lexeme("forward") {
+            if declared {
+                return Err(Diagnostic::at(
+                    &token,
+                    format!(
+                        "Procedure '{}' at line {} column {} was already declared forward.",
+                        token.get_lexeme(),
+                        token.get_line(),
+                        token.get_column()
+                    ),
+                ));
+            }
+            self.consume(Keyword, "forward")?;
+            self.symbol_table[index].set_parameters(parameters);
+            self.symbol_table[index].set_forward(true);
+            self.remove_scope();
+            return Ok(());
+        }
+
+        if declared {
+            let expected = self.symbol_table[index].get_parameters();
+            if parameters.is_empty() {
+                // O cabeçalho pode omitir os parâmetros já informados na declaração 'forward'
+                self.symbol_table.extend(expected);
+            } else if !Self::same_parameters(&expected, &parameters) {
+                let forward = self.symbol_table[index].as_token().unwrap();
+                return Err(Diagnostic::at(&token, format!(
+                    "The header of procedure '{}' at line {} column {} doesn't match its forward declaration at line {} column {}.",
+                    token.get_lexeme(),
+                    token.get_line(),
+                    token.get_column(),
+                    forward.get_line(),
+                    forward.get_column()
+                )));
+            }
+            self.symbol_table[index].set_forward(false);
+        } else {
+            self.symbol_table[index].set_parameters(parameters);
+        }
+        self.mark_parameters();
+
+        let start = self.items.len();
+        self.constants_declaration()?;
+        self.types_declaration()?;
         self.vars_declaration()?;
 
         self.subprograms_declaration()?;
+        self.check_forward_declarations()?;
 
         self.compound_command()?;
+        let body = self.pop_block();
+        let parameters = self.symbol_table[index].get_parameters();
+        let declarations = self.items.split_off(start);
+        self.items.push(Declaration::Procedure(Rc::new(Procedure {
+            name: token.get_lexeme().to_ascii_lowercase(),
+            parameters: parameters
+                .iter()
+                .map(|parameter| {
+                    let name = parameter
+                        .as_token()
+                        .unwrap()
+                        .get_lexeme()
+                        .to_ascii_lowercase();
+                    (name, parameter.get_type().unwrap_or_default())
+                })
+                .collect(),
+            declarations,
+            body,
+        })));
+        self.check_unused(self.scope_start(), parameters.len(), token.get_lexeme());
         self.remove_scope();
         Ok(())
     }
 
-    fn arguments(&mut self) -> Result<(), String> {
+    fn arguments(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("arguments");
         let next = self.peek()?;
 
-        if next.is_type_of(Delimiter) && next.get_lexeme() == "(" {
+        if next.is_type_of(Delimiter) && next.is_lexeme("(") {
             self.consume(Delimiter, "(")?;
 
             self.list_of_parameters()?;
@@ -201,201 +943,847 @@ impl Parser {
         Ok(())
     }
 
-    fn list_of_parameters(&mut self) -> Result<(), String> {
+    fn list_of_parameters(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("list_of_parameters");
         self.list_of_identifiers()?;
 
         self.consume(Delimiter, ":")?;
 
-        self.types()?;
+        let symbol_type = self.types()?;
+        self.update_symbols_type(&symbol_type)?;
 
         self.list_of_parameters_prime()?;
 
         Ok(())
     }
 
-    fn list_of_parameters_prime(&mut self) -> Result<(), String> {
-        let next = self.peek()?;
-        if next.is_type_of(Delimiter) && next.get_lexeme() == ";" {
+    fn list_of_parameters_prime(&mut self) -> Result<(), Diagnostic> {
+        let mut productions = vec![self.production("list_of_parameters'")];
+        let mut next = self.peek()?;
+        while next.is_type_of(Delimiter) && next.is_lexeme(";") {
             self.consume(Delimiter, ";")?;
             self.list_of_identifiers()?;
 
             self.consume(Delimiter, ":")?;
 
-            self.types()?;
+            let symbol_type = self.types()?;
+            self.update_symbols_type(&symbol_type)?;
 
-            self.list_of_parameters_prime()?;
-        } else if next.get_lexeme() != ")" {
-            return Err(format!(
-                "Expected ';'. Instead got {} of type {} at line {} column {}.",
-                next.get_lexeme(),
-                next.get_type(),
-                next.get_line(),
-                next.get_column()
+            productions.push(self.production("list_of_parameters'"));
+            next = self.peek()?;
+        }
+        if !next.is_lexeme(")") {
+            return Err(Diagnostic::at(
+                &next,
+                format!(
+                    "Expected ';'. Instead got {} of type {} at line {} column {}.",
+                    next.get_lexeme(),
+                    next.get_type(),
+                    next.get_line(),
+                    next.get_column()
+                ),
             ));
         }
         Ok(())
     }
 
-    fn compound_command(&mut self) -> Result<(), String> {
+    fn compound_command(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("compound_command");
         self.consume(Keyword, "begin")?;
-
+        let start = self.statements.len();
         self.optional_commands()?;
 
         self.consume(Keyword, "end")?;
+        let body = self.statements.split_off(start);
+        self.statements.push(Statement::Block(body));
 
         Ok(())
     }
 
-    fn optional_commands(&mut self) -> Result<(), String> {
+    fn optional_commands(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("optional_commands");
         let value = self.peek()?;
-        let lexeme = value.get_lexeme();
-        if (value.is_type_of(Keyword)
-            && (lexeme == "if" || lexeme == "var" || lexeme == "while" || lexeme == "begin"))
-            || value.is_type_of(Identifier)
-        {
+        if Self::starts_command(&value) {
             self.list_of_commands()?;
+        } else {
+            self.terminated = false;
         }
         Ok(())
     }
 
-    fn list_of_commands(&mut self) -> Result<(), String> {
+    fn starts_command(value: &Token) -> bool {
+        (value.is_type_of(Keyword)
+            && (value.is_lexeme("if")
+                || value.is_lexeme("var")
+                || value.is_lexeme("while")
+                || value.is_lexeme("for")
+                || value.is_lexeme("repeat")
+                || value.is_lexeme("case")
+                || value.is_lexeme("break")
+                || value.is_lexeme("continue")
+                || value.is_lexeme("exit")
+                || value.is_lexeme("begin")))
+            || value.is_type_of(Identifier)
+    }
+
+    fn list_of_commands(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("list_of_commands");
         self.commands()?;
-        self.list_of_commands_prime()?;
+        self.list_of_commands_prime(self.terminated)?;
         Ok(())
     }
 
-    fn list_of_commands_prime(&mut self) -> Result<(), String> {
-        let next = self.peek()?;
-
-        if next.get_lexeme() == ";" {
+    // 'terminated' indica que o comando anterior encerra o fluxo, o que torna o próximo inalcançável
+    // Ao fim, 'terminated' indica se algum dos comandos da lista encerra o fluxo
+    fn list_of_commands_prime(&mut self, terminated: bool) -> Result<(), Diagnostic> {
+        let mut productions = vec![self.production("list_of_commands'")];
+        let (mut previous, mut any) = (terminated, terminated);
+        while self.peek()?.is_lexeme(";") {
             self.consume(Delimiter, ";")?;
+            let next = self.peek()?;
+            if previous && Self::starts_command(&next) {
+                self.warn(
+                    &next,
+                    format!(
+                        "Unreachable statement at line {} column {}.",
+                        next.get_line(),
+                        next.get_column()
+                    ),
+                );
+            }
             self.commands()?;
-            self.list_of_commands_prime()?;
+            previous = self.terminated;
+            any = any || previous;
+            productions.push(self.production("list_of_commands'"));
+        }
+        self.terminated = any;
+        Ok(())
+    }
+
+    fn commands(&mut self) -> Result<(), Diagnostic> {
+        let depth = self.control_type_stack.len();
+        let start = self.statements.len();
+        self.terminated = false;
+        self.command()?;
+        // Todo comando deixa um nó na árvore, o comando vazio é um bloco sem comandos
+        if self.statements.len() == start {
+            self.statements.push(Statement::Block(vec![]));
         }
+        // Todo comando consome os tipos que empilhou
+        debug_assert_eq!(
+            self.control_type_stack.len(),
+            depth,
+            "Unbalanced type stack {:?}",
+            self.control_type_stack
+        );
+        self.set_elements.clear();
         Ok(())
     }
 
-    fn commands(&mut self) -> Result<(), String> {
+    fn command(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("command");
         let next = self.peek()?;
 
         if next.is_type_of(Identifier) {
             self.command_prime()?;
-        } else if next.get_lexeme() == "begin" {
+        } else if next.is_lexeme("for") {
+            self.for_command()?;
+        } else if next.is_lexeme("case") {
+            self.case_command()?;
+        } else if next.is_lexeme("begin") {
             self.compound_command()?;
-        } else if next.get_lexeme() == "if" {
+        } else if next.is_lexeme("if") {
             self.consume(Keyword, "if")?;
-            self.expression()?;
-            self.consume(Keyword, "then")?;
+            let constant = self.condition(&next)?;
+            let then = self.consume(Keyword, "then")?;
+            if constant == Some(false) {
+                self.warn_never_executed("then", &then);
+            }
+            let before = self.assigned_state();
+            let start = self.statements.len();
             self.commands()?;
+            let then_state = (self.assigned_state(), self.terminated);
+            self.restore_assigned(&before);
+
+            let otherwise = self.peek()?;
+            if otherwise.is_lexeme("else") && constant == Some(true) {
+                self.warn_never_executed("else", &otherwise);
+            }
+            self.terminated = false;
             self.else_part()?;
-        } else if next.get_lexeme() == "while" {
+            let else_state = self.assigned_state();
+            self.restore_assigned(&Self::merge_assigned(&[then_state.0, else_state]));
+            self.terminated = then_state.1 && self.terminated;
+
+            let mut branches = self.statements.split_off(start);
+            let otherwise = (branches.len() > 1).then(|| Box::new(branches.pop().unwrap()));
+            let then = Box::new(branches.pop().unwrap());
+            let condition = self.pop_expression();
+            self.statements
+                .push(Statement::If(condition, then, otherwise));
+        } else if next.is_lexeme("while") {
             self.consume(Keyword, "while")?;
-            self.expression()?;
-            self.consume(Keyword, "do")?;
-            self.commands()?;
+            let constant = self.condition(&next)?;
+            let body = self.consume(Keyword, "do")?;
+            if constant == Some(false) {
+                self.warn_never_executed("do", &body);
+            }
+            self.loop_body()?;
+            let body = Box::new(self.pop_statement());
+            let condition = self.pop_expression();
+            self.statements.push(Statement::While(condition, body));
+        } else if next.is_lexeme("repeat") {
+            self.consume(Keyword, "repeat")?;
+            self.loop_depth += 1;
+            let start = self.statements.len();
+            self.optional_commands()?;
+            self.loop_depth -= 1;
+            let until = self.consume(Keyword, "until")?;
+            self.condition(&until)?;
+            self.terminated = false;
+            let body = self.statements.split_off(start);
+            let condition = self.pop_expression();
+            self.statements.push(Statement::Repeat(body, condition));
+        } else if next.is_lexeme("break") || next.is_lexeme("continue") {
+            self.advance();
+            if self.loop_depth == 0 {
+                return Err(Diagnostic::at(&next, format!(
+                    "'{}' at line {} column {} must be used inside a 'while', 'for' or 'repeat' loop.",
+                    next.get_lexeme(),
+                    next.get_line(),
+                    next.get_column()
+                )));
+            }
+            self.mark_unreachable();
+            self.terminated = true;
+            self.statements.push(if next.is_lexeme("break") {
+                Statement::Break
+            } else {
+                Statement::Continue
+            });
+        } else if next.is_lexeme("exit") {
+            self.consume(Keyword, "exit")?;
+            self.mark_unreachable();
+            self.terminated = true;
+            self.statements.push(Statement::Exit);
+        }
+        Ok(())
+    }
+
+    // Retorna o valor da condição quando ela é uma constante, como em 'while false do'
+    fn condition(&mut self, keyword: &Token) -> Result<Option<bool>, Diagnostic> {
+        let constant = self
+            .constant_ahead()
+            .filter(|(token, _)| token.is_type_of(Boolean))
+            .map(|(_, value)| value == 1);
+        self.expression()?;
+        let found = self.control_type_stack.pop().unwrap_or_default();
+        if *found.host() != Type::Boolean {
+            return Err(Diagnostic::at(
+                keyword,
+                format!(
+                    "Invalid '{}' at line {} column {}: condition must be boolean, found {}.",
+                    keyword.get_lexeme(),
+                    keyword.get_line(),
+                    keyword.get_column(),
+                    found
+                ),
+            ));
+        }
+        Ok(constant)
+    }
+
+    fn warn_never_executed(&mut self, branch: &str, token: &Token) {
+        self.warn(
+            token,
+            format!(
+                "The commands after '{}' at line {} column {} are never executed.",
+                branch,
+                token.get_line(),
+                token.get_column()
+            ),
+        );
+    }
+
+    // O corpo pode não ser executado, então as atribuições feitas nele não valem depois do laço
+    fn loop_body(&mut self) -> Result<(), Diagnostic> {
+        let before = self.assigned_state();
+        self.loop_depth += 1;
+        let result = self.commands();
+        self.loop_depth -= 1;
+        self.restore_assigned(&before);
+        self.terminated = false;
+        result
+    }
+
+    fn for_command(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("for_command");
+        self.consume(Keyword, "for")?;
+
+        let token = self.consume_identifiers()?;
+        let symbol = self.find_symbol(&Symbol::new(token.to_owned()))?;
+        let control_type = symbol.get_type().unwrap();
+        if symbol.get_kind() != Some(SymbolKind::Variable) || !control_type.is_ordinal() {
+            return Err(Diagnostic::at(&token, format!(
+                "The control variable '{}' at line {} column {} must be a variable of an ordinal type, found '{}'.",
+                token.get_lexeme(),
+                token.get_line(),
+                token.get_column(),
+                control_type
+            )));
+        }
+
+        let name = token.get_lexeme().to_ascii_lowercase();
+        self.push_expression(ExpressionKind::Variable(name), &control_type, &token);
+        let assignment = self.consume(Assignment, ":=")?;
+        self.control_type_stack.push(control_type.to_owned());
+        self.expression()?;
+        self.check_atribuation(assignment.get_line(), assignment.get_column())?;
+        self.mark_assigned(&token);
+
+        let next = self.peek()?;
+        if !(next.is_type_of(Keyword) && (next.is_lexeme("to") || next.is_lexeme("downto"))) {
+            return Err(Diagnostic::at(
+                &next,
+                format!(
+                    "Expected 'to' or 'downto'. Instead got '{}' of type {} at line {} column {}.",
+                    next.get_lexeme(),
+                    next.get_type(),
+                    next.get_line(),
+                    next.get_column()
+                ),
+            ));
+        }
+        self.advance();
+        self.control_type_stack.push(control_type);
+        self.expression()?;
+        self.check_atribuation(next.get_line(), next.get_column())?;
+
+        self.consume(Keyword, "do")?;
+        self.loop_body()?;
+
+        let body = Box::new(self.pop_statement());
+        let end = self.pop_expression();
+        let start = self.pop_expression();
+        let variable = self.pop_expression();
+        self.statements.push(Statement::For {
+            variable,
+            start,
+            end,
+            downto: next.is_lexeme("downto"),
+            body,
+        });
+        Ok(())
+    }
+
+    fn case_command(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("case_command");
+        let case = self.consume(Keyword, "case")?;
+        self.expression()?;
+        let selector = self.control_type_stack.pop().unwrap_or_default();
+        if !selector.is_ordinal() {
+            return Err(Diagnostic::at(
+                &case,
+                format!(
+                    "The case selector at line {} column {} must be of an ordinal type, found '{}'.",
+                    case.get_line(),
+                    case.get_column(),
+                    selector
+                ),
+            ));
+        }
+        self.consume(Keyword, "of")?;
+
+        // Cada elemento é um caminho, sem 'else' também é possível não executar nenhum deles
+        let before = self.assigned_state();
+        let mut paths = vec![];
+        let mut labels = vec![];
+        let start = self.statements.len();
+        self.case_element(&selector, &mut labels, &mut paths)?;
+        self.list_of_case_elements_prime(&selector, &mut labels, &mut paths)?;
+        let elements = labels
+            .into_iter()
+            .zip(self.statements.split_off(start))
+            .collect();
+
+        let next = self.peek()?;
+        let otherwise = if next.is_type_of(Keyword) && next.is_lexeme("else") {
+            self.consume(Keyword, "else")?;
+            self.optional_commands()?;
+            paths.push((self.assigned_state(), self.terminated));
+            Some(self.statements.split_off(start))
+        } else {
+            paths.push((before, false));
+            None
+        };
+        let (states, terminated): (Vec<_>, Vec<_>) = paths.into_iter().unzip();
+        self.restore_assigned(&Self::merge_assigned(&states));
+        // O case só encerra o fluxo se todos os caminhos encerram
+        self.terminated = terminated.iter().all(|value| *value);
+
+        self.consume(Keyword, "end")?;
+        let selector = self.pop_expression();
+        self.statements.push(Statement::Case {
+            selector,
+            elements,
+            otherwise,
+        });
+        Ok(())
+    }
+
+    fn list_of_case_elements_prime(
+        &mut self,
+        selector: &Type,
+        labels: &mut Vec<Vec<(i64, i64)>>,
+        paths: &mut Vec<(Vec<bool>, bool)>,
+    ) -> Result<(), Diagnostic> {
+        let mut productions = vec![self.production("list_of_case_elements'")];
+        while self.peek()?.is_lexeme(";") {
+            self.consume(Delimiter, ";")?;
+            let next = self.peek()?;
+            if next.is_lexeme("else") || next.is_lexeme("end") {
+                break;
+            }
+            self.case_element(selector, labels, paths)?;
+            productions.push(self.production("list_of_case_elements'"));
+        }
+        Ok(())
+    }
+
+    // 'paths' recebe o estado das atribuições ao fim do comando do elemento e se ele encerra o fluxo
+    fn case_element(
+        &mut self,
+        selector: &Type,
+        labels: &mut Vec<Vec<(i64, i64)>>,
+        paths: &mut Vec<(Vec<bool>, bool)>,
+    ) -> Result<(), Diagnostic> {
+        let _production = self.production("case_element");
+        let before = self.assigned_state();
+        let mut element = vec![];
+        loop {
+            let label = self.case_label(selector, labels, &element)?;
+            element.push(label);
+
+            if !self.peek()?.is_lexeme(",") {
+                break;
+            }
+            self.consume(Delimiter, ",")?;
         }
+
+        self.consume(Delimiter, ":")?;
+        labels.push(element);
+        self.commands()?;
+        paths.push((self.assigned_state(), self.terminated));
+        self.restore_assigned(&before);
         Ok(())
     }
 
-    fn else_part(&mut self) -> Result<(), String> {
+    // case_label -> constant | constant .. constant, um rótulo simples tem os dois limites iguais
+    fn case_label(
+        &mut self,
+        selector: &Type,
+        labels: &[Vec<(i64, i64)>],
+        element: &[(i64, i64)],
+    ) -> Result<(i64, i64), Diagnostic> {
+        let _production = self.production("case_label");
+        let (token, low) = self.case_constant(selector)?;
+        let high = if self.peek()?.is_lexeme("..") {
+            let range = self.consume(Delimiter, "..")?;
+            let (high_token, high) = self.case_constant(selector)?;
+            if low > high {
+                return Err(Diagnostic::at(
+                    &range,
+                    format!(
+                        "Invalid case range at line {} column {}.\n\
+                        The lower bound '{}' is greater than the upper bound '{}'.",
+                        range.get_line(),
+                        range.get_column(),
+                        token.get_lexeme(),
+                        high_token.get_lexeme()
+                    ),
+                ));
+            }
+            high
+        } else {
+            low
+        };
+
+        let overlaps = labels
+            .iter()
+            .flatten()
+            .chain(element)
+            .any(|(first, last)| low <= *last && *first <= high);
+        if overlaps {
+            return Err(Diagnostic::at(
+                &token,
+                format!(
+                    "Duplicated case label '{}' at line {} column {}.",
+                    token.get_lexeme(),
+                    token.get_line(),
+                    token.get_column()
+                ),
+            ));
+        }
+        Ok((low, high))
+    }
+
+    // Constante de um rótulo, do tipo do seletor e dentro da sua faixa
+    fn case_constant(&mut self, selector: &Type) -> Result<(Token, i64), Diagnostic> {
+        let (token, label_type, value) = self.constant()?;
+        if !self.is_assignable(selector, &label_type) {
+            return Err(Diagnostic::at(
+                &token,
+                format!(
+                    "Invalid case label '{}' at line {} column {}.\n\
+                    Expected a constant of type '{}' but got '{}'.",
+                    token.get_lexeme(),
+                    token.get_line(),
+                    token.get_column(),
+                    selector,
+                    label_type
+                ),
+            ));
+        }
+        self.check_range(selector, value, &token)?;
+        Ok((token, value))
+    }
+
+    fn else_part(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("else_part");
         let next = self.peek()?;
-        if next.get_lexeme() == "else" {
+        if next.is_lexeme("else") {
             self.consume(Keyword, "else")?;
             self.commands()?;
         }
         Ok(())
     }
 
-    fn command_prime(&mut self) -> Result<(), String> {
+    fn command_prime(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("command'");
         let token = self.consume_identifiers()?;
-        let temp = self.find_symbol(&Symbol::new(token))?;
+        let temp = self.find_symbol(&Symbol::new(token.to_owned()))?;
 
-        self.control_type_stack.push(temp.get_type().unwrap());
-        self.command_dual_prime()?;
+        let symbol_type = temp.get_type().unwrap();
+        let target = if temp.is_callable() {
+            symbol_type
+        } else {
+            if self.dereferences_ahead() {
+                self.mark_read(&token);
+            }
+            let name = token.get_lexeme().to_ascii_lowercase();
+            self.push_expression(ExpressionKind::Variable(name), &symbol_type, &token);
+            self.selectors(symbol_type)?
+        };
+        self.control_type_stack.push(target);
+        self.command_dual_prime(&temp, &token)?;
         Ok(())
     }
 
-    fn command_dual_prime(&mut self) -> Result<(), String> {
+    fn command_dual_prime(&mut self, symbol: &Symbol, token: &Token) -> Result<(), Diagnostic> {
+        let _production = self.production("command''");
         let next = self.peek()?;
 
         if next.is_type_of(Assignment) {
+            if matches!(
+                symbol.get_kind(),
+                Some(SymbolKind::Constant) | Some(SymbolKind::Type)
+            ) {
+                return Err(Diagnostic::at(
+                    token,
+                    format!(
+                        "Cannot assign to '{}' at line {} column {}, it is not a variable.",
+                        token.get_lexeme(),
+                        token.get_line(),
+                        token.get_column()
+                    ),
+                ));
+            }
             self.consume(Assignment, ":=")?;
+            let target = self.control_type_stack.last().cloned().unwrap_or_default();
+            let constant = self.constant_ahead();
+            let elements = self.set_elements.len();
             self.expression()?;
             self.check_atribuation(next.get_line(), next.get_column())?;
-        } else if next.get_lexeme() == "(" {
-            self.control_type_stack.pop(); // Pra caso não seja um assignment
-            self.consume(Delimiter, "(")?;
-            self.list_of_expressions()?;
-            self.consume(Delimiter, ")")?;
-        } else if next.get_lexeme() == "=" {
-            return Err(format!(
-                "Invalid operator. Got '=' at line {} column {}, didn't you mean ':='?",
-                next.get_line(),
-                next.get_column()
+            self.mark_assigned(token);
+            if let Some((token, value)) = constant {
+                self.check_range(&target, value, &token)?;
+            }
+            // Os elementos constantes dos conjuntos atribuídos devem estar no tipo base do destino
+            let elements = self.set_elements.split_off(elements);
+            if let Type::Set(base) = target.host() {
+                for (token, value) in elements {
+                    self.check_range(base, value, &token)?;
+                }
+            }
+            let value = self.pop_expression();
+            let target = self.pop_expression();
+            self.statements.push(Statement::Assignment(target, value));
+        } else if next.is_lexeme("=") {
+            return Err(Diagnostic::at(
+                &next,
+                format!(
+                    "Invalid operator. Got '=' at line {} column {}, didn't you mean ':='?",
+                    next.get_line(),
+                    next.get_column()
+                ),
             ));
+        } else {
+            self.control_type_stack.pop(); // Pra caso não seja um assignment
+            if !symbol.is_callable() {
+                self.expressions.pop();
+            }
+            self.procedure_activation_prime(symbol, token)?;
+            if let ExpressionKind::Call(name, arguments) = self.pop_expression().kind {
+                let (line, column) = (token.get_line(), token.get_column());
+                self.statements
+                    .push(Statement::Call(name, arguments, line, column));
+            }
         }
-        // self.control_type_stack.pop(); // Pra caso não seja um assignment
         Ok(())
     }
 
-    fn procedure_activation(&mut self) -> Result<(), String> {
+    // Retorna o tipo resultante da ativação
+    fn procedure_activation(&mut self) -> Result<Type, Diagnostic> {
+        let _production = self.production("procedure_activation");
         let token = self.consume_identifiers()?;
-        self.find_symbol(&Symbol::new(token))?;
-        self.procedure_activation_prime()?;
-        Ok(())
+        let symbol = self.find_symbol(&Symbol::new(token.to_owned()))?;
+        self.mark_read(&token);
+        if symbol.get_kind() == Some(SymbolKind::Type) {
+            return Err(Diagnostic::at(
+                &token,
+                format!(
+                    "'{}' at line {} column {} is a type, not a value.",
+                    token.get_lexeme(),
+                    token.get_line(),
+                    token.get_column()
+                ),
+            ));
+        }
+        let activation_type = self.procedure_activation_prime(&symbol, &token)?;
+        if symbol.is_callable() {
+            Ok(activation_type)
+        } else {
+            self.selectors(activation_type)
+        }
     }
 
-    fn procedure_activation_prime(&mut self) -> Result<(), String> {
+    fn procedure_activation_prime(
+        &mut self,
+        symbol: &Symbol,
+        token: &Token,
+    ) -> Result<Type, Diagnostic> {
+        let _production = self.production("procedure_activation'");
         let next = self.peek()?;
-        if next.is_type_of(Delimiter) && next.get_lexeme() == "(" {
+        let mut amount = 0;
+        if next.is_type_of(Delimiter) && next.is_lexeme("(") {
+            // new(p) atribui um valor ao ponteiro p
+            let ahead = self.peek_second()?;
+            if symbol.is_callable() && token.is_lexeme("new") && ahead.is_type_of(Identifier) {
+                self.mark_assigned(&ahead);
+            }
             self.consume(Delimiter, "(")?;
-            self.list_of_expressions()?;
+            amount = self.list_of_expressions()?;
             self.consume(Delimiter, ")")?;
         }
-        Ok(())
+
+        let symbol = &self.resolve_overload(symbol, amount);
+        let mut symbol_type = symbol.get_type().unwrap();
+        let name = token.get_lexeme().to_ascii_lowercase();
+        if symbol.is_callable() {
+            self.mark_called(token);
+            let arguments = self.check_arguments(symbol, token, amount)?;
+            // 'ordinal' como retorno indica o mesmo tipo do argumento, como em succ e pred
+            if symbol.get_generic() == Some(Generic::Ordinal) {
+                symbol_type = arguments[0].host().to_owned();
+            }
+            let start = self.expressions.len() - amount;
+            let arguments = self.expressions.split_off(start);
+            self.push_expression(ExpressionKind::Call(name, arguments), &symbol_type, token);
+        } else if amount > 0 {
+            return Err(Diagnostic::at(
+                token,
+                format!(
+                    "'{}' at line {} column {} is not a procedure or function.",
+                    token.get_lexeme(),
+                    token.get_line(),
+                    token.get_column()
+                ),
+            ));
+        } else {
+            let kind = match self.declared_value(token) {
+                Some(value) => ExpressionKind::Literal(value),
+                None => ExpressionKind::Variable(name),
+            };
+            self.push_expression(kind, &symbol_type, token);
+        }
+        Ok(symbol_type)
     }
 
-    fn list_of_expressions(&mut self) -> Result<(), String> {
+    // Acesso a elementos de arrays: id[expression][expression]...
+    fn selectors(&mut self, symbol_type: Type) -> Result<Type, Diagnostic> {
+        let _production = self.production("selectors");
+        let next = self.peek()?;
+        if next.is_lexeme("^") {
+            return self.dereference(symbol_type);
+        } else if next.is_lexeme(".") && matches!(symbol_type, Type::Record(_)) {
+            return self.field_access(symbol_type);
+        } else if !next.is_lexeme("[") {
+            return Ok(symbol_type);
+        }
+
+        let (index, element) = match symbol_type {
+            Type::Array(index, element) => (*index, *element),
+            _ => {
+                return Err(Diagnostic::at(
+                    &next,
+                    format!(
+                        "Cannot index a value of type '{}' at line {} column {}.",
+                        symbol_type,
+                        next.get_line(),
+                        next.get_column()
+                    ),
+                ))
+            }
+        };
+
+        self.consume(Delimiter, "[")?;
+        let constant = self.constant_ahead();
         self.expression()?;
-        self.list_of_expressions_prime()?;
-        Ok(())
+        let found = self.control_type_stack.pop().unwrap_or_default();
+        if !self.is_assignable(&index, &found) {
+            return Err(Diagnostic::at(
+                &next,
+                format!(
+                    "Invalid index at line {} column {}.\n\
+                    Expected a value of type '{}' but got '{}'.",
+                    next.get_line(),
+                    next.get_column(),
+                    index,
+                    found
+                ),
+            ));
+        }
+        if let Some((token, value)) = constant {
+            self.check_range(&index, value, &token)?;
+        }
+        self.consume(Delimiter, "]")?;
+        let position = self.pop_expression();
+        self.wrap_expression(
+            |array| ExpressionKind::Index(array, Box::new(position)),
+            &element,
+            &next,
+        );
+
+        self.selectors(element)
     }
 
-    fn list_of_expressions_prime(&mut self) -> Result<(), String> {
-        let next = self.peek()?;
+    fn dereference(&mut self, symbol_type: Type) -> Result<Type, Diagnostic> {
+        let caret = self.consume(Delimiter, "^")?;
+        match symbol_type {
+            Type::Pointer(target) => {
+                let target = self.resolve_type(*target);
+                self.wrap_expression(ExpressionKind::Dereference, &target, &caret);
+                self.selectors(target)
+            }
+            _ => Err(Diagnostic::at(
+                &caret,
+                format!(
+                    "Cannot dereference a value of type '{}' at line {} column {}.",
+                    symbol_type,
+                    caret.get_line(),
+                    caret.get_column()
+                ),
+            )),
+        }
+    }
+
+    fn field_access(&mut self, symbol_type: Type) -> Result<Type, Diagnostic> {
+        let dot = self.consume(Delimiter, ".")?;
+        let token = self.consume_identifiers()?;
 
-        if next.is_type_of(Delimiter) && next.get_lexeme() == "," {
+        let field = match &symbol_type {
+            Type::Record(fields) => fields.iter().find(|(name, _)| token.is_lexeme(name)),
+            _ => None,
+        };
+        match field.cloned() {
+            None => Err(Diagnostic::at(
+                &token,
+                format!(
+                    "Unknown field '{}' at line {} column {} in a value of type '{}'.",
+                    token.get_lexeme(),
+                    token.get_line(),
+                    token.get_column(),
+                    symbol_type
+                ),
+            )),
+            Some((name, field_type)) => {
+                self.wrap_expression(
+                    |record| ExpressionKind::Field(record, name),
+                    &field_type,
+                    &dot,
+                );
+                self.selectors(field_type)
+            }
+        }
+    }
+
+    // Retorna a quantidade de expressões, cada uma deixa o seu tipo na pilha de controle
+    fn list_of_expressions(&mut self) -> Result<usize, Diagnostic> {
+        let _production = self.production("list_of_expressions");
+        self.expression()?;
+        let amount = self.list_of_expressions_prime()?;
+        Ok(amount + 1)
+    }
+
+    fn list_of_expressions_prime(&mut self) -> Result<usize, Diagnostic> {
+        let mut productions = vec![self.production("list_of_expressions'")];
+        let mut amount = 0;
+        while self.peek()?.is_type_of(Delimiter) && self.peek()?.is_lexeme(",") {
             self.consume(Delimiter, ",")?;
             self.expression()?;
-            self.list_of_expressions_prime()?;
+            amount += 1;
+            productions.push(self.production("list_of_expressions'"));
         }
-        Ok(())
+        Ok(amount)
     }
 
-    fn expression(&mut self) -> Result<(), String> {
+    fn expression(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("expression");
+        self.evaluate_ahead()?;
         self.simple_expression()?;
         self.expression_prime()?;
         Ok(())
     }
 
-    fn expression_prime(&mut self) -> Result<(), String> {
+    fn expression_prime(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("expression'");
         let next = self.peek()?;
 
         if next.is_type_of(RelationalOperators) {
-            self.relational_op()?;
+            let operator = self.relational_op()?;
             self.simple_expression()?;
-            self.check_relational(next.get_line(), next.get_column())?; // TODO
+            self.check_relational(operator.get_lexeme(), next.get_line(), next.get_column())?;
+            self.combine(&operator);
         }
 
         Ok(())
     }
 
-    fn simple_expression(&mut self) -> Result<(), String> {
+    fn simple_expression(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("simple_expression");
         let next = self.peek()?;
 
-        if next.get_lexeme() == "+" || next.get_lexeme() == "-" {
-            self.signal()?;
+        if next.is_lexeme("+") || next.is_lexeme("-") {
+            let signal = self.signal()?;
             self.term()?;
+            if signal.is_lexeme("-") {
+                let value_type = self.control_type_stack.last().cloned().unwrap_or_default();
+                self.wrap_expression(
+                    |operand| ExpressionKind::Unary("-".to_string(), operand),
+                    &value_type,
+                    &signal,
+                );
+            }
             self.simple_expression_prime()?;
         } else {
             self.term()?;
@@ -404,100 +1792,225 @@ impl Parser {
         Ok(())
     }
 
-    fn simple_expression_prime(&mut self) -> Result<(), String> {
-        let next = self.peek()?;
-
-        if next.is_type_of(AdditiveOperators) {
-            self.additive_op()?;
+    fn simple_expression_prime(&mut self) -> Result<(), Diagnostic> {
+        let mut productions = vec![self.production("simple_expression'")];
+        loop {
+            let next = self.peek()?;
+            if !next.is_type_of(AdditiveOperators) {
+                return Ok(());
+            }
+            let operator = self.additive_op()?;
             self.term()?;
-            self.check_arithmetics(next.get_line(), next.get_column())?;
-            self.simple_expression_prime()?;
+            if operator.is_lexeme("+") && self.is_textual_operation() {
+                self.check_concatenation();
+            } else if operator.is_lexeme("or") {
+                self.check_logic(next.get_line(), next.get_column())?;
+            } else {
+                self.check_arithmetics(operator.get_lexeme(), next.get_line(), next.get_column())?;
+            }
+            self.combine(&operator);
+            productions.push(self.production("simple_expression'"));
         }
-        Ok(())
     }
 
-    fn signal(&mut self) -> Result<(), String> {
-        match self.tokens_buffer.pop() {
-            None => return Err("Syntactic error. Unexpected end of file.".to_string()),
+    fn signal(&mut self) -> Result<Token, Diagnostic> {
+        let _production = self.production("signal");
+        match self.advance() {
+            None => Err("Syntactic error. Unexpected end of file.".into()),
             Some(value) => {
-                if !(value.get_lexeme() == "+" || value.get_lexeme() == "-") {
-                    return Err(format!("Expected a signal '+' or '-'. Instead got {} of type {} at line {} column {}.",
-                    value.get_lexeme(), value.get_type(), value.get_line(), value.get_column()));
+                if !(value.is_lexeme("+") || value.is_lexeme("-")) {
+                    return Err(Diagnostic::at(&value, format!("Expected a signal '+' or '-'. Instead got {} of type {} at line {} column {}.",
+                    value.get_lexeme(), value.get_type(), value.get_line(), value.get_column())));
                 }
+                Ok(value)
             }
         }
-        Ok(())
     }
 
-    fn term(&mut self) -> Result<(), String> {
+    fn term(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("term");
         self.factor()?;
         self.term_prime()?;
         Ok(())
     }
 
-    fn term_prime(&mut self) -> Result<(), String> {
-        let next = self.peek()?;
-        if next.is_type_of(MultiplicativeOperators) {
+    fn term_prime(&mut self) -> Result<(), Diagnostic> {
+        let mut productions = vec![self.production("term'")];
+        loop {
+            let next = self.peek()?;
+            if !next.is_type_of(MultiplicativeOperators) {
+                return Ok(());
+            }
             let operator = self.multiplicative_op()?;
             self.factor()?;
-            self.term_prime()?;
-            if operator.get_lexeme() == "and" {
+            // Os operadores associam à esquerda, como em 8 / 2 * 2
+            if operator.is_lexeme("and") {
                 self.check_logic(next.get_line(), next.get_column())?;
             } else {
-                self.check_arithmetics(next.get_line(), next.get_column())?;
+                self.check_arithmetics(operator.get_lexeme(), next.get_line(), next.get_column())?;
             }
+            self.combine(&operator);
+            productions.push(self.production("term'"));
         }
-        Ok(())
     }
 
-    fn factor(&mut self) -> Result<(), String> {
+    fn factor(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("factor");
         let next = self.peek()?;
 
         if next.is_type_of(Identifier) {
-            self.procedure_activation()?;
-            let temp = self.find_symbol(&Symbol::new(next.to_owned()))?;
-            let symbol_type = temp.get_type().unwrap();
-            self.control_type_stack.push(symbol_type);
+            let activation_type = self.procedure_activation()?;
+            self.control_type_stack.push(activation_type);
         } else if next.is_type_of(Integer) {
-            self.consume_by_type(Integer)?;
-            self.control_type_stack.push("integer".to_string());
+            let token = self.consume_by_type(Integer)?;
+            let value = parse_integer(token.get_lexeme()).unwrap_or_default();
+            self.literal(Value::Integer(value), &token);
         } else if next.is_type_of(Real) {
-            self.consume_by_type(Real)?;
-            self.control_type_stack.push("real".to_string());
+            let token = self.consume_by_type(Real)?;
+            let value = token.get_lexeme().parse().unwrap_or_default();
+            self.literal(Value::Real(value), &token);
         } else if next.is_type_of(Boolean) {
-            self.consume_by_type(Boolean)?;
-            self.control_type_stack.push("boolean".to_string());
-        } else if next.get_lexeme() == "(" {
+            let token = self.consume_by_type(Boolean)?;
+            self.literal(Value::Boolean(token.is_lexeme("true")), &token);
+        } else if next.is_type_of(Char) {
+            let token = self.consume_by_type(Char)?;
+            let value = unquote(token.get_lexeme())
+                .chars()
+                .next()
+                .unwrap_or_default();
+            self.literal(Value::Char(value), &token);
+        } else if next.is_type_of(Text) {
+            let token = self.consume_by_type(Text)?;
+            self.literal(Value::Text(unquote(token.get_lexeme())), &token);
+        } else if next.is_lexeme("(") {
             self.consume(Delimiter, "(")?;
             self.expression()?;
             self.consume(Delimiter, ")")?;
-        } else if next.is_type_of(Keyword) && next.get_lexeme() == "not" {
+        } else if next.is_lexeme("[") {
+            self.set_constructor()?;
+        } else if next.is_type_of(Keyword) && next.is_lexeme("nil") {
+            self.consume(Keyword, "nil")?;
+            self.control_type_stack.push(Type::Nil);
+            self.push_expression(ExpressionKind::Nil, &Type::Nil, &next);
+        } else if next.is_type_of(Keyword) && next.is_lexeme("not") {
             self.consume(Keyword, "not")?;
-            self.control_type_stack.push("boolean".to_string());
+            self.control_type_stack.push(Type::Boolean);
             self.factor()?;
             self.check_logic(next.get_line(), next.get_column())?;
+            self.wrap_expression(
+                |operand| ExpressionKind::Unary("not".to_string(), operand),
+                &Type::Boolean,
+                &next,
+            );
+        } else {
+            return Err(Diagnostic::at(
+                &next,
+                format!(
+                    "Expected an expression. Instead got '{}' of type {} at line {} column {}.",
+                    next.get_lexeme(),
+                    next.get_type(),
+                    next.get_line(),
+                    next.get_column()
+                ),
+            ));
         }
         Ok(())
     }
 
-    fn relational_op(&mut self) -> Result<(), String> {
-        self.consume_by_type(RelationalOperators)?;
+    // set_constructor -> [ ] | [ set_element {, set_element} ], set_element -> expression [.. expression]
+    fn set_constructor(&mut self) -> Result<(), Diagnostic> {
+        let _production = self.production("set_constructor");
+        let bracket = self.consume(Delimiter, "[")?;
+
+        let mut element_type: Option<Type> = None;
+        let mut elements = vec![];
+        if !self.peek()?.is_lexeme("]") {
+            loop {
+                self.set_element()?;
+                let low = self.control_type_stack.pop().unwrap_or_default();
+                let found = low.host().to_owned();
+                // Um intervalo de elementos, como em [1, 3..5], tem os dois limites do mesmo tipo
+                let range = if self.peek()?.is_lexeme("..") {
+                    self.consume(Delimiter, "..")?;
+                    self.set_element()?;
+                    let high = self.control_type_stack.pop().unwrap_or_default();
+                    let high_node = self.pop_expression();
+                    elements.push((self.pop_expression(), Some(high_node)));
+                    high.host().to_owned()
+                } else {
+                    elements.push((self.pop_expression(), None));
+                    found.to_owned()
+                };
+
+                let expected = element_type.get_or_insert(found.to_owned());
+                if !found.is_ordinal() || *expected != found || range != found {
+                    return Err(Diagnostic::at(&bracket, format!(
+                        "Invalid set element of type '{}' in the set starting at line {} column {}.\n\
+                            The elements must be of the same ordinal type.",
+                        found,
+                        bracket.get_line(),
+                        bracket.get_column()
+                    )));
+                }
+
+                if !self.peek()?.is_lexeme(",") {
+                    break;
+                }
+                self.consume(Delimiter, ",")?;
+            }
+        }
+        self.consume(Delimiter, "]")?;
+
+        let set_type = match element_type {
+            None => Type::EmptySet,
+            Some(base) => Type::Set(Box::new(base)),
+        };
+        self.push_expression(ExpressionKind::Set(elements), &set_type, &bracket);
+        self.control_type_stack.push(set_type);
         Ok(())
     }
 
-    fn additive_op(&mut self) -> Result<Token, String> {
+    // Elemento ou limite de um intervalo do conjunto, guardado quando é constante
+    fn set_element(&mut self) -> Result<(), Diagnostic> {
+        if let Some(constant) = self.constant_ahead() {
+            self.set_elements.push(constant);
+        }
+        self.expression()
+    }
+
+    fn literal(&mut self, value: Value, token: &Token) {
+        let value_type = value.get_type();
+        self.push_expression(ExpressionKind::Literal(value), &value_type, token);
+        self.control_type_stack.push(value_type);
+    }
+
+    fn relational_op(&mut self) -> Result<Token, Diagnostic> {
+        let _production = self.production("relational_op");
+        self.consume_by_type(RelationalOperators)
+    }
+
+    fn additive_op(&mut self) -> Result<Token, Diagnostic> {
+        let _production = self.production("additive_op");
         self.consume_by_type(AdditiveOperators)
     }
 
-    fn multiplicative_op(&mut self) -> Result<Token, String> {
+    fn multiplicative_op(&mut self) -> Result<Token, Diagnostic> {
+        let _production = self.production("multiplicative_op");
         self.consume_by_type(MultiplicativeOperators)
     }
 }
 
 impl Parser {
-    fn peek(&self) -> Result<Token, String> {
+    fn peek(&self) -> Result<Token, Diagnostic> {
         match self.tokens_buffer.last() {
-            None => Err("Syntactic error. Unexpected end of file".to_string()),
+            None => Err("Syntactic error. Unexpected end of file".into()),
+            Some(value) => Ok(value.clone()),
+        }
+    }
+
+    fn peek_second(&self) -> Result<Token, Diagnostic> {
+        match self.tokens_buffer.iter().rev().nth(1) {
+            None => Err("Syntactic error. Unexpected end of file".into()),
             Some(value) => Ok(value.clone()),
         }
     }
@@ -507,19 +2020,22 @@ impl Parser {
         &mut self,
         expected_type: TokenType,
         expected_lexeme: &str,
-    ) -> Result<Token, String> {
-        match self.tokens_buffer.pop() {
-            None => Err("Syntactic error. Unexpected end of file.".to_string()),
+    ) -> Result<Token, Diagnostic> {
+        match self.advance() {
+            None => Err("Syntactic error. Unexpected end of file.".into()),
             Some(value) => {
-                if !(value.is_type_of(expected_type) && value.get_lexeme() == expected_lexeme) {
-                    return Err(format!(
-                        "Expected {} '{}'. Instead got '{}' of type {} at line {} column {}",
-                        expected_type,
-                        expected_lexeme,
-                        value.get_lexeme(),
-                        value.get_type(),
-                        value.get_line(),
-                        value.get_column()
+                if !(value.is_type_of(expected_type) && value.is_lexeme(expected_lexeme)) {
+                    return Err(Diagnostic::at(
+                        &value,
+                        format!(
+                            "Expected {} '{}'. Instead got '{}' of type {} at line {} column {}",
+                            expected_type,
+                            expected_lexeme,
+                            value.get_lexeme(),
+                            value.get_type(),
+                            value.get_line(),
+                            value.get_column()
+                        ),
                     ));
                 }
                 Ok(value)
@@ -527,17 +2043,20 @@ impl Parser {
         }
     }
 
-    fn consume_identifiers(&mut self) -> Result<Token, String> {
-        match self.tokens_buffer.pop() {
-            None => Err("Syntactic error. Unexpected end of file.".to_string()),
+    fn consume_identifiers(&mut self) -> Result<Token, Diagnostic> {
+        match self.advance() {
+            None => Err("Syntactic error. Unexpected end of file.".into()),
             Some(value) => {
                 if !value.is_type_of(Identifier) {
-                    return Err(format!(
-                        "Expected an identifier. Instead got '{}' of type {} at line {} column {}",
-                        value.get_lexeme(),
-                        value.get_type(),
-                        value.get_line(),
-                        value.get_column()
+                    return Err(Diagnostic::at(
+                        &value,
+                        format!(
+                            "Expected an identifier. Instead got '{}' of type {} at line {} column {}",
+                            value.get_lexeme(),
+                            value.get_type(),
+                            value.get_line(),
+                            value.get_column()
+                        ),
                     ));
                 }
                 Ok(value)
@@ -545,18 +2064,21 @@ impl Parser {
         }
     }
 
-    fn consume_by_type(&mut self, expected_type: TokenType) -> Result<Token, String> {
-        match self.tokens_buffer.pop() {
-            None => Err("Syntactic error. Unexpected end of file.".to_string()),
+    fn consume_by_type(&mut self, expected_type: TokenType) -> Result<Token, Diagnostic> {
+        match self.advance() {
+            None => Err("Syntactic error. Unexpected end of file.".into()),
             Some(value) => {
                 if !value.is_type_of(expected_type) {
-                    return Err(format!(
-                        "Expected {}. Instead got '{}' of type {} at line {} column {}",
-                        expected_type,
-                        value.get_lexeme(),
-                        value.get_type(),
-                        value.get_line(),
-                        value.get_column()
+                    return Err(Diagnostic::at(
+                        &value,
+                        format!(
+                            "Expected {}. Instead got '{}' of type {} at line {} column {}",
+                            expected_type,
+                            value.get_lexeme(),
+                            value.get_type(),
+                            value.get_line(),
+                            value.get_column()
+                        ),
                     ));
                 }
                 Ok(value)
@@ -564,199 +2086,628 @@ impl Parser {
         }
     }
 
-    fn add_symbol(&mut self, symbol: Symbol) -> Result<(), String> {
-        if symbol == EOS {
-            panic!("Wrong use of End of Scope");
-        }
-        let mut buffer = self.symbol_table.iter().rev();
-        let mut buffer_symbol = buffer.next().unwrap();
-
-        while buffer_symbol != &EOS {
-            if buffer_symbol == &symbol {
-                return Err(format!(
+    fn add_symbol(&mut self, symbol: Symbol) -> Result<(), Diagnostic> {
+        let Some(token) = symbol.as_token() else {
+            return Err("Wrong use of End of Scope.".into());
+        };
+        // Apenas o escopo atual, os símbolos acima da última marca de fim de escopo
+        let declared = self
+            .symbol_table
+            .iter()
+            .rev()
+            .take_while(|item| **item != EOS)
+            .find(|item| **item == symbol)
+            .and_then(Symbol::as_token);
+        if let Some(declared) = declared {
+            return Err(Diagnostic::at(
+                &declared,
+                format!(
                     "Identifier '{}' already declared in line {} column {}.",
-                    buffer_symbol.as_token().unwrap().get_lexeme(),
-                    buffer_symbol.as_token().unwrap().get_line(),
-                    buffer_symbol.as_token().unwrap().get_column()
-                ));
-            }
-            buffer_symbol = buffer.next().unwrap();
+                    declared.get_lexeme(),
+                    declared.get_line(),
+                    declared.get_column()
+                ),
+            ));
         }
 
+        self.declared.push((token.get_line(), token.get_column()));
         self.symbol_table.push(symbol);
         Ok(())
     }
 
-    fn update_symbols_type(&mut self, symbol_type: &str) {
-        let mut buffer = self.symbol_table.iter_mut().rev();
-        let mut buffer_item = buffer
-            .next()
-            .expect("Something went wrong while updating symbols table type.");
-        while self.amount > 0 {
-            buffer_item.change_type(symbol_type);
-            buffer_item = buffer
-                .next()
-                .expect("Something went wrong while updating symbols table type.");
-            self.amount -= 1;
-        }
+    // Diferencia os símbolos declarados no arquivo dos pré-definidos, que nunca são adicionados, e
+    // dos importados de units, que podem estar na mesma posição de uma declaração local
+    fn is_declared_here(&self, symbol: &Symbol) -> bool {
+        symbol.get_unit().is_none()
+            && symbol.as_token().is_some_and(|token| {
+                self.declared
+                    .contains(&(token.get_line(), token.get_column()))
+            })
     }
 
-    fn find_symbol(&self, symbol: &Symbol) -> Result<Symbol, String> {
-        if symbol == &EOS {
-            panic!("Wrong use of End of Scope");
+    // Os últimos amount símbolos da tabela recebem o tipo
+    fn update_symbols_type(&mut self, symbol_type: &Type) -> Result<(), Diagnostic> {
+        let Some(start) = self.symbol_table.len().checked_sub(self.amount) else {
+            return Err(format!(
+                "Expected {} symbols waiting for the type '{symbol_type}'.",
+                self.amount
+            )
+            .into());
+        };
+        for symbol in &mut self.symbol_table[start..] {
+            symbol.change_type(symbol_type);
         }
-        let temp = symbol.as_token().unwrap();
+        self.amount = 0;
+        Ok(())
+    }
 
-        if temp.get_lexeme() == self.program_name {
-            return Err(format!(
-                "Use of the program name at line {} column {}.",
-                temp.get_line(),
-                temp.get_column()
+    fn find_symbol(&mut self, symbol: &Symbol) -> Result<Symbol, Diagnostic> {
+        let Some(temp) = symbol.as_token() else {
+            return Err("Wrong use of End of Scope.".into());
+        };
+
+        if temp.is_lexeme(&self.program_name) {
+            return Err(Diagnostic::at(
+                &temp,
+                format!(
+                    "Use of the program name at line {} column {}.",
+                    temp.get_line(),
+                    temp.get_column()
+                ),
             ));
         }
 
-        match self.symbol_table.iter().rfind(|item| item == &symbol) {
-            None => Err(format!(
-                "Use of the undeclared identifier '{}' at line {} column {}.",
-                temp.get_lexeme(),
-                temp.get_line(),
-                temp.get_column()
+        match self
+            .symbol_table
+            .iter()
+            .rfind(|item| item == &symbol)
+            .cloned()
+        {
+            None => Err(Diagnostic::at(
+                &temp,
+                format!(
+                    "Use of the undeclared identifier '{}' at line {} column {}.",
+                    temp.get_lexeme(),
+                    temp.get_line(),
+                    temp.get_column()
+                ),
             )),
-            Some(t) => Ok(t.to_owned()),
+            Some(t) => {
+                let declared = t.as_token().unwrap();
+                if self.strict_case && declared.get_lexeme() != temp.get_lexeme() {
+                    self.warn(
+                        &temp,
+                        format!(
+                            "Identifier '{}' at line {} column {} is spelled differently from its declaration '{}' at line {} column {}.",
+                            temp.get_lexeme(),
+                            temp.get_line(),
+                            temp.get_column(),
+                            declared.get_lexeme(),
+                            declared.get_line(),
+                            declared.get_column()
+                            ),
+                        );
+                }
+                self.references.push((temp, t.to_owned()));
+                Ok(t)
+            }
+        }
+    }
+
+    fn check_keywords_case(&mut self) {
+        let tokens: Vec<Token> = self.tokens_buffer.iter().rev().cloned().collect();
+        for token in tokens {
+            let reserved = token.is_type_of(Keyword)
+                || token.is_type_of(Boolean)
+                || token.is_lexeme("and")
+                || token.is_lexeme("or")
+                || token.is_lexeme("in");
+            let lowercase = token.get_lexeme().to_ascii_lowercase();
+            if reserved && token.get_lexeme() != lowercase {
+                self.warn(
+                    &token,
+                    format!(
+                        "Keyword '{}' at line {} column {} should be written as '{}'.",
+                        token.get_lexeme(),
+                        token.get_line(),
+                        token.get_column(),
+                        lowercase
+                    ),
+                );
+            }
+        }
+    }
+
+    fn warn(&mut self, token: &Token, message: String) {
+        let line = token.get_line();
+        let suppressed = self
+            .suppressed
+            .iter()
+            .any(|(start, end)| (*start..=*end).contains(&line));
+        if !suppressed {
+            self.warnings.push(Diagnostic::at(token, message));
+        }
+    }
+
+    // Escolhe entre as sobrecargas de uma função pré-definida pelos tipos dos argumentos na pilha,
+    // preferindo a que não exige conversões
+    fn resolve_overload(&self, symbol: &Symbol, amount: usize) -> Symbol {
+        let Some(position) = self.symbol_table.iter().rposition(|item| item == symbol) else {
+            return symbol.to_owned();
+        };
+        let start = self.symbol_table[..position]
+            .iter()
+            .rposition(|item| item == &EOS)
+            .map_or(0, |index| index + 1);
+        let end = self.symbol_table[position..]
+            .iter()
+            .position(|item| item == &EOS)
+            .map_or(self.symbol_table.len(), |index| position + index);
+        let candidates: Vec<&Symbol> = self.symbol_table[start..end]
+            .iter()
+            .filter(|item| *item == symbol && item.is_callable())
+            .filter(|item| item.accepts_arguments(amount))
+            .collect();
+
+        let arguments =
+            &self.control_type_stack[self.control_type_stack.len().saturating_sub(amount)..];
+        let matches = |exact: bool| {
+            candidates.iter().find(|candidate| {
+                candidate
+                    .parameters_for(amount)
+                    .iter()
+                    .zip(arguments)
+                    .enumerate()
+                    .all(|(index, (parameter, argument))| {
+                        match self.parameter_compatibility(parameter, argument, index) {
+                            Compatibility::Identical => true,
+                            Compatibility::Promoted(_) => !exact,
+                            Compatibility::Incompatible => false,
+                        }
+                    })
+            })
+        };
+        // Sem uma sobrecarga compatível, a última com a mesma quantidade de parâmetros é usada para
+        // apontar o argumento inválido
+        match matches(true)
+            .or_else(|| matches(false))
+            .or(candidates.last())
+        {
+            Some(candidate) => (*candidate).to_owned(),
+            None => symbol.to_owned(),
+        }
+    }
+
+    fn find_in_scope(&self, symbol: &Symbol) -> Option<usize> {
+        for (index, item) in self.symbol_table.iter().enumerate().rev() {
+            if item == &EOS {
+                return None;
+            }
+            if item == symbol {
+                return Some(index);
+            }
+        }
+        None
+    }
+
+    fn current_scope(&self) -> Vec<Symbol> {
+        let start = self
+            .symbol_table
+            .iter()
+            .rposition(|item| item == &EOS)
+            .map_or(0, |index| index + 1);
+        self.symbol_table[start..].to_vec()
+    }
+
+    fn same_parameters(expected: &[Symbol], found: &[Symbol]) -> bool {
+        expected.len() == found.len()
+            && expected
+                .iter()
+                .zip(found)
+                .all(|(a, b)| a == b && a.get_type() == b.get_type())
+    }
+
+    // Todo procedimento declarado com 'forward' precisa receber o corpo no mesmo escopo
+    fn check_forward_declarations(&self) -> Result<(), Diagnostic> {
+        match self.current_scope().iter().find(|item| item.is_forward()) {
+            None => Ok(()),
+            Some(symbol) => {
+                let token = symbol.as_token().unwrap();
+                Err(Diagnostic::at(
+                    &token,
+                    format!(
+                        "Procedure '{}' declared forward at line {} column {} was never given a body.",
+                        token.get_lexeme(),
+                        token.get_line(),
+                        token.get_column()
+                    ),
+                ))
+            }
         }
     }
 
     fn remove_scope(&mut self) {
-        loop {
-            if self.symbol_table.pop() == Some(EOS) {
+        while let Some(symbol) = self.symbol_table.pop() {
+            if symbol == EOS {
                 break;
             }
+            if self.is_declared_here(&symbol) {
+                self.declarations.push(symbol);
+            }
         }
     }
 
-    fn check_arithmetics(&mut self, line: usize, column: usize) -> Result<(), String> {
+    fn check_arithmetics(
+        &mut self,
+        operator: &str,
+        line: usize,
+        column: usize,
+    ) -> Result<(), Diagnostic> {
         let first = match self.control_type_stack.pop() {
             None => {
-                return Err(format!(
-                    "Unable to check type at line {} column {}",
-                    line, column
+                return Err(Diagnostic::new(
+                    format!("Unable to check type at line {} column {}", line, column),
+                    line,
+                    column,
                 ))
             }
-            Some(t) => t,
+            Some(t) => t.host().to_owned(),
         };
         let second = match self.control_type_stack.pop() {
             None => {
-                return Err(format!(
-                    "Unable to check type at line {} column {}",
-                    line, column
+                return Err(Diagnostic::new(
+                    format!("Unable to check type at line {} column {}", line, column),
+                    line,
+                    column,
                 ))
             }
-            Some(t) => t,
+            Some(t) => t.host().to_owned(),
         };
 
-        if (first == "integer" || first == "real") && (first == second) {
-            self.control_type_stack.push(first);
-        } else if first == "integer" && second == "real" {
-            self.control_type_stack.push(second);
-        } else if first == "real" && second == "integer" {
-            self.control_type_stack.push(first);
+        if let Some(result) = self.set_operation_type(&second, &first) {
+            // União, interseção e diferença
+            if operator == "/" {
+                return Err(Diagnostic::new(
+                    format!(
+                        "Invalid operator '/' between sets at line {} column {}.",
+                        line, column
+                    ),
+                    line,
+                    column,
+                ));
+            }
+            self.control_type_stack.push(result);
         } else {
-            return Err(format!(
-                "Invalid type between operands in arithmetic operation at line {} column {}.\n\
-                                Cannot execute arithmetic operations between '{}' and '{}'.",
-                line, column, second, first
-            ));
+            let compatibility = self.numeric_compatibility(&second, &first);
+            if !compatibility.is_compatible() {
+                return Err(Diagnostic::new(
+                    format!(
+                        "Invalid type between operands in arithmetic operation at line {} column {}.\n\
+                                    Cannot execute arithmetic operations between '{}' and '{}'.",
+                        line, column, second, first
+                    ),
+                    line,
+                    column,
+                ));
+            }
+            let result = if first == Type::Real { first } else { second };
+            self.record_conversion(compatibility, 2, line, column);
+            self.control_type_stack.push(result);
         };
 
         Ok(())
     }
 
-    fn check_relational(&mut self, line: usize, column: usize) -> Result<(), String> {
+    fn check_relational(
+        &mut self,
+        operator: &str,
+        line: usize,
+        column: usize,
+    ) -> Result<(), Diagnostic> {
         let first = match self.control_type_stack.pop() {
             None => {
-                return Err(format!(
-                    "Relational check Failed.\nUnable to check type at line {} column {}",
-                    line, column
+                return Err(Diagnostic::new(
+                    format!(
+                        "Relational check Failed.\nUnable to check type at line {} column {}",
+                        line, column
+                    ),
+                    line,
+                    column,
                 ))
             }
-            Some(t) => t,
+            Some(t) => t.host().to_owned(),
         };
         let second = match self.control_type_stack.pop() {
             None => {
-                return Err(format!(
-                    "Relational check Failed.\nUnable to check type at line {} column {}",
-                    line, column
+                return Err(Diagnostic::new(
+                    format!(
+                        "Relational check Failed.\nUnable to check type at line {} column {}",
+                        line, column
+                    ),
+                    line,
+                    column,
                 ))
             }
-            Some(t) => t,
+            Some(t) => t.host().to_owned(),
         };
 
-        if (first == "integer" || first == "real") && (second == "integer" || second == "real") {
-            self.control_type_stack.push("boolean".to_string());
+        if operator.eq_ignore_ascii_case("in") {
+            // O elemento precisa ser do tipo base do conjunto
+            let accepted = match &first {
+                Type::Set(base) => self.is_assignable(base, &second),
+                _ => first == Type::EmptySet && second.is_ordinal(),
+            };
+            if !accepted {
+                return Err(Diagnostic::new(
+                    format!(
+                        "Invalid operands for 'in' at line {} column {}.\n\
+                        Cannot check if a value of type '{}' belongs to '{}'.",
+                        line, column, second, first
+                    ),
+                    line,
+                    column,
+                ));
+            }
+            self.control_type_stack.push(Type::Boolean);
             return Ok(());
         }
 
-        Err(format!(
-            "Invalid type between operands in relational operation at line {} column {}.\n\
+        let compatibility = self.comparison_compatibility(&second, &first);
+        if !compatibility.is_compatible() {
+            return Err(Diagnostic::new(
+                format!(
+                    "Invalid type between operands in relational operation at line {} column {}.\n\
                     Cannot execute relational operations between '{}' and '{}'.",
-            line, column, second, first
-        ))
+                    line, column, second, first
+                ),
+                line,
+                column,
+            ));
+        }
+
+        if self.pointer_operation_type(&second, &first).is_some() {
+            // Ponteiros só podem ser comparados por igualdade
+            if operator != "=" && operator != "<>" {
+                return Err(Diagnostic::new(
+                    format!(
+                        "Invalid operator '{}' between pointers at line {} column {}.",
+                        operator, line, column
+                    ),
+                    line,
+                    column,
+                ));
+            }
+        } else if self.set_operation_type(&second, &first).is_some() {
+            // Igualdade, continência (<=) e contenção (>=)
+            if operator == "<" || operator == ">" {
+                return Err(Diagnostic::new(
+                    format!(
+                        "Invalid operator '{}' between sets at line {} column {}.",
+                        operator, line, column
+                    ),
+                    line,
+                    column,
+                ));
+            }
+        }
+
+        self.record_conversion(compatibility, 2, line, column);
+        self.control_type_stack.push(Type::Boolean);
+        Ok(())
     }
 
-    fn check_atribuation(&mut self, line: usize, column: usize) -> Result<(), String> {
+    fn check_atribuation(&mut self, line: usize, column: usize) -> Result<(), Diagnostic> {
         let first = match self.control_type_stack.pop() {
             None => {
-                return Err(format!(
-                    "Assignment check Failed.\nUnable to check type at line {} column {}",
-                    line, column
+                return Err(Diagnostic::new(
+                    format!(
+                        "Assignment check Failed.\nUnable to check type at line {} column {}",
+                        line, column
+                    ),
+                    line,
+                    column,
                 ))
             }
             Some(t) => t,
         };
         let second = match self.control_type_stack.pop() {
             None => {
-                return Err(format!(
-                    "Assignment check Failed.\nUnable to check type at line {} column {}",
-                    line, column
+                return Err(Diagnostic::new(
+                    format!(
+                        "Assignment check Failed.\nUnable to check type at line {} column {}",
+                        line, column
+                    ),
+                    line,
+                    column,
                 ))
             }
             Some(t) => t,
         };
-        if first != second {
-            return Err(format!(
-                "Invalid assignment at line {} column {}.\n\
+        let compatibility = self.assignment_compatibility(&second, &first);
+        if !compatibility.is_compatible() {
+            return Err(Diagnostic::new(
+                format!(
+                    "Invalid assignment at line {} column {}.\n\
                                 Cannot assign value of type '{}' to an variable of type '{}'.",
-                line, column, first, second
+                    line, column, first, second
+                ),
+                line,
+                column,
+            ));
+        }
+        self.record_conversion(compatibility, 2, line, column);
+        Ok(())
+    }
+
+    // Retorna os tipos dos argumentos, retirados da pilha de controle
+    fn check_arguments(
+        &mut self,
+        symbol: &Symbol,
+        token: &Token,
+        amount: usize,
+    ) -> Result<Vec<Type>, Diagnostic> {
+        let parameters = symbol.parameters_for(amount);
+        if !symbol.accepts_arguments(amount) {
+            return Err(Diagnostic::at(
+                token,
+                format!(
+                    "'{}' expects {}{} argument(s) but got {} at line {} column {}.",
+                    token.get_lexeme(),
+                    if symbol.is_variadic() {
+                        "at least "
+                    } else {
+                        ""
+                    },
+                    parameters.len(),
+                    amount,
+                    token.get_line(),
+                    token.get_column()
+                ),
             ));
         }
+
+        let start = self.control_type_stack.len().saturating_sub(amount);
+        let arguments = self.control_type_stack.split_off(start);
+        for (index, (parameter, argument)) in parameters.iter().zip(&arguments).enumerate() {
+            let compatibility = self.parameter_compatibility(parameter, argument, index);
+            let accepted = compatibility.is_compatible();
+            let (line, column) = (token.get_line(), token.get_column());
+            self.record_conversion(compatibility, amount, line, column);
+            if !accepted {
+                return Err(Diagnostic::at(
+                    token,
+                    format!(
+                        "Invalid argument {} of '{}' at line {} column {}.\n\
+                        Expected a value of type '{}' but got '{}'.",
+                        index + 1,
+                        token.get_lexeme(),
+                        token.get_line(),
+                        token.get_column(),
+                        parameter.type_name(),
+                        argument
+                    ),
+                ));
+            }
+        }
+        Ok(arguments)
+    }
+
+    // Tipos nomeados apontados por ponteiros são resolvidos para a sua definição
+    fn resolve_type(&self, value: Type) -> Type {
+        let Type::Named(name) = &value else {
+            return value;
+        };
+        match self.lookup(name) {
+            Some(symbol) if symbol.get_kind() == Some(SymbolKind::Type) => {
+                symbol.get_type().unwrap_or_default()
+            }
+            _ => value,
+        }
+    }
+
+    // Busca sem efeitos colaterais, usada para resolver tipos
+    fn lookup(&self, name: &str) -> Option<&Symbol> {
+        self.symbol_table
+            .iter()
+            .rev()
+            .find(|symbol| symbol.as_token().is_some_and(|token| token.is_lexeme(name)))
+    }
+
+    fn is_constant(&self, token: &Token) -> bool {
+        self.lookup(token.get_lexeme())
+            .is_some_and(|symbol| symbol.get_kind() == Some(SymbolKind::Constant))
+    }
+
+    // Avalia, sem consumir, a expressão à frente quando ela tem um valor ordinal constante
+    fn constant_ahead(&self) -> Option<(Token, i64)> {
+        let next = self.tokens_buffer.last()?;
+        let (value, length) = self.evaluate_ahead().ok()?;
+        let value = value?;
+        let ordinal = value.ordinal()?;
+        let token = Self::folded_token(next.to_owned(), &value, length);
+        Some((token, ordinal))
+    }
+
+    // Token que representa a constante nos nomes de tipos e diagnósticos: o próprio literal, o
+    // valor enumerado pelo nome ou o valor calculado das expressões e das outras constantes
+    fn folded_token(next: Token, value: &Value, length: usize) -> Token {
+        let named = next.is_type_of(Identifier) && !matches!(value, Value::Enumerated(..));
+        if length == 1 && !named {
+            next
+        } else {
+            value.as_token(next.get_line(), next.get_column())
+        }
+    }
+
+    fn check_range(&self, target: &Type, value: i64, token: &Token) -> Result<(), Diagnostic> {
+        if let Some((low, high)) = target.range() {
+            if value < low || value > high {
+                return Err(Diagnostic::at(
+                    token,
+                    format!(
+                        "Constant '{}' at line {} column {} is out of the range '{}'.",
+                        token.get_lexeme(),
+                        token.get_line(),
+                        token.get_column(),
+                        target
+                    ),
+                ));
+            }
+        }
         Ok(())
     }
 
-    fn check_logic(&mut self, line: usize, column: usize) -> Result<(), String> {
+    fn is_textual_operation(&self) -> bool {
+        let length = self.control_type_stack.len();
+        length >= 2
+            && self.control_type_stack[length - 2..]
+                .iter()
+                .all(Type::is_textual)
+    }
+
+    fn check_concatenation(&mut self) {
+        self.control_type_stack.pop();
+        self.control_type_stack.pop();
+        self.control_type_stack.push(Type::String);
+    }
+
+    fn check_logic(&mut self, line: usize, column: usize) -> Result<(), Diagnostic> {
         let first = match self.control_type_stack.pop() {
             None => {
-                return Err(format!(
-                    "Logic check Failed.\nUnable to check type at line {} column {}",
-                    line, column
+                return Err(Diagnostic::new(
+                    format!(
+                        "Logic check Failed.\nUnable to check type at line {} column {}",
+                        line, column
+                    ),
+                    line,
+                    column,
                 ))
             }
             Some(t) => t,
         };
         let second = match self.control_type_stack.pop() {
             None => {
-                return Err(format!(
-                    "Logic check Failed.\nUnable to check type at line {} column {}",
-                    line, column
+                return Err(Diagnostic::new(
+                    format!(
+                        "Logic check Failed.\nUnable to check type at line {} column {}",
+                        line, column
+                    ),
+                    line,
+                    column,
                 ))
             }
             Some(t) => t,
         };
 
-        if first != "boolean" || second != "boolean" {
-            return Err(format!(
-                "Invalid type between operands in logic operation at line {} column {}.\n\
+        if first != Type::Boolean || second != Type::Boolean {
+            return Err(Diagnostic::new(
+                format!(
+                    "Invalid type between operands in logic operation at line {} column {}.\n\
                     Cannot execute logic operations between '{}' and '{}'.",
-                line, column, second, first
+                    line, column, second, first
+                ),
+                line,
+                column,
             ));
         }
 
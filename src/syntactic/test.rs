@@ -0,0 +1,833 @@
+#[cfg(test)]
+mod syntactic_tests {
+    use crate::common::types::Type;
+    use crate::lexical::Scanner;
+    use crate::syntactic::ast::{Declaration, ExpressionKind, Statement};
+    use crate::syntactic::compatibility::Operand;
+    use crate::syntactic::Parser;
+    use std::path::Path;
+
+    fn parse(input: &str) -> Result<Parser, String> {
+        let mut scanner = Scanner::new(input);
+        let tokens = scanner.init().map_err(|e| e.to_string())?;
+        let mut parser = Parser::new(&tokens);
+        parser.check().map_err(|e| e.to_string())?;
+        Ok(parser)
+    }
+
+    fn warnings(parser: &Parser) -> Vec<&str> {
+        parser
+            .get_warnings()
+            .iter()
+            .map(|warning| warning.message.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_identifiers_ignore_case() {
+        let input = "PROGRAM teste; VAR Chave: BOOLEAN; BEGIN chave := TRUE END.";
+        assert!(parse(input).is_ok());
+    }
+
+    #[test]
+    fn test_strict_case_warnings() {
+        let input = "program teste; var Chave: boolean; BEGIN chave := true end.";
+        let mut scanner = Scanner::new(input);
+        let tokens = scanner.init().unwrap();
+        let mut parser = Parser::new(&tokens);
+        parser.set_strict_case(true);
+        parser.check().unwrap();
+        assert_eq!(parser.get_warnings().len(), 2);
+    }
+
+    #[test]
+    fn test_forward_mutual_recursion() {
+        let input = "program teste; var n: integer;
+            procedure par(x: integer); forward;
+            procedure impar(x: integer); begin if x > 0 then par(x - 1) end;
+            procedure par; begin if x > 0 then impar(x - 1) end;
+            begin par(n) end.";
+        assert!(parse(input).is_ok());
+    }
+
+    #[test]
+    fn test_forward_without_body() {
+        let input = "program teste; procedure a; forward; begin a end.";
+        let error = parse(input).err().unwrap();
+        assert!(error.contains("never given a body"));
+    }
+
+    #[test]
+    fn test_diagnostic_positions() {
+        let input = "program teste; var x: integer; begin x := y end.";
+        let tokens = Scanner::new(input).init().unwrap();
+        let error = Parser::new(&tokens).check().unwrap_err();
+        assert_eq!(error.position, Some((1, 43)));
+        assert_eq!(&input[error.span.start..error.span.end], "y");
+
+        let input = "program teste; var x: integer; begin end.";
+        let parser = parse(input).unwrap();
+        let warning = &parser.get_warnings()[0];
+        assert_eq!(warning.position, Some((1, 20)));
+        assert_eq!(&input[warning.span.start..warning.span.end], "x");
+
+        let input = "program teste; begin";
+        let tokens = Scanner::new(input).init().unwrap();
+        let error = Parser::new(&tokens).check().unwrap_err();
+        assert_eq!(error.position, None);
+    }
+
+    #[test]
+    fn test_forward_header_mismatch() {
+        let input = "program teste;
+            procedure a(x: integer); forward;
+            procedure a(x: real); begin end;
+            begin end.";
+        let error = parse(input).err().unwrap();
+        assert!(error.contains("doesn't match its forward declaration"));
+    }
+
+    #[test]
+    fn test_text_operations() {
+        let input = "program teste; var c: char; s: string; n: integer; b: boolean;
+            begin
+                c := 'a';
+                s := c + 'bc' + s;
+                s := c;
+                b := s < 'abd';
+                n := length(s) + pos('b', copy(s, 1, 2));
+                c := chr(ord(c) + 1)
+            end.";
+        assert!(parse(input).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_text_operations() {
+        let assign = "program teste; var c: char; begin c := 'abc' end.";
+        assert!(parse(assign).is_err());
+        let compare = "program teste; var b: boolean; begin b := 'a' < 1 end.";
+        assert!(parse(compare).is_err());
+        let subtract = "program teste; var s: string; begin s := s - 'a' end.";
+        assert!(parse(subtract).is_err());
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        let amount = "program teste; var n: integer; begin n := length('a', 'b') end.";
+        let error = parse(amount).err().unwrap();
+        assert!(error.contains("expects 1 argument(s) but got 2"));
+        let kind = "program teste; var c: char; begin c := chr('a') end.";
+        let error = parse(kind).err().unwrap();
+        assert!(error.contains("Invalid argument 1 of 'chr'"));
+        let range = "program teste; var c: char; begin c := chr(200 + 100) end.";
+        let error = parse(range).err().unwrap();
+        assert!(error.contains("Invalid argument 300 for 'chr' at line 1 column 40"));
+        let folded = "program teste; const a = chr(65); var c: char; begin c := a end.";
+        assert!(parse(folded).is_ok());
+        let empty = "program teste; begin writeln() end.";
+        let error = parse(empty).err().unwrap();
+        assert!(error.contains("Expected an expression. Instead got ')'"));
+    }
+
+    #[test]
+    fn test_output_procedures() {
+        let input = "program teste; type cor = (azul, verde); var c: cor;
+            begin c := azul; write('cor: ', c, 1.5, true); writeln end.";
+        assert!(parse(input).is_ok());
+        let input = "program teste; var p: ^integer; begin writeln(1, p) end.";
+        let error = parse(input).err().unwrap();
+        assert!(error.contains("Invalid argument 2 of 'writeln'"));
+        let input = "program teste; begin write end.";
+        let error = parse(input).err().unwrap();
+        assert!(error.contains("'write' expects at least 1 argument(s) but got 0"));
+
+        // Quantos argumentos forem necessários, o último parâmetro se repete
+        let arguments = ["1"; 12].join(", ");
+        let input = format!("program teste; begin write({arguments}); writeln({arguments}) end.");
+        assert!(parse(&input).is_ok());
+        let input = format!("program teste; begin writeln({arguments}, nil) end.");
+        let error = parse(&input).err().unwrap();
+        assert!(
+            error.contains("Invalid argument 13 of 'writeln'"),
+            "{error}"
+        );
+    }
+
+    const ENUMERATIONS: &str = "program teste;
+        type
+            Color = (Red, Green, Blue);
+            Digit = 0..9;
+            Warm = Red..Green;
+        var
+            c: Color; w: Warm; d: Digit; i: integer; l: 'a'..'z';
+            counts: array[Color] of integer;
+            grid: array[1..3] of array[Digit] of real;
+        ";
+
+    #[test]
+    fn test_enumerations_and_subranges() {
+        let input = format!(
+            "{ENUMERATIONS}
+            begin
+                c := Red; w := Green; c := succ(c); d := 9; l := 'z';
+                i := ord(Blue) + pred(d);
+                counts[Red] := 1; grid[2][d] := 1.5;
+                for c := Red to Blue do counts[c] := counts[c] + 1;
+                for i := 10 downto 1 do d := 0;
+                case c of
+                    Red, Green: i := 1;
+                    Blue: i := 2
+                end;
+                case l of 'a': i := 0; else i := 3 end;
+                case d of 0..3, 5: i := 0; 6..9: i := 1 end;
+                case c of Red..Green: i := 0; Blue: i := 1 end;
+                if c < Blue then i := 0
+            end."
+        );
+        assert!(parse(&input).is_ok());
+    }
+
+    // Os tipos são estruturados, um limite '.' não se confunde com o '..' do subintervalo
+    #[test]
+    fn test_type_representation() {
+        let input = "program p;
+            type
+                color = (red, green, blue);
+                node = record value: integer; next: ^node end;
+            var
+                m: array['.'..'z'] of set of red..blue;
+                n: node;
+            begin
+                m['.'] := [green]; n.next := nil
+            end.";
+        let parser = parse(input).unwrap();
+        let types: Vec<&Type> = parser
+            .get_program()
+            .unwrap()
+            .declarations
+            .iter()
+            .filter_map(|declaration| match declaration {
+                Declaration::Variable(_, variable_type) => Some(variable_type),
+                _ => None,
+            })
+            .collect();
+
+        let color = Type::Enumeration(
+            "color".to_string(),
+            vec!["red".to_string(), "green".to_string(), "blue".to_string()],
+        );
+        let elements = Type::Set(Box::new(Type::Subrange(Box::new(color), 0, 2)));
+        let index = Type::Subrange(Box::new(Type::Char), '.' as i64, 'z' as i64);
+        assert_eq!(*types[0], Type::Array(Box::new(index), Box::new(elements)));
+        assert_eq!(types[0].to_string(), "array['.'..'z'] of set of red..blue");
+
+        let next = Type::Pointer(Box::new(Type::Named("node".to_string())));
+        let fields = vec![
+            ("value".to_string(), Type::Integer),
+            ("next".to_string(), next),
+        ];
+        assert_eq!(*types[1], Type::Record(fields));
+        assert_eq!(
+            types[1].to_string(),
+            "record value: integer; next: ^node end"
+        );
+    }
+
+    #[test]
+    fn test_subrange_constant_checks() {
+        let cases = [
+            ("d := 10", "out of the range '0..9'"),
+            ("w := Blue", "out of the range 'red..green'"),
+            ("counts[Red] := grid[4][0]", "out of the range '1..3'"),
+            (
+                "case d of 1: i := 0; 1: i := 1 end",
+                "Duplicated case label",
+            ),
+            ("case d of 12: i := 0 end", "out of the range '0..9'"),
+            (
+                "case d of 1..4: i := 0; 4: i := 1 end",
+                "Duplicated case label '4'",
+            ),
+            (
+                "case d of 2..6: i := 0; 0..2: i := 1 end",
+                "Duplicated case label '0'",
+            ),
+            ("case d of 6..2: i := 0 end", "Invalid case range"),
+            (
+                "case d of 5..12: i := 0 end",
+                "'12' at line 10 column 29 is out of the range",
+            ),
+            ("case c of Red..3: i := 0 end", "Invalid case label '3'"),
+            ("case 1.5 of 1: i := 0 end", "must be of an ordinal type"),
+            ("Red := Green", "it is not a variable"),
+            ("c := 1", "Invalid assignment"),
+        ];
+        for (command, message) in cases {
+            let input = format!("{ENUMERATIONS} begin {command} end.");
+            let error = parse(&input).err().unwrap();
+            assert!(error.contains(message), "{command}: {error}");
+        }
+    }
+
+    #[test]
+    fn test_invalid_type_declarations() {
+        let cases = [
+            ("var a: array[integer] of real;", "Invalid array index type"),
+            ("var c: (Red, Green);", "must be declared in a type section"),
+            ("type Wrong = 10..1;", "greater than the upper bound"),
+            ("type Wrong = 1..'z';", "same ordinal type"),
+            (
+                "var x: real; procedure p; begin for x := 1 to 2 do end;",
+                "ordinal type",
+            ),
+        ];
+        for (declaration, message) in cases {
+            let input = format!("program teste; {declaration} begin end.");
+            let error = parse(&input).err().unwrap();
+            assert!(error.contains(message), "{declaration}: {error}");
+        }
+    }
+
+    #[test]
+    fn test_sets() {
+        let input = format!(
+            "{ENUMERATIONS}
+                s: set of Digit; colors: set of Color; letters: set of char; b: boolean;
+            begin
+                s := [1, 3..5];
+                s := s + [d] - [] * s;
+                colors := [Red..Blue];
+                letters := ['a'..'z', '_'];
+                b := (d in s) and (Red in colors);
+                b := s <= [0..9];
+                b := colors <> []
+            end."
+        );
+        assert!(parse(&input).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_sets() {
+        let cases = [
+            ("var s: set of integer;", "Invalid set base type"),
+            ("var s: set of 0..300;", "Invalid set base type"),
+            (
+                "var s: set of 0..9; begin s := [1, 'a'] end",
+                "Invalid set element",
+            ),
+            (
+                "var s: set of 0..9; begin s := ['a'] end",
+                "Invalid assignment",
+            ),
+            (
+                "var s: set of 0..9; begin s := s / s end",
+                "Invalid operator '/'",
+            ),
+            (
+                "var s: set of 0..9; b: boolean; begin b := s < s end",
+                "Invalid operator '<'",
+            ),
+            (
+                "var s: set of char; b: boolean; begin b := 1 in s end",
+                "Invalid operands for 'in'",
+            ),
+            (
+                "var s: set of 0..9; begin s := [1..20] end",
+                "Constant '20' at line 1 column 51 is out of the range '0..9'.",
+            ),
+            (
+                "const N = 5; var s: set of 0..9; begin s := [1, N * 3] end",
+                "Constant '15' at line 1 column 64 is out of the range '0..9'.",
+            ),
+            (
+                "type letras = set of 'a'..'f'; var l: letras; begin l := ['a', 'z'] end",
+                "Constant ''z'' at line 1 column 79 is out of the range ''a'..'f''.",
+            ),
+        ];
+        for (declaration, message) in cases {
+            let body = if declaration.contains("begin") {
+                ""
+            } else {
+                "begin end"
+            };
+            let input = format!("program teste; {declaration} {body}.");
+            let error = parse(&input).err().unwrap();
+            assert!(error.contains(message), "{declaration}: {error}");
+        }
+    }
+
+    const LINKED_LIST: &str = "program teste;
+        type
+            List = ^Node;
+            Node = record
+                value: integer;
+                next: List
+            end;
+        var
+            head, current: List; n: ^integer; b: boolean;
+        ";
+
+    #[test]
+    fn test_pointers() {
+        let input = format!(
+            "{LINKED_LIST}
+            begin
+                head := nil;
+                new(current);
+                current^.value := 1;
+                current^.next := head;
+                head := current;
+                b := (head <> nil) and (head^.next = nil);
+                while current <> nil do
+                    current := current^.next;
+                new(n); n^ := head^.value; dispose(n);
+                dispose(head)
+            end."
+        );
+        assert!(parse(&input).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_pointers() {
+        let cases = [
+            ("head := n", "Invalid assignment"),
+            (
+                "b := head < current",
+                "Invalid operator '<' between pointers",
+            ),
+            ("b := head = n", "Invalid type between operands"),
+            ("n := head^.value", "Invalid assignment"),
+            ("head^.size := 1", "Unknown field 'size'"),
+            ("b^ := true", "Cannot dereference"),
+            ("new(b)", "Invalid argument 1 of 'new'"),
+        ];
+        for (command, message) in cases {
+            let input = format!("{LINKED_LIST} begin {command} end.");
+            let error = parse(&input).err().unwrap();
+            assert!(error.contains(message), "{command}: {error}");
+        }
+
+        let undeclared = "program teste; type List = ^Node; begin end.";
+        let error = parse(undeclared).err().unwrap();
+        assert!(error.contains("undeclared type 'Node'"));
+    }
+
+    #[test]
+    fn test_loop_control() {
+        let input = "program teste;
+            var i, n: integer;
+            procedure busca;
+            begin
+                if n = 0 then exit
+            end;
+            begin
+                n := 0;
+                while n < 10 do
+                begin
+                    n := n + 1;
+                    if n = 3 then continue;
+                    if n = 7 then break
+                end;
+                for i := 1 to 10 do
+                    if i = n then break;
+                repeat
+                    n := n - 1;
+                    if n = 2 then break
+                until n = 0;
+                busca;
+                exit
+            end.";
+        assert!(parse(input).is_ok());
+    }
+
+    #[test]
+    fn test_loop_control_outside_loop() {
+        let input = "program teste;
+            var n: integer;
+            begin
+                n := 0;
+                if n = 0 then break
+            end.";
+        let error = parse(input).err().unwrap();
+        assert!(error.contains("'break' at line 5 column 31"), "{error}");
+
+        let input = "program teste;
+            procedure p;
+            begin
+                continue
+            end;
+            begin
+                while true do p
+            end.";
+        let error = parse(input).err().unwrap();
+        assert!(error.contains("'continue' at line 4 column 17"), "{error}");
+    }
+
+    #[test]
+    fn test_type_compatibility() {
+        let input = "program teste;
+            var x: real; i: integer; chave: boolean; s: string;
+            begin
+                x := 1;
+                chave := true;
+                chave := chave = true;
+                chave := (i < x) or (false < chave);
+                s := 'a'
+            end.";
+        let parser = parse(input).unwrap();
+        let conversions: Vec<_> = parser
+            .conversions
+            .iter()
+            .map(|c| (c.promotion.operand, c.promotion.to.to_owned(), c.line))
+            .collect();
+        assert_eq!(
+            conversions,
+            [
+                (Operand::Right, Type::Real, 4),
+                (Operand::Left, Type::Real, 7),
+                (Operand::Right, Type::String, 8)
+            ]
+        );
+
+        // Cada conversão envolve o operando convertido na árvore
+        let program = parser.get_program().unwrap();
+        let Statement::Assignment(_, value) = &program.body[0] else {
+            panic!("Expected an assignment.");
+        };
+        assert_eq!(value.value_type, Type::Real);
+        assert!(
+            matches!(&value.kind, ExpressionKind::Conversion(operand) if operand.value_type == Type::Integer)
+        );
+        let Statement::Assignment(_, value) = &program.body[3] else {
+            panic!("Expected an assignment.");
+        };
+        let ExpressionKind::Binary(_, left, _) = &value.kind else {
+            panic!("Expected an operation.");
+        };
+        let ExpressionKind::Binary(_, left, _) = &left.kind else {
+            panic!("Expected a comparison.");
+        };
+        assert!(matches!(left.kind, ExpressionKind::Conversion(_)));
+
+        let cases = [
+            ("i := 1.5", "Invalid assignment"),
+            ("chave := i = true", "relational operation"),
+            ("chave := chave or i", "logic operation"),
+        ];
+        for (command, message) in cases {
+            let input =
+                format!("program teste; var i: integer; chave: boolean; begin {command} end.");
+            let error = parse(&input).err().unwrap();
+            assert!(error.contains(message), "{command}: {error}");
+        }
+    }
+
+    #[test]
+    fn test_boolean_conditions() {
+        let cases = [
+            (
+                "while 5 do i := i + 1",
+                "'while' at line 1 column 54",
+                "integer",
+            ),
+            (
+                "if i + 1 then i := 0",
+                "'if' at line 1 column 54",
+                "integer",
+            ),
+            (
+                "repeat i := i + 1 until 'a'",
+                "'until' at line 1 column 72",
+                "char",
+            ),
+        ];
+        for (command, position, found) in cases {
+            let input =
+                format!("program teste; var i: integer; chave: boolean; begin {command} end.");
+            let error = parse(&input).err().unwrap();
+            assert!(error.contains(position), "{command}: {error}");
+            assert!(
+                error.contains(&format!("condition must be boolean, found {found}")),
+                "{command}: {error}"
+            );
+        }
+
+        let input = "program teste;
+            var i: integer; chave: boolean;
+            begin
+                while not chave do
+                    if (i > 10) or (i = 0) then chave := true else i := i + 1;
+                repeat i := i - 1 until chave = (i = 0)
+            end.";
+        let parser = parse(input).unwrap();
+        assert!(parser.control_type_stack.is_empty());
+    }
+
+    #[test]
+    fn test_prelude() {
+        let input = "program teste;
+            var i: integer; x: real; chave: boolean; c: char;
+            procedure sqr;
+            begin
+                i := 0
+            end;
+            begin
+                i := abs(-3) + trunc(x) + round(2.5) + ord(c);
+                x := abs(-1.5) + sqrt(4) + sin(x) * cos(x) + exp(1) / ln(x);
+                chave := odd(i);
+                c := chr(65);
+                sqr
+            end.";
+        let parser = parse(input).unwrap();
+        let promoted: Vec<_> = parser
+            .conversions
+            .iter()
+            .map(|c| (c.promotion.operand, c.line))
+            .collect();
+        assert_eq!(
+            promoted,
+            [(Operand::Argument(1), 9), (Operand::Argument(1), 9)]
+        );
+
+        let cases = [
+            ("i := abs(1.5)", "Invalid assignment"),
+            ("i := sqr(2) + sqrt(4)", "Invalid assignment"),
+            ("chave := odd(1.5)", "Invalid argument 1 of 'odd'"),
+            ("i := trunc('a')", "Invalid argument 1 of 'trunc'"),
+            ("x := sin(1, 2)", "expects 1 argument(s) but got 2"),
+        ];
+        for (command, message) in cases {
+            let input = format!(
+                "program teste; var i: integer; x: real; chave: boolean; begin {command} end."
+            );
+            let error = parse(&input).err().unwrap();
+            assert!(error.contains(message), "{command}: {error}");
+        }
+    }
+
+    fn parse_with_units(input: &str) -> Result<Parser, String> {
+        let units = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/units");
+        let mut scanner = Scanner::new(input);
+        let tokens = scanner.init().map_err(|e| e.to_string())?;
+        let mut parser = Parser::new(&tokens);
+        parser.set_source_path(&units.join("main.pas"));
+        parser.set_unit_paths(vec![units.join("lib"), units]);
+        parser.check().map_err(|e| e.to_string())?;
+        Ok(parser)
+    }
+
+    #[test]
+    fn test_units() {
+        let input = "program teste;
+            uses geometria, formas;
+            var p: Ponto; e: Eixo;
+            begin
+                p := origem;
+                e := horizontal;
+                mover(p, 1);
+                zerar;
+                desenhar(2)
+            end.";
+        assert!(parse_with_units(input).is_ok());
+
+        // 'c' está na mesma linha e coluna que 'Ponto' na unit geometria
+        let input = "program teste;\nuses geometria;\nvar\n    a: integer;\n    b: integer;\n    c: Ponto;\nbegin end.";
+        let mut declarations: Vec<_> = parse_with_units(input)
+            .unwrap()
+            .get_declarations()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        declarations.sort();
+        assert_eq!(declarations, ["a", "b", "c", "teste"]);
+
+        let cases = [
+            (
+                "uses geometria; begin contar end.",
+                "undeclared identifier 'contar'",
+            ),
+            (
+                "uses geometria; begin passos := 1 end.",
+                "undeclared identifier 'passos'",
+            ),
+            ("begin zerar end.", "undeclared identifier 'zerar'"),
+            (
+                "uses algebra; begin end.",
+                "Unit 'algebra' used at line 1 column 21 was not found",
+            ),
+            ("uses ciclo_a; begin end.", "ciclo_a -> ciclo_b -> ciclo_a"),
+        ];
+        for (source, message) in cases {
+            let input = format!("program teste; {source}");
+            let error = parse_with_units(&input).err().unwrap();
+            assert!(error.contains(message), "{source}: {error}");
+        }
+
+        let input = "unit teste; interface procedure p; implementation end.";
+        let error = parse(input).err().unwrap();
+        assert!(error.contains("never given a body"), "{error}");
+    }
+
+    // Os avisos de uma unit indicam o arquivo dela, as posições não são do programa
+    #[test]
+    fn test_unit_warnings() {
+        let input = "program teste;\nuses avisos;\nbegin\n    saudar\nend.";
+        let parser = parse_with_units(input).unwrap();
+        let warnings = parser.get_warnings();
+        assert_eq!(warnings.len(), 1);
+        let file = warnings[0].file.as_deref().unwrap();
+        assert!(file.ends_with("units/avisos.pas"), "{}", file.display());
+        assert!(warnings[0].message.starts_with("In unit '"));
+        assert!(warnings[0].message.ends_with(
+            "avisos.pas': Variable 'oculto' declared at line 10 column 5 is never used."
+        ));
+    }
+
+    #[test]
+    fn test_variable_warnings() {
+        let input = "program teste;
+            var a, b, r, i, z, nunca: integer; p: ^integer;
+            procedure soma(x: integer; y: real);
+            var s, t: real;
+            begin
+                s := x + t
+            end;
+            begin
+                while i <= 5 do
+                begin
+                    a := a + 1;
+                    b := b - 1;
+                    r := a + b
+                end;
+                if a > 0 then z := 1 else begin r := 2; z := 3 end;
+                case z of
+                    1: b := 0;
+                    2: exit
+                else
+                    b := 1
+                end;
+                new(p);
+                p^ := r + z + b;
+                for i := 1 to z do soma(i, p^)
+            end.";
+        let parser = parse(input).unwrap();
+        let warnings: Vec<_> = warnings(&parser)
+            .iter()
+            .map(|warning| warning.split(" at line").next().unwrap())
+            .collect();
+        assert_eq!(
+            warnings,
+            [
+                "Variable 't' is read",
+                "Parameter 'y' of procedure 'soma' declared",
+                "Variable 'i' is read",
+                "Variable 'a' is read",
+                "Variable 'b' is read",
+                "Variable 'r' is read",
+                "Variable 'nunca' declared",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dead_code_warnings() {
+        let input = "program teste;
+            var n: integer;
+            procedure usado; begin n := 1 end;
+            procedure esquecido; begin n := 2 end;
+            begin
+                usado;
+                while false do n := 3;
+                if true then n := 4 else n := 5;
+                if false then n := 6;
+                if n > 0 then exit else begin n := 7; exit end;
+                n := 8;
+                n := 9
+            end.";
+        let parser = parse(input).unwrap();
+        assert_eq!(
+            warnings(&parser),
+            [
+                "The commands after 'do' at line 7 column 29 are never executed.",
+                "The commands after 'else' at line 8 column 37 are never executed.",
+                "The commands after 'then' at line 9 column 26 are never executed.",
+                "Unreachable statement at line 11 column 17.",
+                "Procedure 'esquecido' declared at line 4 column 23 is never called.",
+            ]
+        );
+
+        let input = "program teste;
+            var n: integer;
+            {$warnings off}
+            procedure esquecido; begin n := 2 end;
+            {$WARNINGS ON}
+            begin
+                n := 1;
+                while false do n := 3
+            end.";
+        let mut scanner = Scanner::new(input);
+        let tokens = scanner.init().unwrap();
+        let mut parser = Parser::new(&tokens);
+        parser.set_directives(scanner.get_directives());
+        parser.check().unwrap();
+        assert_eq!(parser.get_warnings().len(), 1);
+        assert!(warnings(&parser)[0].contains("after 'do' at line 8"));
+    }
+
+    #[test]
+    fn test_constant_folding() {
+        let input = "program teste;
+            const N = 10; LIMITE = N * 2 - 1; PI = 3.5 - 2; NOME = 'ab' + 'c'; ATIVO = (5 > 4) and true;
+            type vetor = array[1..N] of integer; matriz = array[0..N - 1] of real;
+            var v: vetor; m: matriz; i: integer; x: real; chave: boolean;
+            begin
+                m[0] := PI; i := LIMITE; x := PI * m[N - 1]; chave := ATIVO; v[N] := i;
+                case i of N - 9: i := 0; LIMITE: i := 1 end;
+                while N < 5 do i := i + 1
+            end.";
+        let parser = parse(input).unwrap();
+        assert_eq!(
+            warnings(&parser),
+            ["The commands after 'do' at line 8 column 29 are never executed."]
+        );
+
+        let cases = [
+            ("i := i / 0", "Division by zero at line 1 column 95."),
+            (
+                "x := 1.5 / (2 - 2)",
+                "Division by zero at line 1 column 97.",
+            ),
+            (
+                "i := 2147483647 + 1",
+                "Integer overflow in the constant expression at line 1 column 104.",
+            ),
+            ("v[N + 1] := 0", "Constant '11' at line 1 column 90"),
+        ];
+        for (command, expected) in cases {
+            let input = format!(
+                "program teste; const N = 10; var v: array[1..N] of integer; i: integer; x: real; begin {command} end."
+            );
+            let error = parse(&input).err().unwrap();
+            assert!(error.contains(expected), "{command}: {error}");
+        }
+
+        let error = parse("program teste; const N = abs(2); begin end.")
+            .err()
+            .unwrap();
+        assert!(error.contains("constant 'N' at line 1 column 26 is not a constant expression"));
+        let error = parse("program teste; var v: array[1..10 - 11] of integer; begin end.")
+            .err()
+            .unwrap();
+        assert!(error.contains("lower bound '1' is greater than the upper bound '-1'"));
+
+        let error = parse("program teste; const X = 1e308 * 10; begin end.")
+            .err()
+            .unwrap();
+        assert!(error.contains("Real overflow in the constant expression at line 1 column 32."));
+        let error =
+            parse("program teste; const N = 5; var v: array[1..N] of integer; begin v := 3 end.")
+                .err()
+                .unwrap();
+        assert!(error.contains("'array[1..5] of integer'"), "{error}");
+    }
+}
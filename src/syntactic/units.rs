@@ -0,0 +1,164 @@
+use crate::common::diagnostic::Diagnostic;
+use crate::common::symbol::Symbol;
+use crate::common::token::Token;
+use crate::lexical::Scanner;
+use crate::syntactic::ast::Program;
+use crate::syntactic::Parser;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+// Localiza e guarda as units importadas, compartilhado entre o programa e as units que ele usa
+#[derive(Default)]
+pub struct UnitLoader {
+    search_paths: Vec<PathBuf>,
+    loading: Vec<String>, // Units sendo analisadas, para detectar dependências circulares
+    loaded: Vec<(String, Vec<Symbol>, Program)>, // Símbolos da interface e árvore de cada unit analisada
+}
+
+impl UnitLoader {
+    pub fn new(search_paths: Vec<PathBuf>) -> Self {
+        Self {
+            search_paths,
+            ..Self::default()
+        }
+    }
+
+    // A unit é procurada primeiro no diretório do arquivo que a usa e depois no caminho de busca
+    fn find(&self, name: &str, directory: &Path) -> Option<PathBuf> {
+        let files = [
+            format!("{name}.pas"),
+            format!("{}.pas", name.to_lowercase()),
+        ];
+        std::iter::once(directory)
+            .chain(self.search_paths.iter().map(PathBuf::as_path))
+            .flat_map(|path| files.iter().map(move |file| path.join(file)))
+            .find(|path| path.is_file())
+    }
+
+    pub(super) fn enter(&mut self, name: &str) {
+        self.loading.push(name.to_lowercase());
+    }
+
+    pub(super) fn leave(&mut self) {
+        self.loading.pop();
+    }
+}
+
+impl Parser {
+    // Analisa a unit indicada na cláusula uses e retorna os símbolos exportados pela sua interface
+    pub(super) fn import_unit(&mut self, token: &Token) -> Result<Vec<Symbol>, Diagnostic> {
+        let name = token.get_lexeme().to_lowercase();
+        {
+            let loader = self.units.borrow();
+            if let Some((_, symbols, program)) =
+                loader.loaded.iter().find(|(unit, _, _)| *unit == name)
+            {
+                self.used_units.push(program.to_owned());
+                return Ok(symbols.to_owned());
+            }
+            if loader.loading.contains(&name) {
+                let mut chain = loader.loading.clone();
+                chain.push(name);
+                return Err(Diagnostic::at(
+                    token,
+                    format!(
+                        "Circular unit dependency at line {} column {}: {}.",
+                        token.get_line(),
+                        token.get_column(),
+                        chain.join(" -> ")
+                    ),
+                ));
+            }
+        }
+
+        let path = match self
+            .units
+            .borrow()
+            .find(token.get_lexeme(), &self.directory)
+        {
+            None => {
+                return Err(Diagnostic::at(
+                    token,
+                    format!(
+                        "Unit '{}' used at line {} column {} was not found.",
+                        token.get_lexeme(),
+                        token.get_line(),
+                        token.get_column()
+                    ),
+                ))
+            }
+            Some(path) => path,
+        };
+        let input = fs::read_to_string(&path).map_err(|e| {
+            let message = format!("Unable to read unit '{}': {}", path.display(), e);
+            Diagnostic::at(token, message)
+        })?;
+        let mut scanner = Scanner::new(&input);
+        let tokens = scanner
+            .init()
+            .map_err(|e| Diagnostic::at(token, format!("In unit '{}':\n{}", path.display(), e)))?;
+
+        let mut parser = Parser::new(&tokens);
+        parser.set_directives(scanner.get_directives());
+        parser.units = Rc::clone(&self.units);
+        parser.directory = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        parser.strict_case = self.strict_case;
+        if parser.strict_case {
+            parser.check_keywords_case();
+        }
+        if !parser.peek()?.is_lexeme("unit") {
+            return Err(Diagnostic::at(
+                token,
+                format!(
+                    "'{}' used at line {} column {} is not a unit.",
+                    path.display(),
+                    token.get_line(),
+                    token.get_column()
+                ),
+            ));
+        }
+
+        let depth = self.units.borrow().loading.len();
+        let result = parser.programa();
+        self.units.borrow_mut().loading.truncate(depth);
+        result
+            .map_err(|e| Diagnostic::at(token, format!("In unit '{}':\n{}", path.display(), e)))?;
+        // A posição dos avisos é a do arquivo da unit, que fica indicado neles
+        for mut warning in parser.warnings.drain(..) {
+            if warning.file.is_none() {
+                warning.message = format!("In unit '{}': {}", path.display(), warning.message);
+                warning.file = Some(path.to_owned());
+            }
+            self.warnings.push(warning);
+        }
+
+        if !token.is_lexeme(&parser.program_name) {
+            return Err(Diagnostic::at(
+                token,
+                format!(
+                    "The file '{}' used at line {} column {} declares the unit '{}' instead of '{}'.",
+                    path.display(),
+                    token.get_line(),
+                    token.get_column(),
+                    parser.program_name,
+                    token.get_lexeme()
+                ),
+            ));
+        }
+
+        // Os símbolos reexportados de outras units mantêm a unit de origem
+        for symbol in &mut parser.exports {
+            if symbol.get_unit().is_none() {
+                symbol.set_unit(&name);
+            }
+        }
+        let program = parser.program.take().unwrap_or_default();
+        self.used_units.push(program.to_owned());
+        self.units
+            .borrow_mut()
+            .loaded
+            .push((name, parser.exports.to_owned(), program));
+        Ok(parser.exports)
+    }
+}
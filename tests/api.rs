@@ -0,0 +1,126 @@
+use compilador2::ast::{Declaration, Statement};
+use compilador2::{check, check_file, compile, parse, tokenize, Error, Target, TokenType, Type};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(name)
+}
+
+fn source(name: &str) -> String {
+    fs::read_to_string(fixture(name)).unwrap()
+}
+
+#[test]
+fn test_tokenize() {
+    let tokens = tokenize(&source("tests/Test2.pas")).unwrap();
+    assert!(tokens[0].is_lexeme("program"));
+    assert_eq!(tokens[0].get_type(), TokenType::Keyword);
+    assert_eq!((tokens[0].get_line(), tokens[0].get_column()), (1, 1));
+    assert!(tokens
+        .iter()
+        .all(|token| token.get_type() != TokenType::Comment));
+
+    let error = tokenize(&source("tests/Test1.pas")).unwrap_err();
+    let Error::Lexical(diagnostic) = &error else {
+        panic!("{error:?}");
+    };
+    assert!(diagnostic.message.contains("'&'"));
+    assert_eq!(diagnostic.position, Some((4, 2)));
+}
+
+#[test]
+fn test_parse() {
+    let tokens = tokenize(&source("tests/Test2.pas")).unwrap();
+    let program = parse(&tokens).unwrap();
+    assert!(!program.body.is_empty());
+
+    let tokens = tokenize(&source("tests/Test3.pas")).unwrap();
+    let error = parse(&tokens).unwrap_err();
+    assert!(matches!(&error, Error::Syntactic(diagnostic) if diagnostic.position == Some((5, 11))));
+
+    for name in ["tests/Test3.pas", "tests/Test4.pas"] {
+        let tokens = tokenize(&source(name)).unwrap();
+        assert!(matches!(parse(&tokens), Err(Error::Syntactic(_))), "{name}");
+    }
+}
+
+#[test]
+fn test_check() {
+    let checked = check(&source("tests/Test5.pas")).unwrap();
+    let warnings: Vec<(&str, Option<(usize, usize)>)> = checked
+        .warnings
+        .iter()
+        .map(|warning| (warning.message.as_str(), warning.position))
+        .collect();
+    assert_eq!(
+        warnings,
+        [
+            (
+                "Variable 'A' is read at line 7 column 12 before being assigned.",
+                Some((7, 12))
+            ),
+            (
+                "Variable 'B' is read at line 8 column 12 before being assigned.",
+                Some((8, 12))
+            ),
+        ]
+    );
+
+    let checked = check(&source("ola_mundo.pas")).unwrap();
+    assert_eq!(checked.program.name, "test1");
+    let procedures = checked
+        .program
+        .declarations
+        .iter()
+        .filter(|declaration| matches!(declaration, Declaration::Procedure(_)))
+        .count();
+    assert!(procedures > 0);
+    assert!(checked
+        .program
+        .body
+        .iter()
+        .any(|statement| matches!(statement, Statement::Call(name, ..) if name == "limpartela")));
+
+    let suppressed = check("{$warnings off} program p; var x: integer; begin x := x end.");
+    assert!(suppressed.unwrap().warnings.is_empty());
+}
+
+#[test]
+fn test_check_file() {
+    let checked = check_file(&fixture("tests/units/geometria.pas")).unwrap();
+    assert_eq!(checked.program.name, "geometria");
+
+    let error = check_file(&fixture("tests/units/ciclo_a.pas")).unwrap_err();
+    assert!(error.to_string().contains("Circular unit dependency"));
+
+    let missing = check_file(&fixture("tests/nao_existe.pas")).unwrap_err();
+    assert!(matches!(missing, Error::Io(_)));
+}
+
+#[test]
+fn test_types() {
+    let checked = check("program p; var v: array[1..3] of real; begin v[1] := 2 end.").unwrap();
+    let Declaration::Variable(name, variable_type) = &checked.program.declarations[0] else {
+        panic!("{:?}", checked.program.declarations);
+    };
+    let index = Type::Subrange(Box::new(Type::Integer), 1, 3);
+    assert_eq!(name, "v");
+    assert_eq!(
+        *variable_type,
+        Type::Array(Box::new(index), Box::new(Type::Real))
+    );
+    assert_eq!(variable_type.to_string(), "array[1..3] of real");
+}
+
+#[test]
+fn test_compile() {
+    let code = compile(&source("tests/fatorial.pas"), Target::C).unwrap();
+    assert!(code.contains("int main(void) {"));
+    let error = compile(&source("ola_mundo.pas"), Target::Llvm).unwrap_err();
+    assert!(matches!(&error, Error::Unsupported(message) if message.contains("'llvm' backend")));
+    let lexical = compile(&source("tests/Test1.pas"), Target::C).unwrap_err();
+    assert!(matches!(lexical, Error::Lexical(_)));
+    assert_eq!(Target::from_name("wat"), Some(Target::Wat));
+    assert_eq!(Target::Bytecode.to_string(), "bytecode");
+}
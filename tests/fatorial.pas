@@ -0,0 +1,11 @@
+program fatorial;
+var
+   i, f: integer;
+begin
+   f := 1;
+   for i := 1 to 7 do
+   begin
+      f := f * i;
+      writeln(i, '! = ', f)
+   end
+end.
@@ -0,0 +1,90 @@
+// Compara a saída do compilador para cada programa de exemplo com os arquivos esperados em
+// tests/golden. Com BLESS=1 os arquivos esperados são reescritos com a saída atual
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// Comandos comparados, cada um com um arquivo esperado de mesma extensão
+const COMMANDS: [&str; 4] = ["check", "tokens", "ast", "run"];
+
+fn root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+// ola_mundo.pas e todos os .pas de tests, inclusive os das units
+fn fixtures() -> Vec<PathBuf> {
+    let mut fixtures = vec![PathBuf::from("ola_mundo.pas")];
+    let mut directories = vec![PathBuf::from("tests")];
+    while let Some(directory) = directories.pop() {
+        for entry in fs::read_dir(root().join(&directory)).unwrap() {
+            let path = directory.join(entry.unwrap().file_name());
+            if root().join(&path).is_dir() {
+                if !path.ends_with("golden") {
+                    directories.push(path);
+                }
+            } else if path.extension().is_some_and(|extension| extension == "pas") {
+                fixtures.push(path);
+            }
+        }
+    }
+    fixtures.sort();
+    fixtures
+}
+
+// Código de saída, saída padrão e saída de erros do compilador, com caminhos relativos ao projeto
+fn snapshot(command: &str, fixture: &Path) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_compilador2"))
+        .current_dir(root())
+        .arg(command)
+        .arg("--unit-path=tests/units")
+        .arg(fixture)
+        .output()
+        .unwrap();
+    format!(
+        "exit: {}\n--- stdout\n{}--- stderr\n{}",
+        output.status.code().unwrap_or(-1),
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    )
+}
+
+// tests/Test1.pas tem os arquivos esperados tests/golden/Test1.<comando>
+fn expected(fixture: &Path, extension: &str) -> PathBuf {
+    let relative = fixture.strip_prefix("tests").unwrap_or(fixture);
+    root()
+        .join("tests/golden")
+        .join(relative)
+        .with_extension(extension)
+}
+
+#[test]
+fn test_golden_files() {
+    let bless = env::var_os("BLESS").is_some_and(|value| value != "0");
+    let mut mismatches = vec![];
+    for fixture in fixtures() {
+        for command in COMMANDS {
+            let actual = snapshot(command, &fixture);
+            let path = expected(&fixture, command);
+            if bless {
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(&path, &actual).unwrap();
+                continue;
+            }
+            match fs::read_to_string(&path) {
+                Ok(expected) if expected == actual => {}
+                Ok(expected) => mismatches.push(format!(
+                    "{}:\n=== expected\n{expected}=== actual\n{actual}",
+                    path.display()
+                )),
+                Err(_) => mismatches.push(format!("{}: missing", path.display())),
+            }
+        }
+    }
+    assert!(
+        mismatches.is_empty(),
+        "{} golden files differ, run with BLESS=1 to update them.\n\n{}",
+        mismatches.len(),
+        mismatches.join("\n")
+    );
+}
@@ -0,0 +1,17 @@
+unit avisos;
+
+interface
+
+procedure saudar;
+
+implementation
+
+var
+    oculto: integer;
+
+procedure saudar;
+begin
+    writeln('ola')
+end;
+
+end.
@@ -0,0 +1,9 @@
+unit ciclo_a;
+
+interface
+
+uses ciclo_b;
+
+implementation
+
+end.
@@ -0,0 +1,9 @@
+unit ciclo_b;
+
+interface
+
+uses ciclo_a;
+
+implementation
+
+end.
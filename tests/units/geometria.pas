@@ -0,0 +1,39 @@
+unit geometria;
+
+interface
+
+type
+    Ponto = record x, y: real end;
+    Eixo = (horizontal, vertical);
+
+var
+    origem: Ponto;
+
+procedure mover(p: Ponto; dx: real);
+procedure zerar;
+
+implementation
+
+var
+    passos: integer;
+
+procedure contar;
+begin
+    passos := passos + 1
+end;
+
+procedure mover;
+begin
+    p.x := p.x + dx;
+    contar
+end;
+
+procedure zerar;
+begin
+    origem.x := 0;
+    origem.y := 0
+end;
+
+begin
+    passos := 0
+end.
@@ -0,0 +1,17 @@
+unit formas;
+
+interface
+
+uses geometria;
+
+procedure desenhar(lado: integer);
+
+implementation
+
+procedure desenhar;
+begin
+    zerar;
+    mover(origem, lado)
+end;
+
+end.